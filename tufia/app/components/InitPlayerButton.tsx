@@ -3,7 +3,7 @@ import { Button } from "@chakra-ui/react"
 import { SystemProgram } from "@solana/web3.js"
 import { useConnection, useWallet } from "@solana/wallet-adapter-react"
 import { useGameState } from "@/contexts/GameStateProvider"
-import { GAME_DATA_SEED, gameDataPDA, program } from "@/utils/anchor"
+import { CLASS_WARRIOR, configPDA, program } from "@/utils/anchor"
 
 const InitPlayerButton = () => {
   const { publicKey, sendTransaction } = useWallet()
@@ -19,10 +19,10 @@ const InitPlayerButton = () => {
 
     try {
       const transaction = await program.methods
-        .initPlayer(GAME_DATA_SEED)
+        .initPlayer(CLASS_WARRIOR)
         .accounts({
           player: playerDataPDA,
-          gameData: gameDataPDA,
+          config: configPDA,
           signer: publicKey,
           systemProgram: SystemProgram.programId,
         })
@@ -0,0 +1,81 @@
+import Image from "next/image"
+import { useCallback, useState } from "react"
+import { Button, VStack } from "@chakra-ui/react"
+import { SystemProgram } from "@solana/web3.js"
+import { useConnection, useWallet } from "@solana/wallet-adapter-react"
+import { useGameState } from "@/contexts/GameStateProvider"
+import {
+  configPDA,
+  directoryPDA,
+  floorPDA,
+  program,
+  routeToInstance,
+} from "@/utils/anchor"
+
+const JoinFloorButton = () => {
+  const { publicKey, sendTransaction } = useWallet()
+  const { connection } = useConnection()
+  const { gameState, playerDataPDA, season } = useGameState()
+  const [isLoading, setIsLoading] = useState(false)
+  const [transactionCounter, setTransactionCounter] = useState(0)
+
+  // Joins the floor the player is on. The directory decides the instance.
+  const onJoinClick = useCallback(async () => {
+    if (!publicKey || !playerDataPDA || !gameState || season == null) return
+
+    setIsLoading(true)
+    setTransactionCounter(transactionCounter + 1)
+
+    try {
+      const floorId = gameState.currentFloor
+      const directory = directoryPDA(season, floorId)
+      const instances = await program.account.floorDirectory
+        .fetchNullable(directory)
+        .then((data) => (data ? data.instances : []))
+      const instance = routeToInstance(instances)
+      // The instance the player is on now, they are taken off it
+      const oldGameData = floorPDA(season, floorId, gameState.currentInstance)
+      const oldGameDataExists =
+        (await connection.getAccountInfo(oldGameData)) != null
+
+      const transaction = await program.methods
+        .buyNextFloor({ floorId, instance }, transactionCounter)
+        .accounts({
+          sessionToken: null,
+          player: playerDataPDA,
+          config: configPDA,
+          directory,
+          gameData: floorPDA(season, floorId, instance),
+          oldGameData: oldGameDataExists ? oldGameData : null,
+          party: null,
+          signer: publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .transaction()
+
+      const txSig = await sendTransaction(transaction, connection, {
+        skipPreflight: true,
+      })
+      console.log(`https://explorer.solana.com/tx/${txSig}?cluster=devnet`)
+    } catch (error: any) {
+      console.log("error", `Joining the floor failed! ${error?.message}`)
+    } finally {
+      setIsLoading(false)
+    }
+  }, [publicKey, playerDataPDA, gameState, season, connection, transactionCounter])
+
+  return (
+    <>
+      {publicKey && gameState && (
+        <VStack>
+          <Image src="/Beaver.png" alt="Energy Icon" width={64} height={64} />
+          <Button isLoading={isLoading} onClick={onJoinClick} width="175px">
+            Join floor {gameState.currentFloor}
+          </Button>
+        </VStack>
+      )}
+    </>
+  )
+}
+
+export default JoinFloorButton
@@ -6,19 +6,21 @@ import {
   PlayerData,
   MAX_ENERGY,
   TIME_TO_REFILL_ENERGY,
-  GameData,
-  GAME_DATA_SEED,
+  FloorData,
+  configPDA,
+  floorPDA,
 } from "@/utils/anchor"
-import { BN } from "@coral-xyz/anchor"
 
 const GameStateContext = createContext<{
   playerDataPDA: PublicKey | null  
   gameState: PlayerData | null
+  season: number | null
   nextEnergyIn: number
   totalWoodAvailable: number | null
 }>({
   playerDataPDA: null,
   gameState: null,
+  season: null,
   nextEnergyIn: 0,
   totalWoodAvailable: 0
 })
@@ -38,8 +40,21 @@ export const GameStateProvider = ({
   const [timePassed, setTimePassed] = useState<any>([])
   const [nextEnergyIn, setEnergyNextIn] = useState<number>(0)
   const [gameDataPDA, setGameDataPDA] = useState<PublicKey | null>(null)
-  const [gameData, setGameData] = useState<GameData | null>(null)
+  const [gameData, setGameData] = useState<FloorData | null>(null)
   const [totalWoodAvailable, setTotalWoodAvailable] = useState<number | null>(0)
+  const [season, setSeason] = useState<number | null>(null)
+
+  // Floor accounts are per season
+  useEffect(() => {
+    program.account.gameConfig
+      .fetch(configPDA)
+      .then((config) => {
+        setSeason(config.season)
+      })
+      .catch((error) => {
+        console.log("No game config found", error)
+      })
+  }, [])
 
   useEffect(() => {
     setPlayerState(null)
@@ -47,7 +62,7 @@ export const GameStateProvider = ({
       return
     }
     const [pda] = PublicKey.findProgramAddressSync(
-      [Buffer.from("player", "utf8"), publicKey.toBuffer()],
+      [Buffer.from("player1", "utf8"), publicKey.toBuffer()],
       program.programId
     )
     setPlayerData(pda)
@@ -66,33 +81,37 @@ export const GameStateProvider = ({
     })
   }, [publicKey])
 
+  // The floor the player joined with buyNextFloor
+  const currentFloor = playerState?.currentFloor
+  const currentInstance = playerState?.currentInstance
+
   useEffect(() => {
     setGameData(null)
-    if (!publicKey) {
+    if (!publicKey || season == null || currentFloor == null || currentInstance == null) {
       return
     }
-    const [pda] = PublicKey.findProgramAddressSync(
-      [Buffer.from(GAME_DATA_SEED, "utf8")],
-      program.programId
-    )
-    setGameDataPDA(gameDataPDA)
+    const pda = floorPDA(season, currentFloor, currentInstance)
+    setGameDataPDA(pda)
 
-    program.account.gameData
+    program.account.floorData
       .fetch(pda)
       .then((data) => {
         setGameData(data)
         setTotalWoodAvailable(data.totalWoodCollected.toNumber());
       })
       .catch((error) => {
-        window.alert("No game data found, please init!")
+        window.alert("No floor data found, please join a floor!")
       })
 
-    connection.onAccountChange(pda, (account) => {
-      const newGameData = program.coder.accounts.decode("gameData", account.data)
+    const subscription = connection.onAccountChange(pda, (account) => {
+      const newGameData = program.coder.accounts.decode("floorData", account.data)
       setGameData(newGameData);
       setTotalWoodAvailable(newGameData.totalWoodCollected.toNumber());
     })
-  }, [publicKey])
+    return () => {
+      connection.removeAccountChangeListener(subscription)
+    }
+  }, [publicKey, season, currentFloor, currentInstance])
 
 
   useEffect(() => {
@@ -100,7 +119,7 @@ export const GameStateProvider = ({
       if (
         playerState == null ||
         playerState.lastLogin == undefined ||
-        playerState.energy.toNumber() >= MAX_ENERGY
+        playerState.energy >= MAX_ENERGY
       ) {
         return;
       }
@@ -109,8 +128,8 @@ export const GameStateProvider = ({
       const currentTime = Date.now();
       let timePassed = (currentTime - lastLoginTime) / 1000;
     
-      while (timePassed >= TIME_TO_REFILL_ENERGY.toNumber() && playerState.energy.toNumber() < MAX_ENERGY) {
-        playerState.energy = playerState.energy.add(new BN(1));
+      while (timePassed >= TIME_TO_REFILL_ENERGY.toNumber() && playerState.energy < MAX_ENERGY) {
+        playerState.energy = playerState.energy + 1;
         playerState.lastLogin = playerState.lastLogin.add(TIME_TO_REFILL_ENERGY);
         timePassed -= TIME_TO_REFILL_ENERGY.toNumber();
       }
@@ -129,6 +148,7 @@ export const GameStateProvider = ({
       value={{
         playerDataPDA,
         gameState: playerState,
+        season,
         nextEnergyIn,
         totalWoodAvailable,
       }}
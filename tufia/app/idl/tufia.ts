@@ -11,8 +11,8 @@ export type Tufia = {
           "isSigner": false
         },
         {
-          "name": "gameData",
-          "isMut": true,
+          "name": "config",
+          "isMut": false,
           "isSigner": false
         },
         {
@@ -28,13 +28,13 @@ export type Tufia = {
       ],
       "args": [
         {
-          "name": "levelSeed",
-          "type": "string"
+          "name": "class",
+          "type": "u8"
         }
       ]
     },
     {
-      "name": "chopTree",
+      "name": "moveToTile",
       "accounts": [
         {
           "name": "sessionToken",
@@ -47,11 +47,28 @@ export type Tufia = {
           "isMut": true,
           "isSigner": false
         },
+        {
+          "name": "config",
+          "isMut": false,
+          "isSigner": false
+        },
         {
           "name": "gameData",
           "isMut": true,
           "isSigner": false
         },
+        {
+          "name": "leaderboard",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "party",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true
+        },
         {
           "name": "signer",
           "isMut": true,
@@ -65,101 +82,126 @@ export type Tufia = {
       ],
       "args": [
         {
-          "name": "levelSeed",
-          "type": "string"
+          "name": "floor",
+          "type": {
+            "defined": "FloorAddress"
+          }
         },
         {
           "name": "counter",
           "type": "u16"
+        },
+        {
+          "name": "x",
+          "type": "u64"
+        },
+        {
+          "name": "y",
+          "type": "u64"
         }
       ]
-    }
-  ],
-  "accounts": [
-    {
-      "name": "gameData",
-      "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "totalWoodCollected",
-            "type": "u64"
-          }
-        ]
-      }
     },
     {
-      "name": "playerData",
-      "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "authority",
-            "type": "publicKey"
-          },
-          {
-            "name": "name",
-            "type": "string"
-          },
-          {
-            "name": "level",
-            "type": "u8"
-          },
-          {
-            "name": "xp",
-            "type": "u64"
-          },
-          {
-            "name": "wood",
-            "type": "u64"
-          },
-          {
-            "name": "energy",
-            "type": "u64"
-          },
-          {
-            "name": "lastLogin",
-            "type": "i64"
-          },
-          {
-            "name": "lastId",
-            "type": "u16"
+      "name": "moveToNextFloor",
+      "accounts": [
+        {
+          "name": "sessionToken",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "player",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "config",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "gameData",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "directory",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "leaderboard",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "signer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "floor",
+          "type": {
+            "defined": "FloorAddress"
           }
-        ]
-      }
-    }
-  ],
-  "errors": [
-    {
-      "code": 6000,
-      "name": "NotEnoughEnergy",
-      "msg": "Not enough energy"
+        },
+        {
+          "name": "counter",
+          "type": "u16"
+        }
+      ]
     },
     {
-      "code": 6001,
-      "name": "WrongAuthority",
-      "msg": "Wrong Authority"
-    }
-  ]
-};
-
-export const IDL: Tufia = {
-  "version": "0.1.0",
-  "name": "tufia",
-  "instructions": [
-    {
-      "name": "initPlayer",
+      "name": "buyNextFloor",
       "accounts": [
+        {
+          "name": "sessionToken",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true
+        },
         {
           "name": "player",
           "isMut": true,
           "isSigner": false
         },
+        {
+          "name": "config",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "directory",
+          "isMut": true,
+          "isSigner": false
+        },
         {
           "name": "gameData",
           "isMut": true,
           "isSigner": false
         },
+        {
+          "name": "oldGameData",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "party",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true
+        },
         {
           "name": "signer",
           "isMut": true,
@@ -173,13 +215,19 @@ export const IDL: Tufia = {
       ],
       "args": [
         {
-          "name": "levelSeed",
-          "type": "string"
+          "name": "floor",
+          "type": {
+            "defined": "FloorAddress"
+          }
+        },
+        {
+          "name": "counter",
+          "type": "u16"
         }
       ]
     },
     {
-      "name": "chopTree",
+      "name": "resetFloor",
       "accounts": [
         {
           "name": "sessionToken",
@@ -192,6 +240,11 @@ export const IDL: Tufia = {
           "isMut": true,
           "isSigner": false
         },
+        {
+          "name": "config",
+          "isMut": false,
+          "isSigner": false
+        },
         {
           "name": "gameData",
           "isMut": true,
@@ -210,8 +263,10 @@ export const IDL: Tufia = {
       ],
       "args": [
         {
-          "name": "levelSeed",
-          "type": "string"
+          "name": "floor",
+          "type": {
+            "defined": "FloorAddress"
+          }
         },
         {
           "name": "counter",
@@ -222,13 +277,133 @@ export const IDL: Tufia = {
   ],
   "accounts": [
     {
-      "name": "gameData",
+      "name": "floorData",
       "type": {
         "kind": "struct",
         "fields": [
+          {
+            "name": "idCounter",
+            "type": "u64"
+          },
+          {
+            "name": "actionIndex",
+            "type": "u8"
+          },
           {
             "name": "totalWoodCollected",
             "type": "u64"
+          },
+          {
+            "name": "floorId",
+            "type": "u32"
+          },
+          {
+            "name": "owner",
+            "type": "publicKey"
+          },
+          {
+            "name": "mode",
+            "type": "u8"
+          },
+          {
+            "name": "players",
+            "type": {
+              "array": [
+                {
+                  "defined": "PlayerSlot"
+                },
+                29
+              ]
+            }
+          },
+          {
+            "name": "tiles",
+            "type": {
+              "array": [
+                {
+                  "array": [
+                    {
+                      "defined": "PackedTile"
+                    },
+                    10
+                  ]
+                },
+                10
+              ]
+            }
+          },
+          {
+            "name": "actions",
+            "type": {
+              "array": [
+                {
+                  "defined": "PackedAction"
+                },
+                20
+              ]
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "floorDirectory",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "season",
+            "type": "u16"
+          },
+          {
+            "name": "floorId",
+            "type": "u32"
+          },
+          {
+            "name": "instances",
+            "type": {
+              "vec": "u8"
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "gameConfig",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "admin",
+            "type": "publicKey"
+          },
+          {
+            "name": "bossFloorInterval",
+            "type": "u16"
+          },
+          {
+            "name": "season",
+            "type": "u16"
+          },
+          {
+            "name": "itemUri",
+            "type": "string"
+          },
+          {
+            "name": "recipes",
+            "type": {
+              "vec": {
+                "defined": "Recipe"
+              }
+            }
+          },
+          {
+            "name": "armorRegenPerMove",
+            "type": "u32"
+          },
+          {
+            "name": "healthRegenSeconds",
+            "type": "i64"
           }
         ]
       }
@@ -248,19 +423,39 @@ export const IDL: Tufia = {
           },
           {
             "name": "level",
-            "type": "u8"
+            "type": "u32"
           },
           {
             "name": "xp",
-            "type": "u64"
+            "type": "u32"
           },
           {
-            "name": "wood",
-            "type": "u64"
+            "name": "health",
+            "type": "u32"
+          },
+          {
+            "name": "maxHealth",
+            "type": "u32"
+          },
+          {
+            "name": "damage",
+            "type": "u32"
+          },
+          {
+            "name": "defence",
+            "type": "u32"
+          },
+          {
+            "name": "swords",
+            "type": "u32"
+          },
+          {
+            "name": "shields",
+            "type": "u32"
           },
           {
             "name": "energy",
-            "type": "u64"
+            "type": "u32"
           },
           {
             "name": "lastLogin",
@@ -269,13 +464,2285 @@ export const IDL: Tufia = {
           {
             "name": "lastId",
             "type": "u16"
-          }
-        ]
-      }
-    }
-  ],
-  "errors": [
-    {
+          },
+          {
+            "name": "currentFloor",
+            "type": "u16"
+          },
+          {
+            "name": "tileData",
+            "type": {
+              "defined": "TileData2"
+            }
+          },
+          {
+            "name": "keys",
+            "type": "u32"
+          },
+          {
+            "name": "shrineDamageBonus",
+            "type": "u32"
+          },
+          {
+            "name": "shrineBuffMoves",
+            "type": "u32"
+          },
+          {
+            "name": "healthPotions",
+            "type": "u32"
+          },
+          {
+            "name": "encounter",
+            "type": {
+              "defined": "Encounter"
+            }
+          },
+          {
+            "name": "critChance",
+            "type": "u8"
+          },
+          {
+            "name": "critMultiplier",
+            "type": "u8"
+          },
+          {
+            "name": "evasion",
+            "type": "u8"
+          },
+          {
+            "name": "damageRange",
+            "type": "u8"
+          },
+          {
+            "name": "class",
+            "type": "u8"
+          },
+          {
+            "name": "maxArmor",
+            "type": "u32"
+          },
+          {
+            "name": "statPoints",
+            "type": "u16"
+          },
+          {
+            "name": "skillPoints",
+            "type": "u16"
+          },
+          {
+            "name": "skills",
+            "type": "u32"
+          },
+          {
+            "name": "avatar",
+            "type": "u8"
+          },
+          {
+            "name": "title",
+            "type": "string"
+          },
+          {
+            "name": "stats",
+            "type": {
+              "defined": "LifetimeStats"
+            }
+          },
+          {
+            "name": "season",
+            "type": "u16"
+          },
+          {
+            "name": "seasonStats",
+            "type": {
+              "defined": "LifetimeStats"
+            }
+          },
+          {
+            "name": "badges",
+            "type": {
+              "vec": {
+                "defined": "SeasonBadge"
+              }
+            }
+          },
+          {
+            "name": "party",
+            "type": "publicKey"
+          },
+          {
+            "name": "guild",
+            "type": "publicKey"
+          },
+          {
+            "name": "inventory",
+            "type": {
+              "vec": {
+                "defined": "InventoryItem"
+              }
+            }
+          },
+          {
+            "name": "gear",
+            "type": {
+              "vec": {
+                "defined": "GearItem"
+              }
+            }
+          },
+          {
+            "name": "buffs",
+            "type": {
+              "vec": {
+                "defined": "Buff"
+              }
+            }
+          },
+          {
+            "name": "lastHeal",
+            "type": "i64"
+          },
+          {
+            "name": "currentInstance",
+            "type": "u16"
+          },
+          {
+            "name": "rngNonce",
+            "type": "u64"
+          }
+        ]
+      }
+    }
+  ],
+  "types": [
+    {
+      "name": "Encounter",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "active",
+            "type": "bool"
+          },
+          {
+            "name": "enemyX",
+            "type": "u8"
+          },
+          {
+            "name": "enemyY",
+            "type": "u8"
+          },
+          {
+            "name": "round",
+            "type": "u16"
+          },
+          {
+            "name": "abilityUsed",
+            "type": "bool"
+          },
+          {
+            "name": "floor",
+            "type": "publicKey"
+          }
+        ]
+      }
+    },
+    {
+      "name": "FloorAddress",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "floorId",
+            "type": "u32"
+          },
+          {
+            "name": "instance",
+            "type": "u16"
+          }
+        ]
+      }
+    },
+    {
+      "name": "GameAction",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "actionId",
+            "type": "u64"
+          },
+          {
+            "name": "actionType",
+            "type": "u8"
+          },
+          {
+            "name": "fromX",
+            "type": "u8"
+          },
+          {
+            "name": "fromY",
+            "type": "u8"
+          },
+          {
+            "name": "toX",
+            "type": "u8"
+          },
+          {
+            "name": "toY",
+            "type": "u8"
+          },
+          {
+            "name": "tile",
+            "type": {
+              "defined": "TileData"
+            }
+          },
+          {
+            "name": "amount",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "GearItem",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "rarity",
+            "type": "u8"
+          },
+          {
+            "name": "damage",
+            "type": "u32"
+          },
+          {
+            "name": "defence",
+            "type": "u32"
+          },
+          {
+            "name": "armor",
+            "type": "u32"
+          },
+          {
+            "name": "floor",
+            "type": "u16"
+          },
+          {
+            "name": "equipped",
+            "type": "bool"
+          },
+          {
+            "name": "tier",
+            "type": "u8"
+          },
+          {
+            "name": "status",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "Buff",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "kind",
+            "type": "u8"
+          },
+          {
+            "name": "amount",
+            "type": "u32"
+          },
+          {
+            "name": "expiresAt",
+            "type": "i64"
+          },
+          {
+            "name": "movesLeft",
+            "type": "u16"
+          }
+        ]
+      }
+    },
+    {
+      "name": "InventoryItem",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "itemId",
+            "type": "u16"
+          },
+          {
+            "name": "amount",
+            "type": "u32"
+          }
+        ]
+      }
+    },
+    {
+      "name": "LifetimeStats",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "maxFloor",
+            "type": "u16"
+          },
+          {
+            "name": "kills",
+            "type": "u32"
+          },
+          {
+            "name": "deaths",
+            "type": "u32"
+          },
+          {
+            "name": "chestsOpened",
+            "type": "u32"
+          }
+        ]
+      }
+    },
+    {
+      "name": "PackedAction",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "actionId",
+            "type": "u64"
+          },
+          {
+            "name": "actionType",
+            "type": "u8"
+          },
+          {
+            "name": "from",
+            "type": "u8"
+          },
+          {
+            "name": "to",
+            "type": "u8"
+          },
+          {
+            "name": "tile",
+            "type": {
+              "defined": "PackedTile"
+            }
+          },
+          {
+            "name": "amount",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "PackedTile",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "tileType",
+            "type": "u8"
+          },
+          {
+            "name": "entity",
+            "type": "u8"
+          },
+          {
+            "name": "level",
+            "type": "u16"
+          },
+          {
+            "name": "xp",
+            "type": "u32"
+          },
+          {
+            "name": "damage",
+            "type": "u32"
+          },
+          {
+            "name": "defence",
+            "type": "u32"
+          },
+          {
+            "name": "armor",
+            "type": "u32"
+          },
+          {
+            "name": "maxArmor",
+            "type": "u32"
+          },
+          {
+            "name": "health",
+            "type": "u32"
+          },
+          {
+            "name": "maxHealth",
+            "type": "u32"
+          },
+          {
+            "name": "critChance",
+            "type": "u8"
+          },
+          {
+            "name": "critMultiplier",
+            "type": "u8"
+          },
+          {
+            "name": "evasion",
+            "type": "u8"
+          },
+          {
+            "name": "damageRange",
+            "type": "u8"
+          },
+          {
+            "name": "class",
+            "type": "u8"
+          },
+          {
+            "name": "status",
+            "type": "u8"
+          },
+          {
+            "name": "statusTurns",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "PlayerSlot",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "player",
+            "type": "publicKey"
+          },
+          {
+            "name": "avatar",
+            "type": "u8"
+          },
+          {
+            "name": "name",
+            "type": {
+              "array": [
+                "u8",
+                12
+              ]
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "Recipe",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "materials",
+            "type": {
+              "vec": {
+                "defined": "InventoryItem"
+              }
+            }
+          },
+          {
+            "name": "result",
+            "type": {
+              "defined": "GearItem"
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "SeasonBadge",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "season",
+            "type": "u16"
+          },
+          {
+            "name": "rank",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "TileData",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "tileType",
+            "type": "u8"
+          },
+          {
+            "name": "tileLevel",
+            "type": "u32"
+          },
+          {
+            "name": "tileOwner",
+            "type": "publicKey"
+          },
+          {
+            "name": "tileXp",
+            "type": "u32"
+          },
+          {
+            "name": "tileDamage",
+            "type": "u32"
+          },
+          {
+            "name": "tileDefence",
+            "type": "u32"
+          },
+          {
+            "name": "tileArmor",
+            "type": "u32"
+          },
+          {
+            "name": "tileMaxArmor",
+            "type": "u32"
+          },
+          {
+            "name": "tileHealth",
+            "type": "u32"
+          },
+          {
+            "name": "tileMaxHealth",
+            "type": "u32"
+          },
+          {
+            "name": "tileCritChance",
+            "type": "u8"
+          },
+          {
+            "name": "tileCritMultiplier",
+            "type": "u8"
+          },
+          {
+            "name": "tileEvasion",
+            "type": "u8"
+          },
+          {
+            "name": "tileDamageRange",
+            "type": "u8"
+          },
+          {
+            "name": "tileClass",
+            "type": "u8"
+          },
+          {
+            "name": "tileAvatar",
+            "type": "u8"
+          },
+          {
+            "name": "tileName",
+            "type": {
+              "array": [
+                "u8",
+                12
+              ]
+            }
+          },
+          {
+            "name": "tileStatus",
+            "type": "u8"
+          },
+          {
+            "name": "tileStatusTurns",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "TileData2",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "tileType",
+            "type": "u8"
+          },
+          {
+            "name": "tileLevel",
+            "type": "u32"
+          },
+          {
+            "name": "tileOwner",
+            "type": "publicKey"
+          },
+          {
+            "name": "tileXp",
+            "type": "u32"
+          },
+          {
+            "name": "tileDamage",
+            "type": "u32"
+          },
+          {
+            "name": "tileDefence",
+            "type": "u32"
+          },
+          {
+            "name": "tileArmor",
+            "type": "u32"
+          },
+          {
+            "name": "tileMaxArmor",
+            "type": "u32"
+          },
+          {
+            "name": "tileHealth",
+            "type": "u32"
+          },
+          {
+            "name": "tileMaxHealth",
+            "type": "u32"
+          },
+          {
+            "name": "tileCritChance",
+            "type": "u8"
+          },
+          {
+            "name": "tileCritMultiplier",
+            "type": "u8"
+          },
+          {
+            "name": "tileEvasion",
+            "type": "u8"
+          },
+          {
+            "name": "tileDamageRange",
+            "type": "u8"
+          },
+          {
+            "name": "tileClass",
+            "type": "u8"
+          },
+          {
+            "name": "tileAvatar",
+            "type": "u8"
+          },
+          {
+            "name": "tileName",
+            "type": {
+              "array": [
+                "u8",
+                12
+              ]
+            }
+          },
+          {
+            "name": "tileStatus",
+            "type": "u8"
+          },
+          {
+            "name": "tileStatusTurns",
+            "type": "u8"
+          }
+        ]
+      }
+    }
+  ],
+  "errors": [
+    {
+      "code": 6000,
+      "name": "NotEnoughEnergy",
+      "msg": "Not enough energy"
+    },
+    {
+      "code": 6001,
+      "name": "WrongAuthority",
+      "msg": "Wrong Authority"
+    },
+    {
+      "code": 6002,
+      "name": "PlayerNotOnBoard",
+      "msg": "Player not on board"
+    },
+    {
+      "code": 6003,
+      "name": "OutOfBounds",
+      "msg": "Out of bounds"
+    },
+    {
+      "code": 6004,
+      "name": "PlayerAlreadyExists",
+      "msg": "PlayerAlreadyExists"
+    },
+    {
+      "code": 6005,
+      "name": "BoardIsFull",
+      "msg": "BoardIsFull"
+    },
+    {
+      "code": 6006,
+      "name": "PlayerIsAlreadyOnThisTile",
+      "msg": "PlayerIsAlreadyOnThisTile"
+    },
+    {
+      "code": 6007,
+      "name": "DoorIsLocked",
+      "msg": "DoorIsLocked"
+    },
+    {
+      "code": 6008,
+      "name": "WrongAdmin",
+      "msg": "WrongAdmin"
+    },
+    {
+      "code": 6009,
+      "name": "NotABossFloor",
+      "msg": "NotABossFloor"
+    },
+    {
+      "code": 6010,
+      "name": "NotNextToBoss",
+      "msg": "NotNextToBoss"
+    },
+    {
+      "code": 6011,
+      "name": "BossAlreadyDefeated",
+      "msg": "BossAlreadyDefeated"
+    },
+    {
+      "code": 6012,
+      "name": "BossNotDefeated",
+      "msg": "BossNotDefeated"
+    },
+    {
+      "code": 6013,
+      "name": "TooManyBossContributors",
+      "msg": "TooManyBossContributors"
+    },
+    {
+      "code": 6014,
+      "name": "NoBossDamageDealt",
+      "msg": "NoBossDamageDealt"
+    },
+    {
+      "code": 6015,
+      "name": "BossRewardAlreadyClaimed",
+      "msg": "BossRewardAlreadyClaimed"
+    },
+    {
+      "code": 6016,
+      "name": "InCombat",
+      "msg": "InCombat"
+    },
+    {
+      "code": 6017,
+      "name": "NotInCombat",
+      "msg": "NotInCombat"
+    },
+    {
+      "code": 6018,
+      "name": "InvalidCombatAction",
+      "msg": "InvalidCombatAction"
+    },
+    {
+      "code": 6019,
+      "name": "NoConsumables",
+      "msg": "NoConsumables"
+    },
+    {
+      "code": 6020,
+      "name": "InvalidClass",
+      "msg": "InvalidClass"
+    },
+    {
+      "code": 6021,
+      "name": "NoClassAbility",
+      "msg": "NoClassAbility"
+    },
+    {
+      "code": 6022,
+      "name": "AbilityAlreadyUsed",
+      "msg": "AbilityAlreadyUsed"
+    },
+    {
+      "code": 6023,
+      "name": "NotEnoughStatPoints",
+      "msg": "NotEnoughStatPoints"
+    },
+    {
+      "code": 6024,
+      "name": "NotEnoughSkillPoints",
+      "msg": "NotEnoughSkillPoints"
+    },
+    {
+      "code": 6025,
+      "name": "InvalidSkill",
+      "msg": "InvalidSkill"
+    },
+    {
+      "code": 6026,
+      "name": "SkillAlreadyLearned",
+      "msg": "SkillAlreadyLearned"
+    },
+    {
+      "code": 6027,
+      "name": "SkillRequirementMissing",
+      "msg": "SkillRequirementMissing"
+    },
+    {
+      "code": 6028,
+      "name": "InvalidName",
+      "msg": "InvalidName"
+    },
+    {
+      "code": 6029,
+      "name": "InvalidTitle",
+      "msg": "InvalidTitle"
+    },
+    {
+      "code": 6030,
+      "name": "InvalidAvatar",
+      "msg": "InvalidAvatar"
+    },
+    {
+      "code": 6031,
+      "name": "NameTaken",
+      "msg": "NameTaken"
+    },
+    {
+      "code": 6032,
+      "name": "WrongNameRegistry",
+      "msg": "WrongNameRegistry"
+    },
+    {
+      "code": 6033,
+      "name": "InvalidLeaderboardMetric",
+      "msg": "InvalidLeaderboardMetric"
+    },
+    {
+      "code": 6034,
+      "name": "WrongSeason",
+      "msg": "WrongSeason"
+    },
+    {
+      "code": 6035,
+      "name": "NoSeasonReward",
+      "msg": "NoSeasonReward"
+    },
+    {
+      "code": 6036,
+      "name": "SeasonRewardAlreadyClaimed",
+      "msg": "SeasonRewardAlreadyClaimed"
+    },
+    {
+      "code": 6037,
+      "name": "WrongDay",
+      "msg": "WrongDay"
+    },
+    {
+      "code": 6038,
+      "name": "DailyRunFinished",
+      "msg": "DailyRunFinished"
+    },
+    {
+      "code": 6039,
+      "name": "NotNextToStairs",
+      "msg": "NotNextToStairs"
+    },
+    {
+      "code": 6040,
+      "name": "DailyNotOver",
+      "msg": "DailyNotOver"
+    },
+    {
+      "code": 6041,
+      "name": "NoDailyReward",
+      "msg": "NoDailyReward"
+    },
+    {
+      "code": 6042,
+      "name": "DailyRewardAlreadyClaimed",
+      "msg": "DailyRewardAlreadyClaimed"
+    },
+    {
+      "code": 6043,
+      "name": "WrongFloor",
+      "msg": "WrongFloor"
+    },
+    {
+      "code": 6044,
+      "name": "WrongInstance",
+      "msg": "WrongInstance"
+    },
+    {
+      "code": 6045,
+      "name": "TooManyInstances",
+      "msg": "TooManyInstances"
+    },
+    {
+      "code": 6046,
+      "name": "InstanceFull",
+      "msg": "InstanceFull"
+    },
+    {
+      "code": 6047,
+      "name": "InvalidPartyRule",
+      "msg": "InvalidPartyRule"
+    },
+    {
+      "code": 6048,
+      "name": "PartyFull",
+      "msg": "PartyFull"
+    },
+    {
+      "code": 6049,
+      "name": "AlreadyInParty",
+      "msg": "AlreadyInParty"
+    },
+    {
+      "code": 6050,
+      "name": "NotInvited",
+      "msg": "NotInvited"
+    },
+    {
+      "code": 6051,
+      "name": "NotInParty",
+      "msg": "NotInParty"
+    },
+    {
+      "code": 6052,
+      "name": "NotPartyLeader",
+      "msg": "NotPartyLeader"
+    },
+    {
+      "code": 6053,
+      "name": "WrongParty",
+      "msg": "WrongParty"
+    },
+    {
+      "code": 6054,
+      "name": "FriendlyFire",
+      "msg": "FriendlyFire"
+    },
+    {
+      "code": 6055,
+      "name": "AlreadyInGuild",
+      "msg": "AlreadyInGuild"
+    },
+    {
+      "code": 6056,
+      "name": "NotInGuild",
+      "msg": "NotInGuild"
+    },
+    {
+      "code": 6057,
+      "name": "GuildFull",
+      "msg": "GuildFull"
+    },
+    {
+      "code": 6058,
+      "name": "NotGuildOfficer",
+      "msg": "NotGuildOfficer"
+    },
+    {
+      "code": 6059,
+      "name": "NotGuildLeader",
+      "msg": "NotGuildLeader"
+    },
+    {
+      "code": 6060,
+      "name": "InvalidGuildRole",
+      "msg": "InvalidGuildRole"
+    },
+    {
+      "code": 6061,
+      "name": "InvalidShop",
+      "msg": "InvalidShop"
+    },
+    {
+      "code": 6062,
+      "name": "ShopFull",
+      "msg": "ShopFull"
+    },
+    {
+      "code": 6063,
+      "name": "ItemNotForSale",
+      "msg": "ItemNotForSale"
+    },
+    {
+      "code": 6064,
+      "name": "OutOfStock",
+      "msg": "OutOfStock"
+    },
+    {
+      "code": 6065,
+      "name": "NotAtShop",
+      "msg": "NotAtShop"
+    },
+    {
+      "code": 6066,
+      "name": "InventoryFull",
+      "msg": "InventoryFull"
+    },
+    {
+      "code": 6067,
+      "name": "NotEnoughItems",
+      "msg": "NotEnoughItems"
+    },
+    {
+      "code": 6068,
+      "name": "GearFull",
+      "msg": "GearFull"
+    },
+    {
+      "code": 6069,
+      "name": "InvalidItem",
+      "msg": "InvalidItem"
+    },
+    {
+      "code": 6070,
+      "name": "InvalidItemUri",
+      "msg": "InvalidItemUri"
+    },
+    {
+      "code": 6071,
+      "name": "ItemEquipped",
+      "msg": "ItemEquipped"
+    },
+    {
+      "code": 6072,
+      "name": "TooManyEquipped",
+      "msg": "TooManyEquipped"
+    },
+    {
+      "code": 6073,
+      "name": "TradeTooLarge",
+      "msg": "TradeTooLarge"
+    },
+    {
+      "code": 6074,
+      "name": "OfferExpired",
+      "msg": "OfferExpired"
+    },
+    {
+      "code": 6075,
+      "name": "NotOfferTaker",
+      "msg": "NotOfferTaker"
+    },
+    {
+      "code": 6076,
+      "name": "InvalidRecipe",
+      "msg": "InvalidRecipe"
+    },
+    {
+      "code": 6077,
+      "name": "MaxTierReached",
+      "msg": "MaxTierReached"
+    },
+    {
+      "code": 6078,
+      "name": "NotConsumable",
+      "msg": "NotConsumable"
+    },
+    {
+      "code": 6079,
+      "name": "TooManyBuffs",
+      "msg": "TooManyBuffs"
+    },
+    {
+      "code": 6080,
+      "name": "NotSafeToRest",
+      "msg": "NotSafeToRest"
+    },
+    {
+      "code": 6081,
+      "name": "ShopOverflow",
+      "msg": "ShopOverflow"
+    },
+    {
+      "code": 6082,
+      "name": "StatOverflow",
+      "msg": "StatOverflow"
+    }
+  ]
+};
+
+export const IDL: Tufia = {
+  "version": "0.1.0",
+  "name": "tufia",
+  "instructions": [
+    {
+      "name": "initPlayer",
+      "accounts": [
+        {
+          "name": "player",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "config",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "signer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "class",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "moveToTile",
+      "accounts": [
+        {
+          "name": "sessionToken",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "player",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "config",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "gameData",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "leaderboard",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "party",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "signer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "floor",
+          "type": {
+            "defined": "FloorAddress"
+          }
+        },
+        {
+          "name": "counter",
+          "type": "u16"
+        },
+        {
+          "name": "x",
+          "type": "u64"
+        },
+        {
+          "name": "y",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "moveToNextFloor",
+      "accounts": [
+        {
+          "name": "sessionToken",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "player",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "config",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "gameData",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "directory",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "leaderboard",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "signer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "floor",
+          "type": {
+            "defined": "FloorAddress"
+          }
+        },
+        {
+          "name": "counter",
+          "type": "u16"
+        }
+      ]
+    },
+    {
+      "name": "buyNextFloor",
+      "accounts": [
+        {
+          "name": "sessionToken",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "player",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "config",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "directory",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "gameData",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "oldGameData",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "party",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "signer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "floor",
+          "type": {
+            "defined": "FloorAddress"
+          }
+        },
+        {
+          "name": "counter",
+          "type": "u16"
+        }
+      ]
+    },
+    {
+      "name": "resetFloor",
+      "accounts": [
+        {
+          "name": "sessionToken",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "player",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "config",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "gameData",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "signer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "floor",
+          "type": {
+            "defined": "FloorAddress"
+          }
+        },
+        {
+          "name": "counter",
+          "type": "u16"
+        }
+      ]
+    }
+  ],
+  "accounts": [
+    {
+      "name": "floorData",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "idCounter",
+            "type": "u64"
+          },
+          {
+            "name": "actionIndex",
+            "type": "u8"
+          },
+          {
+            "name": "totalWoodCollected",
+            "type": "u64"
+          },
+          {
+            "name": "floorId",
+            "type": "u32"
+          },
+          {
+            "name": "owner",
+            "type": "publicKey"
+          },
+          {
+            "name": "mode",
+            "type": "u8"
+          },
+          {
+            "name": "players",
+            "type": {
+              "array": [
+                {
+                  "defined": "PlayerSlot"
+                },
+                29
+              ]
+            }
+          },
+          {
+            "name": "tiles",
+            "type": {
+              "array": [
+                {
+                  "array": [
+                    {
+                      "defined": "PackedTile"
+                    },
+                    10
+                  ]
+                },
+                10
+              ]
+            }
+          },
+          {
+            "name": "actions",
+            "type": {
+              "array": [
+                {
+                  "defined": "PackedAction"
+                },
+                20
+              ]
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "floorDirectory",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "season",
+            "type": "u16"
+          },
+          {
+            "name": "floorId",
+            "type": "u32"
+          },
+          {
+            "name": "instances",
+            "type": {
+              "vec": "u8"
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "gameConfig",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "admin",
+            "type": "publicKey"
+          },
+          {
+            "name": "bossFloorInterval",
+            "type": "u16"
+          },
+          {
+            "name": "season",
+            "type": "u16"
+          },
+          {
+            "name": "itemUri",
+            "type": "string"
+          },
+          {
+            "name": "recipes",
+            "type": {
+              "vec": {
+                "defined": "Recipe"
+              }
+            }
+          },
+          {
+            "name": "armorRegenPerMove",
+            "type": "u32"
+          },
+          {
+            "name": "healthRegenSeconds",
+            "type": "i64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "playerData",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "authority",
+            "type": "publicKey"
+          },
+          {
+            "name": "name",
+            "type": "string"
+          },
+          {
+            "name": "level",
+            "type": "u32"
+          },
+          {
+            "name": "xp",
+            "type": "u32"
+          },
+          {
+            "name": "health",
+            "type": "u32"
+          },
+          {
+            "name": "maxHealth",
+            "type": "u32"
+          },
+          {
+            "name": "damage",
+            "type": "u32"
+          },
+          {
+            "name": "defence",
+            "type": "u32"
+          },
+          {
+            "name": "swords",
+            "type": "u32"
+          },
+          {
+            "name": "shields",
+            "type": "u32"
+          },
+          {
+            "name": "energy",
+            "type": "u32"
+          },
+          {
+            "name": "lastLogin",
+            "type": "i64"
+          },
+          {
+            "name": "lastId",
+            "type": "u16"
+          },
+          {
+            "name": "currentFloor",
+            "type": "u16"
+          },
+          {
+            "name": "tileData",
+            "type": {
+              "defined": "TileData2"
+            }
+          },
+          {
+            "name": "keys",
+            "type": "u32"
+          },
+          {
+            "name": "shrineDamageBonus",
+            "type": "u32"
+          },
+          {
+            "name": "shrineBuffMoves",
+            "type": "u32"
+          },
+          {
+            "name": "healthPotions",
+            "type": "u32"
+          },
+          {
+            "name": "encounter",
+            "type": {
+              "defined": "Encounter"
+            }
+          },
+          {
+            "name": "critChance",
+            "type": "u8"
+          },
+          {
+            "name": "critMultiplier",
+            "type": "u8"
+          },
+          {
+            "name": "evasion",
+            "type": "u8"
+          },
+          {
+            "name": "damageRange",
+            "type": "u8"
+          },
+          {
+            "name": "class",
+            "type": "u8"
+          },
+          {
+            "name": "maxArmor",
+            "type": "u32"
+          },
+          {
+            "name": "statPoints",
+            "type": "u16"
+          },
+          {
+            "name": "skillPoints",
+            "type": "u16"
+          },
+          {
+            "name": "skills",
+            "type": "u32"
+          },
+          {
+            "name": "avatar",
+            "type": "u8"
+          },
+          {
+            "name": "title",
+            "type": "string"
+          },
+          {
+            "name": "stats",
+            "type": {
+              "defined": "LifetimeStats"
+            }
+          },
+          {
+            "name": "season",
+            "type": "u16"
+          },
+          {
+            "name": "seasonStats",
+            "type": {
+              "defined": "LifetimeStats"
+            }
+          },
+          {
+            "name": "badges",
+            "type": {
+              "vec": {
+                "defined": "SeasonBadge"
+              }
+            }
+          },
+          {
+            "name": "party",
+            "type": "publicKey"
+          },
+          {
+            "name": "guild",
+            "type": "publicKey"
+          },
+          {
+            "name": "inventory",
+            "type": {
+              "vec": {
+                "defined": "InventoryItem"
+              }
+            }
+          },
+          {
+            "name": "gear",
+            "type": {
+              "vec": {
+                "defined": "GearItem"
+              }
+            }
+          },
+          {
+            "name": "buffs",
+            "type": {
+              "vec": {
+                "defined": "Buff"
+              }
+            }
+          },
+          {
+            "name": "lastHeal",
+            "type": "i64"
+          },
+          {
+            "name": "currentInstance",
+            "type": "u16"
+          },
+          {
+            "name": "rngNonce",
+            "type": "u64"
+          }
+        ]
+      }
+    }
+  ],
+  "types": [
+    {
+      "name": "Encounter",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "active",
+            "type": "bool"
+          },
+          {
+            "name": "enemyX",
+            "type": "u8"
+          },
+          {
+            "name": "enemyY",
+            "type": "u8"
+          },
+          {
+            "name": "round",
+            "type": "u16"
+          },
+          {
+            "name": "abilityUsed",
+            "type": "bool"
+          },
+          {
+            "name": "floor",
+            "type": "publicKey"
+          }
+        ]
+      }
+    },
+    {
+      "name": "FloorAddress",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "floorId",
+            "type": "u32"
+          },
+          {
+            "name": "instance",
+            "type": "u16"
+          }
+        ]
+      }
+    },
+    {
+      "name": "GameAction",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "actionId",
+            "type": "u64"
+          },
+          {
+            "name": "actionType",
+            "type": "u8"
+          },
+          {
+            "name": "fromX",
+            "type": "u8"
+          },
+          {
+            "name": "fromY",
+            "type": "u8"
+          },
+          {
+            "name": "toX",
+            "type": "u8"
+          },
+          {
+            "name": "toY",
+            "type": "u8"
+          },
+          {
+            "name": "tile",
+            "type": {
+              "defined": "TileData"
+            }
+          },
+          {
+            "name": "amount",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "GearItem",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "rarity",
+            "type": "u8"
+          },
+          {
+            "name": "damage",
+            "type": "u32"
+          },
+          {
+            "name": "defence",
+            "type": "u32"
+          },
+          {
+            "name": "armor",
+            "type": "u32"
+          },
+          {
+            "name": "floor",
+            "type": "u16"
+          },
+          {
+            "name": "equipped",
+            "type": "bool"
+          },
+          {
+            "name": "tier",
+            "type": "u8"
+          },
+          {
+            "name": "status",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "Buff",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "kind",
+            "type": "u8"
+          },
+          {
+            "name": "amount",
+            "type": "u32"
+          },
+          {
+            "name": "expiresAt",
+            "type": "i64"
+          },
+          {
+            "name": "movesLeft",
+            "type": "u16"
+          }
+        ]
+      }
+    },
+    {
+      "name": "InventoryItem",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "itemId",
+            "type": "u16"
+          },
+          {
+            "name": "amount",
+            "type": "u32"
+          }
+        ]
+      }
+    },
+    {
+      "name": "LifetimeStats",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "maxFloor",
+            "type": "u16"
+          },
+          {
+            "name": "kills",
+            "type": "u32"
+          },
+          {
+            "name": "deaths",
+            "type": "u32"
+          },
+          {
+            "name": "chestsOpened",
+            "type": "u32"
+          }
+        ]
+      }
+    },
+    {
+      "name": "PackedAction",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "actionId",
+            "type": "u64"
+          },
+          {
+            "name": "actionType",
+            "type": "u8"
+          },
+          {
+            "name": "from",
+            "type": "u8"
+          },
+          {
+            "name": "to",
+            "type": "u8"
+          },
+          {
+            "name": "tile",
+            "type": {
+              "defined": "PackedTile"
+            }
+          },
+          {
+            "name": "amount",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "PackedTile",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "tileType",
+            "type": "u8"
+          },
+          {
+            "name": "entity",
+            "type": "u8"
+          },
+          {
+            "name": "level",
+            "type": "u16"
+          },
+          {
+            "name": "xp",
+            "type": "u32"
+          },
+          {
+            "name": "damage",
+            "type": "u32"
+          },
+          {
+            "name": "defence",
+            "type": "u32"
+          },
+          {
+            "name": "armor",
+            "type": "u32"
+          },
+          {
+            "name": "maxArmor",
+            "type": "u32"
+          },
+          {
+            "name": "health",
+            "type": "u32"
+          },
+          {
+            "name": "maxHealth",
+            "type": "u32"
+          },
+          {
+            "name": "critChance",
+            "type": "u8"
+          },
+          {
+            "name": "critMultiplier",
+            "type": "u8"
+          },
+          {
+            "name": "evasion",
+            "type": "u8"
+          },
+          {
+            "name": "damageRange",
+            "type": "u8"
+          },
+          {
+            "name": "class",
+            "type": "u8"
+          },
+          {
+            "name": "status",
+            "type": "u8"
+          },
+          {
+            "name": "statusTurns",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "PlayerSlot",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "player",
+            "type": "publicKey"
+          },
+          {
+            "name": "avatar",
+            "type": "u8"
+          },
+          {
+            "name": "name",
+            "type": {
+              "array": [
+                "u8",
+                12
+              ]
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "Recipe",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "materials",
+            "type": {
+              "vec": {
+                "defined": "InventoryItem"
+              }
+            }
+          },
+          {
+            "name": "result",
+            "type": {
+              "defined": "GearItem"
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "SeasonBadge",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "season",
+            "type": "u16"
+          },
+          {
+            "name": "rank",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "TileData",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "tileType",
+            "type": "u8"
+          },
+          {
+            "name": "tileLevel",
+            "type": "u32"
+          },
+          {
+            "name": "tileOwner",
+            "type": "publicKey"
+          },
+          {
+            "name": "tileXp",
+            "type": "u32"
+          },
+          {
+            "name": "tileDamage",
+            "type": "u32"
+          },
+          {
+            "name": "tileDefence",
+            "type": "u32"
+          },
+          {
+            "name": "tileArmor",
+            "type": "u32"
+          },
+          {
+            "name": "tileMaxArmor",
+            "type": "u32"
+          },
+          {
+            "name": "tileHealth",
+            "type": "u32"
+          },
+          {
+            "name": "tileMaxHealth",
+            "type": "u32"
+          },
+          {
+            "name": "tileCritChance",
+            "type": "u8"
+          },
+          {
+            "name": "tileCritMultiplier",
+            "type": "u8"
+          },
+          {
+            "name": "tileEvasion",
+            "type": "u8"
+          },
+          {
+            "name": "tileDamageRange",
+            "type": "u8"
+          },
+          {
+            "name": "tileClass",
+            "type": "u8"
+          },
+          {
+            "name": "tileAvatar",
+            "type": "u8"
+          },
+          {
+            "name": "tileName",
+            "type": {
+              "array": [
+                "u8",
+                12
+              ]
+            }
+          },
+          {
+            "name": "tileStatus",
+            "type": "u8"
+          },
+          {
+            "name": "tileStatusTurns",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "TileData2",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "tileType",
+            "type": "u8"
+          },
+          {
+            "name": "tileLevel",
+            "type": "u32"
+          },
+          {
+            "name": "tileOwner",
+            "type": "publicKey"
+          },
+          {
+            "name": "tileXp",
+            "type": "u32"
+          },
+          {
+            "name": "tileDamage",
+            "type": "u32"
+          },
+          {
+            "name": "tileDefence",
+            "type": "u32"
+          },
+          {
+            "name": "tileArmor",
+            "type": "u32"
+          },
+          {
+            "name": "tileMaxArmor",
+            "type": "u32"
+          },
+          {
+            "name": "tileHealth",
+            "type": "u32"
+          },
+          {
+            "name": "tileMaxHealth",
+            "type": "u32"
+          },
+          {
+            "name": "tileCritChance",
+            "type": "u8"
+          },
+          {
+            "name": "tileCritMultiplier",
+            "type": "u8"
+          },
+          {
+            "name": "tileEvasion",
+            "type": "u8"
+          },
+          {
+            "name": "tileDamageRange",
+            "type": "u8"
+          },
+          {
+            "name": "tileClass",
+            "type": "u8"
+          },
+          {
+            "name": "tileAvatar",
+            "type": "u8"
+          },
+          {
+            "name": "tileName",
+            "type": {
+              "array": [
+                "u8",
+                12
+              ]
+            }
+          },
+          {
+            "name": "tileStatus",
+            "type": "u8"
+          },
+          {
+            "name": "tileStatusTurns",
+            "type": "u8"
+          }
+        ]
+      }
+    }
+  ],
+  "errors": [
+    {
       "code": 6000,
       "name": "NotEnoughEnergy",
       "msg": "Not enough energy"
@@ -284,6 +2751,411 @@ export const IDL: Tufia = {
       "code": 6001,
       "name": "WrongAuthority",
       "msg": "Wrong Authority"
+    },
+    {
+      "code": 6002,
+      "name": "PlayerNotOnBoard",
+      "msg": "Player not on board"
+    },
+    {
+      "code": 6003,
+      "name": "OutOfBounds",
+      "msg": "Out of bounds"
+    },
+    {
+      "code": 6004,
+      "name": "PlayerAlreadyExists",
+      "msg": "PlayerAlreadyExists"
+    },
+    {
+      "code": 6005,
+      "name": "BoardIsFull",
+      "msg": "BoardIsFull"
+    },
+    {
+      "code": 6006,
+      "name": "PlayerIsAlreadyOnThisTile",
+      "msg": "PlayerIsAlreadyOnThisTile"
+    },
+    {
+      "code": 6007,
+      "name": "DoorIsLocked",
+      "msg": "DoorIsLocked"
+    },
+    {
+      "code": 6008,
+      "name": "WrongAdmin",
+      "msg": "WrongAdmin"
+    },
+    {
+      "code": 6009,
+      "name": "NotABossFloor",
+      "msg": "NotABossFloor"
+    },
+    {
+      "code": 6010,
+      "name": "NotNextToBoss",
+      "msg": "NotNextToBoss"
+    },
+    {
+      "code": 6011,
+      "name": "BossAlreadyDefeated",
+      "msg": "BossAlreadyDefeated"
+    },
+    {
+      "code": 6012,
+      "name": "BossNotDefeated",
+      "msg": "BossNotDefeated"
+    },
+    {
+      "code": 6013,
+      "name": "TooManyBossContributors",
+      "msg": "TooManyBossContributors"
+    },
+    {
+      "code": 6014,
+      "name": "NoBossDamageDealt",
+      "msg": "NoBossDamageDealt"
+    },
+    {
+      "code": 6015,
+      "name": "BossRewardAlreadyClaimed",
+      "msg": "BossRewardAlreadyClaimed"
+    },
+    {
+      "code": 6016,
+      "name": "InCombat",
+      "msg": "InCombat"
+    },
+    {
+      "code": 6017,
+      "name": "NotInCombat",
+      "msg": "NotInCombat"
+    },
+    {
+      "code": 6018,
+      "name": "InvalidCombatAction",
+      "msg": "InvalidCombatAction"
+    },
+    {
+      "code": 6019,
+      "name": "NoConsumables",
+      "msg": "NoConsumables"
+    },
+    {
+      "code": 6020,
+      "name": "InvalidClass",
+      "msg": "InvalidClass"
+    },
+    {
+      "code": 6021,
+      "name": "NoClassAbility",
+      "msg": "NoClassAbility"
+    },
+    {
+      "code": 6022,
+      "name": "AbilityAlreadyUsed",
+      "msg": "AbilityAlreadyUsed"
+    },
+    {
+      "code": 6023,
+      "name": "NotEnoughStatPoints",
+      "msg": "NotEnoughStatPoints"
+    },
+    {
+      "code": 6024,
+      "name": "NotEnoughSkillPoints",
+      "msg": "NotEnoughSkillPoints"
+    },
+    {
+      "code": 6025,
+      "name": "InvalidSkill",
+      "msg": "InvalidSkill"
+    },
+    {
+      "code": 6026,
+      "name": "SkillAlreadyLearned",
+      "msg": "SkillAlreadyLearned"
+    },
+    {
+      "code": 6027,
+      "name": "SkillRequirementMissing",
+      "msg": "SkillRequirementMissing"
+    },
+    {
+      "code": 6028,
+      "name": "InvalidName",
+      "msg": "InvalidName"
+    },
+    {
+      "code": 6029,
+      "name": "InvalidTitle",
+      "msg": "InvalidTitle"
+    },
+    {
+      "code": 6030,
+      "name": "InvalidAvatar",
+      "msg": "InvalidAvatar"
+    },
+    {
+      "code": 6031,
+      "name": "NameTaken",
+      "msg": "NameTaken"
+    },
+    {
+      "code": 6032,
+      "name": "WrongNameRegistry",
+      "msg": "WrongNameRegistry"
+    },
+    {
+      "code": 6033,
+      "name": "InvalidLeaderboardMetric",
+      "msg": "InvalidLeaderboardMetric"
+    },
+    {
+      "code": 6034,
+      "name": "WrongSeason",
+      "msg": "WrongSeason"
+    },
+    {
+      "code": 6035,
+      "name": "NoSeasonReward",
+      "msg": "NoSeasonReward"
+    },
+    {
+      "code": 6036,
+      "name": "SeasonRewardAlreadyClaimed",
+      "msg": "SeasonRewardAlreadyClaimed"
+    },
+    {
+      "code": 6037,
+      "name": "WrongDay",
+      "msg": "WrongDay"
+    },
+    {
+      "code": 6038,
+      "name": "DailyRunFinished",
+      "msg": "DailyRunFinished"
+    },
+    {
+      "code": 6039,
+      "name": "NotNextToStairs",
+      "msg": "NotNextToStairs"
+    },
+    {
+      "code": 6040,
+      "name": "DailyNotOver",
+      "msg": "DailyNotOver"
+    },
+    {
+      "code": 6041,
+      "name": "NoDailyReward",
+      "msg": "NoDailyReward"
+    },
+    {
+      "code": 6042,
+      "name": "DailyRewardAlreadyClaimed",
+      "msg": "DailyRewardAlreadyClaimed"
+    },
+    {
+      "code": 6043,
+      "name": "WrongFloor",
+      "msg": "WrongFloor"
+    },
+    {
+      "code": 6044,
+      "name": "WrongInstance",
+      "msg": "WrongInstance"
+    },
+    {
+      "code": 6045,
+      "name": "TooManyInstances",
+      "msg": "TooManyInstances"
+    },
+    {
+      "code": 6046,
+      "name": "InstanceFull",
+      "msg": "InstanceFull"
+    },
+    {
+      "code": 6047,
+      "name": "InvalidPartyRule",
+      "msg": "InvalidPartyRule"
+    },
+    {
+      "code": 6048,
+      "name": "PartyFull",
+      "msg": "PartyFull"
+    },
+    {
+      "code": 6049,
+      "name": "AlreadyInParty",
+      "msg": "AlreadyInParty"
+    },
+    {
+      "code": 6050,
+      "name": "NotInvited",
+      "msg": "NotInvited"
+    },
+    {
+      "code": 6051,
+      "name": "NotInParty",
+      "msg": "NotInParty"
+    },
+    {
+      "code": 6052,
+      "name": "NotPartyLeader",
+      "msg": "NotPartyLeader"
+    },
+    {
+      "code": 6053,
+      "name": "WrongParty",
+      "msg": "WrongParty"
+    },
+    {
+      "code": 6054,
+      "name": "FriendlyFire",
+      "msg": "FriendlyFire"
+    },
+    {
+      "code": 6055,
+      "name": "AlreadyInGuild",
+      "msg": "AlreadyInGuild"
+    },
+    {
+      "code": 6056,
+      "name": "NotInGuild",
+      "msg": "NotInGuild"
+    },
+    {
+      "code": 6057,
+      "name": "GuildFull",
+      "msg": "GuildFull"
+    },
+    {
+      "code": 6058,
+      "name": "NotGuildOfficer",
+      "msg": "NotGuildOfficer"
+    },
+    {
+      "code": 6059,
+      "name": "NotGuildLeader",
+      "msg": "NotGuildLeader"
+    },
+    {
+      "code": 6060,
+      "name": "InvalidGuildRole",
+      "msg": "InvalidGuildRole"
+    },
+    {
+      "code": 6061,
+      "name": "InvalidShop",
+      "msg": "InvalidShop"
+    },
+    {
+      "code": 6062,
+      "name": "ShopFull",
+      "msg": "ShopFull"
+    },
+    {
+      "code": 6063,
+      "name": "ItemNotForSale",
+      "msg": "ItemNotForSale"
+    },
+    {
+      "code": 6064,
+      "name": "OutOfStock",
+      "msg": "OutOfStock"
+    },
+    {
+      "code": 6065,
+      "name": "NotAtShop",
+      "msg": "NotAtShop"
+    },
+    {
+      "code": 6066,
+      "name": "InventoryFull",
+      "msg": "InventoryFull"
+    },
+    {
+      "code": 6067,
+      "name": "NotEnoughItems",
+      "msg": "NotEnoughItems"
+    },
+    {
+      "code": 6068,
+      "name": "GearFull",
+      "msg": "GearFull"
+    },
+    {
+      "code": 6069,
+      "name": "InvalidItem",
+      "msg": "InvalidItem"
+    },
+    {
+      "code": 6070,
+      "name": "InvalidItemUri",
+      "msg": "InvalidItemUri"
+    },
+    {
+      "code": 6071,
+      "name": "ItemEquipped",
+      "msg": "ItemEquipped"
+    },
+    {
+      "code": 6072,
+      "name": "TooManyEquipped",
+      "msg": "TooManyEquipped"
+    },
+    {
+      "code": 6073,
+      "name": "TradeTooLarge",
+      "msg": "TradeTooLarge"
+    },
+    {
+      "code": 6074,
+      "name": "OfferExpired",
+      "msg": "OfferExpired"
+    },
+    {
+      "code": 6075,
+      "name": "NotOfferTaker",
+      "msg": "NotOfferTaker"
+    },
+    {
+      "code": 6076,
+      "name": "InvalidRecipe",
+      "msg": "InvalidRecipe"
+    },
+    {
+      "code": 6077,
+      "name": "MaxTierReached",
+      "msg": "MaxTierReached"
+    },
+    {
+      "code": 6078,
+      "name": "NotConsumable",
+      "msg": "NotConsumable"
+    },
+    {
+      "code": 6079,
+      "name": "TooManyBuffs",
+      "msg": "TooManyBuffs"
+    },
+    {
+      "code": 6080,
+      "name": "NotSafeToRest",
+      "msg": "NotSafeToRest"
+    },
+    {
+      "code": 6081,
+      "name": "ShopOverflow",
+      "msg": "ShopOverflow"
+    },
+    {
+      "code": 6082,
+      "name": "StatOverflow",
+      "msg": "StatOverflow"
     }
   ]
 };
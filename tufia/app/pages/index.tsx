@@ -4,7 +4,7 @@ import WalletMultiButton from "@/components/WalletMultiButton"
 import DisplayGameState from "@/components/DisplayGameState"
 import InitPlayerButton from "@/components/InitPlayerButton"
 import SessionKeyButton from "@/components/SessionKeyButton"
-import ChopTreeButton from "@/components/ChopTreeButton"
+import JoinFloorButton from "@/components/JoinFloorButton"
 import RequestAirdrop from "@/components/RequestAirdrop"
 import DisplayNfts from "@/components/DisplayNfts"
 
@@ -23,7 +23,7 @@ export default function Home() {
         <DisplayGameState />
         <InitPlayerButton />
         <SessionKeyButton />
-        <ChopTreeButton />
+        <JoinFloorButton />
         <RequestAirdrop />
         <DisplayNfts />
       </VStack>
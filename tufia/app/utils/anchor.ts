@@ -10,9 +10,6 @@ export const CONNECTION = new WrappedConnection(process.env.NEXT_PUBLIC_RPC ? pr
 
 export const METAPLEX_READAPI = "https://devnet.helius-rpc.com/?api-key=78065db3-87fb-431c-8d43-fcd190212125";
 
-// Here you can basically use what ever seed you want. For example one per level or city or whatever.
-export const GAME_DATA_SEED = "level_2";
-
 // Tufia game program ID
 const programId = new PublicKey("Bip92wN115UuArG265UHWZJVwuL64ymthirNZAw5jHYJ")
 
@@ -21,17 +18,53 @@ export const program = new Program<Tufia>(IDL, programId, {
   connection: CONNECTION,
 })
 
-export const [gameDataPDA] = PublicKey.findProgramAddressSync(
-  [Buffer.from(GAME_DATA_SEED, "utf8")],
+export const [configPDA] = PublicKey.findProgramAddressSync(
+  [Buffer.from("config", "utf8")],
   program.programId
 )
 
+// Floors are addressed by season, floor id and instance, all little endian
+export const floorPDA = (season: number, floorId: number, instance: number) => {
+  const seasonBytes = Buffer.alloc(2)
+  seasonBytes.writeUInt16LE(season)
+  const floorIdBytes = Buffer.alloc(4)
+  floorIdBytes.writeUInt32LE(floorId)
+  const instanceBytes = Buffer.alloc(2)
+  instanceBytes.writeUInt16LE(instance)
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("floor", "utf8"), seasonBytes, floorIdBytes, instanceBytes],
+    program.programId
+  )[0]
+}
+
+export const directoryPDA = (season: number, floorId: number) => {
+  const seasonBytes = Buffer.alloc(2)
+  seasonBytes.writeUInt16LE(season)
+  const floorIdBytes = Buffer.alloc(4)
+  floorIdBytes.writeUInt32LE(floorId)
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("directory", "utf8"), seasonBytes, floorIdBytes],
+    program.programId
+  )[0]
+}
+
+// Same as FloorDirectory::route in the program, the first instance with
+// space left or a new one if all are full
+export const routeToInstance = (instances: number[]) => {
+  const instance = instances.findIndex(
+    (count) => count + 1 <= MAX_PLAYERS_PER_INSTANCE
+  )
+  return instance >= 0 ? instance : instances.length
+}
+
 // Player Data Account Type from Idl
 export type PlayerData = IdlAccounts<Tufia>["playerData"]
-export type GameData = IdlAccounts<Tufia>["gameData"]
+export type FloorData = IdlAccounts<Tufia>["floorData"]
 
 // Constants for the game
 export const TIME_TO_REFILL_ENERGY: BN = new BN(60)
 export const MAX_ENERGY = 100
 export const ENERGY_PER_TICK: BN = new BN(1)
 export const TOTAL_WOOD_AVAILABLE: BN = new BN(100000)
+export const MAX_PLAYERS_PER_INSTANCE = 8
+export const CLASS_WARRIOR = 1
@@ -0,0 +1,1598 @@
+// Harness for the compute unit benchmarks. Every instruction is sent in its
+// own transaction with the maximum compute limit, the units it used are
+// printed and checked against BUDGETS.
+
+use anchor_lang::{
+    prelude::Pubkey, system_program, AccountDeserialize, AccountSerialize, Discriminator, Id,
+    InstructionData, ToAccountMetas,
+};
+use anchor_spl::{
+    associated_token,
+    metadata::Metadata,
+    token::{self, spl_token},
+};
+use solana_program_test::{ProgramTest, ProgramTestContext};
+use solana_sdk::{
+    account::{Account, AccountSharedData},
+    bpf_loader,
+    bpf_loader_upgradeable::{self, UpgradeableLoaderState},
+    clock::Clock,
+    compute_budget::ComputeBudgetInstruction,
+    instruction::Instruction,
+    native_token::LAMPORTS_PER_SOL,
+    program_pack::Pack,
+    rent::Rent,
+    signature::{Keypair, Signer},
+    transaction::Transaction,
+};
+use std::path::Path;
+use tufia::{
+    constants::SECONDS_PER_DAY,
+    state::{
+        crafting::Recipe,
+        floor::FloorAddress,
+        floor_data::FloorData,
+        game_data::{GameData, TileData, STATE_ENEMY, STATE_PLAYER},
+        name_registry,
+        player_class::CLASS_WARRIOR,
+        player_data::{PlayerData, StatAllocation},
+        shop::ShopItem,
+        trade::OfferArgs,
+    },
+};
+
+// Compute units an instruction may use per scenario. Raise a budget only
+// together with the change that needs it.
+pub const BUDGETS: &[(&str, u64)] = &[
+    ("init_config", 15_000),
+    ("update_config", 10_000),
+    ("set_armor_regen", 10_000),
+    ("set_health_regen", 10_000),
+    ("set_item_uri", 15_000),
+    ("init_player", 40_000),
+    ("allocate_points", 80_000),
+    ("learn_skill", 15_000),
+    ("set_profile", 100_000),
+    ("buy_next_floor/first_spawn", 150_000),
+    ("buy_next_floor/next_floor", 150_000),
+    ("move_to_tile/empty_board", 80_000),
+    ("move_to_tile/full_board", 120_000),
+    ("move_to_tile/encounter", 100_000),
+    ("combat_action/long_fight", 100_000),
+    ("move_to_next_floor", 100_000),
+    ("sync_floor_instance", 60_000),
+    ("attack_boss", 120_000),
+    ("claim_boss_reward", 30_000),
+    ("use_item", 100_000),
+    ("rest", 80_000),
+    ("reset_floor/empty_board", 60_000),
+    ("reset_floor/full_board", 100_000),
+    ("start_daily_run", 150_000),
+    ("daily_move", 100_000),
+    ("daily_combat_action", 100_000),
+    ("daily_next_floor", 150_000),
+    ("claim_daily_reward", 60_000),
+    ("init_leaderboard", 20_000),
+    ("update_leaderboard", 30_000),
+    ("end_season", 40_000),
+    ("claim_season_reward", 60_000),
+    ("set_recipe", 20_000),
+    ("craft_item", 30_000),
+    ("equip_item", 80_000),
+    ("upgrade_item", 20_000),
+    ("init_gold_mint", 40_000),
+    ("set_shop_item", 80_000),
+    ("buy_item", 40_000),
+    ("sell_item", 40_000),
+    ("create_offer", 80_000),
+    ("accept_offer", 80_000),
+    ("cancel_offer", 60_000),
+    ("withdraw_item", 200_000),
+    ("deposit_item", 100_000),
+    ("create_party", 25_000),
+    ("invite", 15_000),
+    ("join_party", 15_000),
+    ("claim_party_share", 20_000),
+    ("leave_party", 20_000),
+    ("create_guild", 80_000),
+    ("invite_to_guild", 20_000),
+    ("join_guild", 20_000),
+    ("set_guild_role", 20_000),
+    ("contribute_gold", 40_000),
+    ("spend_guild_treasury", 40_000),
+    ("update_guild_ranking", 40_000),
+    ("leave_guild", 60_000),
+];
+
+const MAX_COMPUTE_UNITS: u32 = 1_400_000;
+
+// New configs start in season 0
+const SEASON: u16 = 0;
+
+// Every fifth floor has a boss, floor 0 never has one
+pub const BOSS_FLOOR_INTERVAL: u16 = 5;
+
+pub fn budget(name: &str) -> u64 {
+    match BUDGETS.iter().find(|(budget_name, _)| *budget_name == name) {
+        Some((_, units)) => *units,
+        None => panic!("No budget for {}", name),
+    }
+}
+
+pub fn config_pda() -> Pubkey {
+    Pubkey::find_program_address(&[b"config"], &tufia::ID).0
+}
+
+pub fn player_pda(authority: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"player1", authority.as_ref()], &tufia::ID).0
+}
+
+pub fn program_data_pda() -> Pubkey {
+    Pubkey::find_program_address(&[tufia::ID.as_ref()], &bpf_loader_upgradeable::ID).0
+}
+
+pub fn directory_pda(floor_id: u32) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"directory", &SEASON.to_le_bytes(), &floor_id.to_le_bytes()],
+        &tufia::ID,
+    )
+    .0
+}
+
+pub fn boss_pda(floor: &FloorAddress) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            b"boss",
+            &SEASON.to_le_bytes(),
+            &floor.floor_id.to_le_bytes(),
+            &floor.instance.to_le_bytes(),
+        ],
+        &tufia::ID,
+    )
+    .0
+}
+
+pub fn gold_mint_pda() -> Pubkey {
+    Pubkey::find_program_address(&[b"gold"], &tufia::ID).0
+}
+
+pub fn gold_account(owner: &Pubkey) -> Pubkey {
+    associated_token::get_associated_token_address(owner, &gold_mint_pda())
+}
+
+pub fn shop_pda(kind: u8) -> Pubkey {
+    Pubkey::find_program_address(&[b"shop", &[kind]], &tufia::ID).0
+}
+
+pub fn offer_pda(maker: &Pubkey, id: u32) -> Pubkey {
+    Pubkey::find_program_address(&[b"offer", maker.as_ref(), &id.to_le_bytes()], &tufia::ID).0
+}
+
+pub fn name_pda(name: &str) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"name", name_registry::registry_seed(name).as_bytes()],
+        &tufia::ID,
+    )
+    .0
+}
+
+pub fn leaderboard_pda(season: u16, metric: u8) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"leaderboard", &season.to_le_bytes(), &[metric]],
+        &tufia::ID,
+    )
+    .0
+}
+
+pub fn season_pda(season: u16) -> Pubkey {
+    Pubkey::find_program_address(&[b"season", &season.to_le_bytes()], &tufia::ID).0
+}
+
+pub fn daily_run_pda(day: u32, authority: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"daily_run", &day.to_le_bytes(), authority.as_ref()],
+        &tufia::ID,
+    )
+    .0
+}
+
+pub fn daily_floor_pda(day: u32, authority: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"daily", &day.to_le_bytes(), authority.as_ref()],
+        &tufia::ID,
+    )
+    .0
+}
+
+pub fn daily_leaderboard_pda(day: u32) -> Pubkey {
+    Pubkey::find_program_address(&[b"daily_board", &day.to_le_bytes()], &tufia::ID).0
+}
+
+pub fn party_pda(founder: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"party", founder.as_ref()], &tufia::ID).0
+}
+
+pub fn guild_pda(name: &str) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"guild", name_registry::registry_seed(name).as_bytes()],
+        &tufia::ID,
+    )
+    .0
+}
+
+pub fn guild_leaderboard_pda(season: u16) -> Pubkey {
+    Pubkey::find_program_address(&[b"guild_board", &season.to_le_bytes()], &tufia::ID).0
+}
+
+pub fn item_record_pda(mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"item", mint.as_ref()], &tufia::ID).0
+}
+
+pub fn metadata_pda(mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"metadata", Metadata::id().as_ref(), mint.as_ref()],
+        &Metadata::id(),
+    )
+    .0
+}
+
+pub fn master_edition_pda(mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            b"metadata",
+            Metadata::id().as_ref(),
+            mint.as_ref(),
+            b"edition",
+        ],
+        &Metadata::id(),
+    )
+    .0
+}
+
+pub fn floor_pda(floor: &FloorAddress) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            b"floor",
+            &SEASON.to_le_bytes(),
+            &floor.floor_id.to_le_bytes(),
+            &floor.instance.to_le_bytes(),
+        ],
+        &tufia::ID,
+    )
+    .0
+}
+
+// Tiles for the prepared boards of the scenarios
+pub fn player_tile(owner: Pubkey, health: u32) -> TileData {
+    TileData {
+        tile_type: STATE_PLAYER,
+        tile_owner: owner,
+        tile_level: 1,
+        tile_health: health,
+        tile_max_health: health,
+        tile_damage: 1,
+        tile_class: CLASS_WARRIOR,
+        ..Default::default()
+    }
+}
+
+pub fn enemy_tile(owner: Pubkey) -> TileData {
+    TileData {
+        tile_type: STATE_ENEMY,
+        tile_owner: owner,
+        tile_level: 1,
+        tile_health: 5,
+        tile_max_health: 5,
+        tile_damage: 1,
+        tile_defence: 1,
+        ..Default::default()
+    }
+}
+
+fn floor_account(game_data: &GameData) -> Account {
+    let mut floor_data = FloorData::default();
+    floor_data.save(game_data).unwrap();
+
+    let mut data = FloorData::DISCRIMINATOR.to_vec();
+    data.extend_from_slice(bytemuck::bytes_of(&floor_data));
+    Account {
+        lamports: Rent::default().minimum_balance(data.len()),
+        data,
+        owner: tufia::ID,
+        ..Account::default()
+    }
+}
+
+pub struct Bench {
+    program_test: ProgramTest,
+    pub player: Keypair,
+}
+
+pub struct RunningBench {
+    context: ProgramTestContext,
+    pub player: Keypair,
+    // Program logs of the last instruction
+    pub logs: Vec<String>,
+}
+
+impl Bench {
+    pub fn new() -> Self {
+        let mut program_test = ProgramTest::default();
+        let player = Keypair::new();
+        deploy_upgradeable(&mut program_test, &player.pubkey());
+
+        let mut bench = Bench {
+            program_test,
+            player,
+        };
+        bench.fund(&bench.player.pubkey());
+        bench
+    }
+
+    // Another wallet with some SOL, for instructions between two players
+    pub fn add_wallet(&mut self) -> Keypair {
+        let wallet = Keypair::new();
+        self.fund(&wallet.pubkey());
+        wallet
+    }
+
+    fn fund(&mut self, wallet: &Pubkey) {
+        self.program_test.add_account(
+            *wallet,
+            Account {
+                lamports: 10 * LAMPORTS_PER_SOL,
+                owner: system_program::ID,
+                ..Account::default()
+            },
+        );
+    }
+
+    // Puts a prepared board on the floor so scenarios do not have to play
+    // their way there
+    pub fn add_floor(&mut self, floor: &FloorAddress, game_data: &GameData) {
+        self.program_test
+            .add_account(floor_pda(floor), floor_account(game_data));
+    }
+
+    // Item NFTs need the token metadata program. It is the same binary the
+    // local validator loads, see tests/fixtures/README.md.
+    pub fn add_token_metadata(&mut self) {
+        let path =
+            Path::new(env!("CARGO_MANIFEST_DIR")).join("../tests/fixtures/mpl_token_metadata.so");
+        let elf = std::fs::read(&path)
+            .unwrap_or_else(|_| panic!("{} is missing, see its README", path.display()));
+        self.program_test.add_account(
+            Metadata::id(),
+            Account {
+                lamports: Rent::default().minimum_balance(elf.len()),
+                data: elf,
+                owner: bpf_loader::ID,
+                executable: true,
+                ..Account::default()
+            },
+        );
+    }
+
+    // Starts the bank with a config and a player on the first floor
+    pub async fn start(self, class: u8) -> RunningBench {
+        let context = self.program_test.start_with_context().await;
+        let mut bench = RunningBench {
+            context,
+            player: self.player,
+            logs: Vec::new(),
+        };
+        bench.init_config().await;
+        bench.init_player(class).await;
+        bench
+    }
+}
+
+// init_config checks the upgrade authority, so the program is deployed with
+// the upgradeable loader and the bench player as its authority
+fn deploy_upgradeable(program_test: &mut ProgramTest, authority: &Pubkey) {
+    let out_dir = std::env::var("SBF_OUT_DIR").expect("SBF_OUT_DIR is not set");
+    let elf = std::fs::read(Path::new(&out_dir).join("tufia.so")).expect("tufia.so is not built");
+
+    let mut data = bincode::serialize(&UpgradeableLoaderState::ProgramData {
+        slot: 0,
+        upgrade_authority_address: Some(*authority),
+    })
+    .unwrap();
+    data.resize(UpgradeableLoaderState::size_of_programdata_metadata(), 0);
+    data.extend_from_slice(&elf);
+    program_test.add_account(
+        program_data_pda(),
+        Account {
+            lamports: Rent::default().minimum_balance(data.len()),
+            data,
+            owner: bpf_loader_upgradeable::ID,
+            ..Account::default()
+        },
+    );
+
+    let data = bincode::serialize(&UpgradeableLoaderState::Program {
+        programdata_address: program_data_pda(),
+    })
+    .unwrap();
+    program_test.add_account(
+        tufia::ID,
+        Account {
+            lamports: Rent::default().minimum_balance(data.len()),
+            data,
+            owner: bpf_loader_upgradeable::ID,
+            executable: true,
+            ..Account::default()
+        },
+    );
+}
+
+impl Default for Bench {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RunningBench {
+    pub async fn init_config(&mut self) -> u64 {
+        let accounts = tufia::accounts::InitConfig {
+            config: config_pda(),
+            program: tufia::ID,
+            program_data: program_data_pda(),
+            signer: self.player.pubkey(),
+            system_program: system_program::ID,
+        };
+        let data = tufia::instruction::InitConfig {
+            boss_floor_interval: BOSS_FLOOR_INTERVAL,
+        };
+        self.run("init_config", accounts.to_account_metas(None), data.data())
+            .await
+    }
+
+    pub async fn init_player(&mut self, class: u8) -> u64 {
+        let player = self.player.insecure_clone();
+        self.init_player_of(&player, class).await
+    }
+
+    pub async fn init_player_of(&mut self, wallet: &Keypair, class: u8) -> u64 {
+        let accounts = tufia::accounts::InitPlayer {
+            player: player_pda(&wallet.pubkey()),
+            config: config_pda(),
+            signer: wallet.pubkey(),
+            system_program: system_program::ID,
+        };
+        let data = tufia::instruction::InitPlayer { class };
+        self.run_signed(
+            "init_player",
+            accounts.to_account_metas(None),
+            data.data(),
+            &[wallet],
+        )
+        .await
+    }
+
+    pub async fn account<T: AccountDeserialize>(&mut self, address: &Pubkey) -> T {
+        let account = self
+            .context
+            .banks_client
+            .get_account(*address)
+            .await
+            .unwrap()
+            .unwrap_or_else(|| panic!("Account {} does not exist", address));
+        T::try_deserialize(&mut account.data.as_slice()).unwrap()
+    }
+
+    pub async fn account_exists(&mut self, address: &Pubkey) -> bool {
+        let account = self.context.banks_client.get_account(*address).await;
+        account.unwrap().is_some()
+    }
+
+    // Changes an account of the program directly, e.g. to skip a fight that
+    // would take hundreds of transactions
+    pub async fn update_account<T: AccountDeserialize + AccountSerialize>(
+        &mut self,
+        address: &Pubkey,
+        update: impl FnOnce(&mut T),
+    ) {
+        let mut account = self
+            .context
+            .banks_client
+            .get_account(*address)
+            .await
+            .unwrap()
+            .unwrap_or_else(|| panic!("Account {} does not exist", address));
+        let mut value = T::try_deserialize(&mut account.data.as_slice()).unwrap();
+        update(&mut value);
+
+        let mut data = Vec::new();
+        value.try_serialize(&mut data).unwrap();
+        account.data[..data.len()].copy_from_slice(&data);
+        self.context
+            .set_account(address, &AccountSharedData::from(account));
+    }
+
+    // Changes the player account directly, e.g. to put gear into the
+    // inventory as if it was found in a chest
+    pub async fn update_player(
+        &mut self,
+        authority: &Pubkey,
+        update: impl FnOnce(&mut PlayerData),
+    ) {
+        self.update_account(&player_pda(authority), update).await;
+    }
+
+    // Replaces the board of a floor account that the program created, the
+    // daily floors are generated from a seed
+    pub fn set_board(&mut self, address: &Pubkey, game_data: &GameData) {
+        self.context
+            .set_account(address, &AccountSharedData::from(floor_account(game_data)));
+    }
+
+    pub async fn today(&mut self) -> u32 {
+        let clock: Clock = self.context.banks_client.get_sysvar().await.unwrap();
+        (clock.unix_timestamp / SECONDS_PER_DAY) as u32
+    }
+
+    // Daily rewards can only be claimed once the day is over
+    pub async fn next_day(&mut self) {
+        let mut clock: Clock = self.context.banks_client.get_sysvar().await.unwrap();
+        clock.unix_timestamp += SECONDS_PER_DAY;
+        self.context.set_sysvar(&clock);
+    }
+
+    // Gold is only minted by the program as loot, the benchmarks write the
+    // token account of the owner instead
+    pub fn give_gold(&mut self, owner: &Pubkey, amount: u64) {
+        let mut data = vec![0; spl_token::state::Account::LEN];
+        spl_token::state::Account {
+            mint: gold_mint_pda(),
+            owner: *owner,
+            amount,
+            state: spl_token::state::AccountState::Initialized,
+            ..Default::default()
+        }
+        .pack_into_slice(&mut data);
+        let account = Account {
+            lamports: Rent::default().minimum_balance(data.len()),
+            data,
+            owner: token::ID,
+            ..Account::default()
+        };
+        self.context
+            .set_account(&gold_account(owner), &AccountSharedData::from(account));
+    }
+
+    pub async fn buy_next_floor(&mut self, scenario: &str, floor: FloorAddress) -> u64 {
+        let accounts = tufia::accounts::BuyNextFloor {
+            session_token: None,
+            player: player_pda(&self.player.pubkey()),
+            config: config_pda(),
+            directory: directory_pda(floor.floor_id),
+            game_data: floor_pda(&floor),
+            old_game_data: None,
+            party: None,
+            signer: self.player.pubkey(),
+            system_program: system_program::ID,
+        };
+        let data = tufia::instruction::BuyNextFloor { floor, counter: 1 };
+        self.run(
+            &format!("buy_next_floor/{}", scenario),
+            accounts.to_account_metas(None),
+            data.data(),
+        )
+        .await
+    }
+
+    pub async fn move_to_tile(
+        &mut self,
+        scenario: &str,
+        floor: FloorAddress,
+        x: u64,
+        y: u64,
+    ) -> u64 {
+        let accounts = tufia::accounts::MoveToTile {
+            session_token: None,
+            player: player_pda(&self.player.pubkey()),
+            config: config_pda(),
+            game_data: floor_pda(&floor),
+            leaderboard: None,
+            party: None,
+            signer: self.player.pubkey(),
+            system_program: system_program::ID,
+        };
+        let data = tufia::instruction::MoveToTile {
+            floor,
+            counter: 1,
+            x,
+            y,
+        };
+        self.run(
+            &format!("move_to_tile/{}", scenario),
+            accounts.to_account_metas(None),
+            data.data(),
+        )
+        .await
+    }
+
+    pub async fn reset_floor(&mut self, scenario: &str, floor: FloorAddress) -> u64 {
+        let accounts = tufia::accounts::ResetFloor {
+            session_token: None,
+            player: player_pda(&self.player.pubkey()),
+            config: config_pda(),
+            game_data: floor_pda(&floor),
+            signer: self.player.pubkey(),
+            system_program: system_program::ID,
+        };
+        let data = tufia::instruction::ResetFloor { floor, counter: 1 };
+        self.run(
+            &format!("reset_floor/{}", scenario),
+            accounts.to_account_metas(None),
+            data.data(),
+        )
+        .await
+    }
+
+    pub async fn combat_action(&mut self, scenario: &str, floor: FloorAddress, action: u8) -> u64 {
+        let accounts = tufia::accounts::CombatAction {
+            session_token: None,
+            player: player_pda(&self.player.pubkey()),
+            config: config_pda(),
+            game_data: floor_pda(&floor),
+            leaderboard: None,
+            party: None,
+            signer: self.player.pubkey(),
+        };
+        let data = tufia::instruction::CombatAction {
+            floor,
+            counter: 1,
+            action,
+        };
+        self.run(
+            &format!("combat_action/{}", scenario),
+            accounts.to_account_metas(None),
+            data.data(),
+        )
+        .await
+    }
+
+    pub async fn move_to_next_floor(&mut self, floor: FloorAddress) -> u64 {
+        let accounts = tufia::accounts::NextFloor {
+            session_token: None,
+            player: player_pda(&self.player.pubkey()),
+            config: config_pda(),
+            game_data: floor_pda(&floor),
+            directory: None,
+            leaderboard: None,
+            signer: self.player.pubkey(),
+            system_program: system_program::ID,
+        };
+        let data = tufia::instruction::MoveToNextFloor { floor, counter: 1 };
+        self.run(
+            "move_to_next_floor",
+            accounts.to_account_metas(None),
+            data.data(),
+        )
+        .await
+    }
+
+    pub async fn attack_boss(&mut self, floor: FloorAddress) -> u64 {
+        let accounts = tufia::accounts::AttackBoss {
+            session_token: None,
+            player: player_pda(&self.player.pubkey()),
+            config: config_pda(),
+            game_data: floor_pda(&floor),
+            boss: boss_pda(&floor),
+            leaderboard: None,
+            signer: self.player.pubkey(),
+            system_program: system_program::ID,
+        };
+        let data = tufia::instruction::AttackBoss { floor, counter: 1 };
+        self.run("attack_boss", accounts.to_account_metas(None), data.data())
+            .await
+    }
+
+    pub async fn use_item(&mut self, floor: FloorAddress, item_id: u16) -> u64 {
+        let accounts = tufia::accounts::UseItem {
+            session_token: None,
+            player: player_pda(&self.player.pubkey()),
+            config: config_pda(),
+            game_data: floor_pda(&floor),
+            signer: self.player.pubkey(),
+        };
+        let data = tufia::instruction::UseItem {
+            floor,
+            counter: 1,
+            item_id,
+        };
+        self.run("use_item", accounts.to_account_metas(None), data.data())
+            .await
+    }
+
+    pub async fn set_recipe(&mut self, index: u8, recipe: Recipe) -> u64 {
+        let accounts = tufia::accounts::SetRecipe {
+            config: config_pda(),
+            admin: self.player.pubkey(),
+        };
+        let data = tufia::instruction::SetRecipe { index, recipe };
+        self.run("set_recipe", accounts.to_account_metas(None), data.data())
+            .await
+    }
+
+    pub async fn craft_item(&mut self, recipe: u8) -> u64 {
+        let accounts = tufia::accounts::CraftItem {
+            session_token: None,
+            player: player_pda(&self.player.pubkey()),
+            config: config_pda(),
+            signer: self.player.pubkey(),
+        };
+        let data = tufia::instruction::CraftItem { counter: 1, recipe };
+        self.run("craft_item", accounts.to_account_metas(None), data.data())
+            .await
+    }
+
+    pub async fn equip_item(&mut self, floor: FloorAddress, index: u8, equip: bool) -> u64 {
+        let accounts = tufia::accounts::EquipItem {
+            session_token: None,
+            player: player_pda(&self.player.pubkey()),
+            config: config_pda(),
+            game_data: floor_pda(&floor),
+            signer: self.player.pubkey(),
+        };
+        let data = tufia::instruction::EquipItem {
+            floor,
+            counter: 1,
+            index,
+            equip,
+        };
+        self.run("equip_item", accounts.to_account_metas(None), data.data())
+            .await
+    }
+
+    pub async fn upgrade_item(&mut self, index: u8) -> u64 {
+        let accounts = tufia::accounts::UpgradeItem {
+            session_token: None,
+            player: player_pda(&self.player.pubkey()),
+            signer: self.player.pubkey(),
+        };
+        let data = tufia::instruction::UpgradeItem { counter: 1, index };
+        self.run("upgrade_item", accounts.to_account_metas(None), data.data())
+            .await
+    }
+
+    pub async fn init_gold_mint(&mut self) -> u64 {
+        let accounts = tufia::accounts::InitGoldMint {
+            config: config_pda(),
+            gold_mint: gold_mint_pda(),
+            admin: self.player.pubkey(),
+            token_program: token::ID,
+            system_program: system_program::ID,
+        };
+        let data = tufia::instruction::InitGoldMint {};
+        self.run(
+            "init_gold_mint",
+            accounts.to_account_metas(None),
+            data.data(),
+        )
+        .await
+    }
+
+    pub async fn set_shop_item(&mut self, kind: u8, item: ShopItem) -> u64 {
+        let accounts = tufia::accounts::SetShopItem {
+            config: config_pda(),
+            shop: shop_pda(kind),
+            gold_mint: gold_mint_pda(),
+            treasury: gold_account(&shop_pda(kind)),
+            admin: self.player.pubkey(),
+            token_program: token::ID,
+            associated_token_program: associated_token::ID,
+            system_program: system_program::ID,
+        };
+        let data = tufia::instruction::SetShopItem { kind, item };
+        self.run(
+            "set_shop_item",
+            accounts.to_account_metas(None),
+            data.data(),
+        )
+        .await
+    }
+
+    // Shopping in the lobby, the merchant shop would need a merchant tile
+    // next to the player
+    pub async fn buy_item(&mut self, kind: u8, item_id: u16, amount: u32) -> u64 {
+        let accounts = tufia::accounts::BuyItem {
+            player: player_pda(&self.player.pubkey()),
+            config: config_pda(),
+            shop: shop_pda(kind),
+            game_data: None,
+            gold_mint: gold_mint_pda(),
+            player_gold: gold_account(&self.player.pubkey()),
+            treasury: gold_account(&shop_pda(kind)),
+            signer: self.player.pubkey(),
+            token_program: token::ID,
+        };
+        let data = tufia::instruction::BuyItem {
+            floor: FloorAddress::default(),
+            kind,
+            item_id,
+            amount,
+        };
+        self.run("buy_item", accounts.to_account_metas(None), data.data())
+            .await
+    }
+
+    pub async fn sell_item(&mut self, kind: u8, item_id: u16, amount: u32) -> u64 {
+        let accounts = tufia::accounts::SellItem {
+            player: player_pda(&self.player.pubkey()),
+            config: config_pda(),
+            shop: shop_pda(kind),
+            game_data: None,
+            gold_mint: gold_mint_pda(),
+            player_gold: gold_account(&self.player.pubkey()),
+            treasury: gold_account(&shop_pda(kind)),
+            signer: self.player.pubkey(),
+            token_program: token::ID,
+        };
+        let data = tufia::instruction::SellItem {
+            floor: FloorAddress::default(),
+            kind,
+            item_id,
+            amount,
+        };
+        self.run("sell_item", accounts.to_account_metas(None), data.data())
+            .await
+    }
+
+    pub async fn create_offer(&mut self, id: u32, args: OfferArgs) -> u64 {
+        let maker = self.player.pubkey();
+        let offer = offer_pda(&maker, id);
+        let accounts = tufia::accounts::CreateOffer {
+            player: player_pda(&maker),
+            offer,
+            gold_mint: gold_mint_pda(),
+            maker_gold: gold_account(&maker),
+            escrow_gold: gold_account(&offer),
+            signer: maker,
+            token_program: token::ID,
+            associated_token_program: associated_token::ID,
+            system_program: system_program::ID,
+        };
+        let data = tufia::instruction::CreateOffer { id, args };
+        self.run("create_offer", accounts.to_account_metas(None), data.data())
+            .await
+    }
+
+    // Accepts an offer of the bench player
+    pub async fn accept_offer(&mut self, taker: &Keypair, id: u32) -> u64 {
+        let maker = self.player.pubkey();
+        let offer = offer_pda(&maker, id);
+        let accounts = tufia::accounts::AcceptOffer {
+            player: player_pda(&taker.pubkey()),
+            maker_player: player_pda(&maker),
+            offer,
+            maker,
+            gold_mint: gold_mint_pda(),
+            escrow_gold: gold_account(&offer),
+            taker_gold: gold_account(&taker.pubkey()),
+            maker_gold: gold_account(&maker),
+            signer: taker.pubkey(),
+            token_program: token::ID,
+            associated_token_program: associated_token::ID,
+            system_program: system_program::ID,
+        };
+        let data = tufia::instruction::AcceptOffer {};
+        self.run_signed(
+            "accept_offer",
+            accounts.to_account_metas(None),
+            data.data(),
+            &[taker],
+        )
+        .await
+    }
+
+    pub async fn cancel_offer(&mut self, id: u32) -> u64 {
+        let maker = self.player.pubkey();
+        let offer = offer_pda(&maker, id);
+        let accounts = tufia::accounts::CancelOffer {
+            player: player_pda(&maker),
+            offer,
+            gold_mint: gold_mint_pda(),
+            escrow_gold: gold_account(&offer),
+            maker_gold: gold_account(&maker),
+            signer: maker,
+            token_program: token::ID,
+        };
+        let data = tufia::instruction::CancelOffer {};
+        self.run("cancel_offer", accounts.to_account_metas(None), data.data())
+            .await
+    }
+
+    pub async fn withdraw_item(&mut self, index: u8, item_mint: &Keypair) -> u64 {
+        let mint = item_mint.pubkey();
+        let accounts = tufia::accounts::WithdrawItem {
+            player: player_pda(&self.player.pubkey()),
+            config: config_pda(),
+            item_mint: mint,
+            player_item: associated_token::get_associated_token_address(
+                &self.player.pubkey(),
+                &mint,
+            ),
+            item_record: item_record_pda(&mint),
+            metadata: metadata_pda(&mint),
+            master_edition: master_edition_pda(&mint),
+            signer: self.player.pubkey(),
+            token_program: token::ID,
+            associated_token_program: associated_token::ID,
+            token_metadata_program: Metadata::id(),
+            system_program: system_program::ID,
+            rent: solana_sdk::sysvar::rent::ID,
+        };
+        let data = tufia::instruction::WithdrawItem { index };
+        self.run_signed(
+            "withdraw_item",
+            accounts.to_account_metas(None),
+            data.data(),
+            &[item_mint],
+        )
+        .await
+    }
+
+    pub async fn deposit_item(&mut self, mint: &Pubkey) -> u64 {
+        let accounts = tufia::accounts::DepositItem {
+            player: player_pda(&self.player.pubkey()),
+            item_mint: *mint,
+            player_item: associated_token::get_associated_token_address(
+                &self.player.pubkey(),
+                mint,
+            ),
+            item_record: item_record_pda(mint),
+            metadata: metadata_pda(mint),
+            master_edition: master_edition_pda(mint),
+            signer: self.player.pubkey(),
+            token_program: token::ID,
+            token_metadata_program: Metadata::id(),
+        };
+        let data = tufia::instruction::DepositItem {};
+        self.run("deposit_item", accounts.to_account_metas(None), data.data())
+            .await
+    }
+
+    pub async fn create_party(&mut self, xp_rule: u8, loot_rule: u8) -> u64 {
+        let founder = self.player.pubkey();
+        let accounts = tufia::accounts::CreateParty {
+            session_token: None,
+            player: player_pda(&founder),
+            party: party_pda(&founder),
+            signer: founder,
+            system_program: system_program::ID,
+        };
+        let data = tufia::instruction::CreateParty {
+            counter: 1,
+            xp_rule,
+            loot_rule,
+        };
+        self.run("create_party", accounts.to_account_metas(None), data.data())
+            .await
+    }
+
+    // Party instructions of the bench player, they lead the party
+    pub async fn invite(&mut self, invitee: &Pubkey) -> u64 {
+        let founder = self.player.pubkey();
+        let accounts = tufia::accounts::Invite {
+            session_token: None,
+            player: player_pda(&founder),
+            party: party_pda(&founder),
+            signer: founder,
+        };
+        let data = tufia::instruction::Invite {
+            counter: 1,
+            invitee: *invitee,
+        };
+        self.run("invite", accounts.to_account_metas(None), data.data())
+            .await
+    }
+
+    // The other members play with their own wallet in the party of the
+    // bench player
+    pub async fn join_party(&mut self, member: &Keypair) -> u64 {
+        let accounts = tufia::accounts::JoinParty {
+            session_token: None,
+            player: player_pda(&member.pubkey()),
+            party: party_pda(&self.player.pubkey()),
+            signer: member.pubkey(),
+        };
+        let data = tufia::instruction::JoinParty { counter: 1 };
+        self.run_signed(
+            "join_party",
+            accounts.to_account_metas(None),
+            data.data(),
+            &[member],
+        )
+        .await
+    }
+
+    pub async fn claim_party_share(&mut self, member: &Keypair) -> u64 {
+        let accounts = tufia::accounts::ClaimPartyShare {
+            session_token: None,
+            player: player_pda(&member.pubkey()),
+            party: party_pda(&self.player.pubkey()),
+            config: config_pda(),
+            game_data: None,
+            signer: member.pubkey(),
+        };
+        let data = tufia::instruction::ClaimPartyShare { counter: 1 };
+        self.run_signed(
+            "claim_party_share",
+            accounts.to_account_metas(None),
+            data.data(),
+            &[member],
+        )
+        .await
+    }
+
+    pub async fn leave_party(&mut self, member: &Keypair) -> u64 {
+        let accounts = tufia::accounts::LeaveParty {
+            session_token: None,
+            player: player_pda(&member.pubkey()),
+            party: party_pda(&self.player.pubkey()),
+            authority: member.pubkey(),
+            signer: member.pubkey(),
+        };
+        let data = tufia::instruction::LeaveParty { counter: 1 };
+        self.run_signed(
+            "leave_party",
+            accounts.to_account_metas(None),
+            data.data(),
+            &[member],
+        )
+        .await
+    }
+
+    pub async fn create_guild(&mut self, name: &str) -> u64 {
+        let leader = self.player.pubkey();
+        let guild = guild_pda(name);
+        let accounts = tufia::accounts::CreateGuild {
+            player: player_pda(&leader),
+            guild,
+            gold_mint: gold_mint_pda(),
+            treasury: gold_account(&guild),
+            signer: leader,
+            token_program: token::ID,
+            associated_token_program: associated_token::ID,
+            system_program: system_program::ID,
+        };
+        let data = tufia::instruction::CreateGuild {
+            name: name.to_string(),
+        };
+        self.run("create_guild", accounts.to_account_metas(None), data.data())
+            .await
+    }
+
+    // Guild instructions of the bench player, they lead the guild
+    pub async fn invite_to_guild(&mut self, name: &str, invitee: &Pubkey) -> u64 {
+        let leader = self.player.pubkey();
+        let accounts = tufia::accounts::InviteToGuild {
+            player: player_pda(&leader),
+            guild: guild_pda(name),
+            signer: leader,
+        };
+        let data = tufia::instruction::InviteToGuild { invitee: *invitee };
+        self.run(
+            "invite_to_guild",
+            accounts.to_account_metas(None),
+            data.data(),
+        )
+        .await
+    }
+
+    pub async fn set_guild_role(&mut self, name: &str, member: &Pubkey, role: u8) -> u64 {
+        let leader = self.player.pubkey();
+        let accounts = tufia::accounts::SetGuildRole {
+            player: player_pda(&leader),
+            guild: guild_pda(name),
+            signer: leader,
+        };
+        let data = tufia::instruction::SetGuildRole {
+            member: *member,
+            role,
+        };
+        self.run(
+            "set_guild_role",
+            accounts.to_account_metas(None),
+            data.data(),
+        )
+        .await
+    }
+
+    pub async fn contribute_gold(&mut self, name: &str, amount: u64) -> u64 {
+        let member = self.player.pubkey();
+        let guild = guild_pda(name);
+        let accounts = tufia::accounts::ContributeGold {
+            player: player_pda(&member),
+            guild,
+            gold_mint: gold_mint_pda(),
+            player_gold: gold_account(&member),
+            treasury: gold_account(&guild),
+            signer: member,
+            token_program: token::ID,
+        };
+        let data = tufia::instruction::ContributeGold { amount };
+        self.run(
+            "contribute_gold",
+            accounts.to_account_metas(None),
+            data.data(),
+        )
+        .await
+    }
+
+    pub async fn spend_guild_treasury(
+        &mut self,
+        name: &str,
+        recipient: &Pubkey,
+        amount: u64,
+    ) -> u64 {
+        let guild = guild_pda(name);
+        let accounts = tufia::accounts::SpendGuildTreasury {
+            guild,
+            gold_mint: gold_mint_pda(),
+            treasury: gold_account(&guild),
+            recipient: gold_account(recipient),
+            signer: self.player.pubkey(),
+            token_program: token::ID,
+        };
+        let data = tufia::instruction::SpendGuildTreasury { amount };
+        self.run(
+            "spend_guild_treasury",
+            accounts.to_account_metas(None),
+            data.data(),
+        )
+        .await
+    }
+
+    pub async fn update_guild_ranking(&mut self, name: &str, member: &Pubkey) -> u64 {
+        let accounts = tufia::accounts::UpdateGuildRanking {
+            config: config_pda(),
+            player: player_pda(member),
+            guild: guild_pda(name),
+            guild_leaderboard: guild_leaderboard_pda(SEASON),
+            signer: self.player.pubkey(),
+            system_program: system_program::ID,
+        };
+        let data = tufia::instruction::UpdateGuildRanking {};
+        self.run(
+            "update_guild_ranking",
+            accounts.to_account_metas(None),
+            data.data(),
+        )
+        .await
+    }
+
+    // The other members use their own wallet
+    pub async fn join_guild(&mut self, name: &str, member: &Keypair) -> u64 {
+        let accounts = tufia::accounts::JoinGuild {
+            player: player_pda(&member.pubkey()),
+            guild: guild_pda(name),
+            signer: member.pubkey(),
+        };
+        let data = tufia::instruction::JoinGuild {};
+        self.run_signed(
+            "join_guild",
+            accounts.to_account_metas(None),
+            data.data(),
+            &[member],
+        )
+        .await
+    }
+
+    // The last member to leave closes the guild
+    pub async fn leave_guild(&mut self, name: &str, member: &Keypair) -> u64 {
+        let guild = guild_pda(name);
+        let accounts = tufia::accounts::LeaveGuild {
+            player: player_pda(&member.pubkey()),
+            guild,
+            gold_mint: gold_mint_pda(),
+            treasury: gold_account(&guild),
+            player_gold: gold_account(&member.pubkey()),
+            signer: member.pubkey(),
+            token_program: token::ID,
+        };
+        let data = tufia::instruction::LeaveGuild {};
+        self.run_signed(
+            "leave_guild",
+            accounts.to_account_metas(None),
+            data.data(),
+            &[member],
+        )
+        .await
+    }
+
+    pub async fn update_config(&mut self, boss_floor_interval: u16) -> u64 {
+        let accounts = tufia::accounts::UpdateConfig {
+            config: config_pda(),
+            admin: self.player.pubkey(),
+        };
+        let data = tufia::instruction::UpdateConfig {
+            boss_floor_interval,
+        };
+        self.run(
+            "update_config",
+            accounts.to_account_metas(None),
+            data.data(),
+        )
+        .await
+    }
+
+    pub async fn set_armor_regen(&mut self, armor_regen_per_move: u32) -> u64 {
+        let accounts = tufia::accounts::SetArmorRegen {
+            config: config_pda(),
+            admin: self.player.pubkey(),
+        };
+        let data = tufia::instruction::SetArmorRegen {
+            armor_regen_per_move,
+        };
+        self.run(
+            "set_armor_regen",
+            accounts.to_account_metas(None),
+            data.data(),
+        )
+        .await
+    }
+
+    pub async fn set_health_regen(&mut self, health_regen_seconds: i64) -> u64 {
+        let accounts = tufia::accounts::SetHealthRegen {
+            config: config_pda(),
+            admin: self.player.pubkey(),
+        };
+        let data = tufia::instruction::SetHealthRegen {
+            health_regen_seconds,
+        };
+        self.run(
+            "set_health_regen",
+            accounts.to_account_metas(None),
+            data.data(),
+        )
+        .await
+    }
+
+    pub async fn set_item_uri(&mut self, item_uri: &str) -> u64 {
+        let accounts = tufia::accounts::SetItemUri {
+            config: config_pda(),
+            admin: self.player.pubkey(),
+        };
+        let data = tufia::instruction::SetItemUri {
+            item_uri: item_uri.to_string(),
+        };
+        self.run("set_item_uri", accounts.to_account_metas(None), data.data())
+            .await
+    }
+
+    pub async fn allocate_points(
+        &mut self,
+        floor: FloorAddress,
+        allocation: StatAllocation,
+    ) -> u64 {
+        let accounts = tufia::accounts::AllocatePoints {
+            session_token: None,
+            player: player_pda(&self.player.pubkey()),
+            config: config_pda(),
+            game_data: floor_pda(&floor),
+            signer: self.player.pubkey(),
+        };
+        let data = tufia::instruction::AllocatePoints {
+            floor,
+            counter: 1,
+            allocation,
+        };
+        self.run(
+            "allocate_points",
+            accounts.to_account_metas(None),
+            data.data(),
+        )
+        .await
+    }
+
+    pub async fn learn_skill(&mut self, skill: u8) -> u64 {
+        let accounts = tufia::accounts::LearnSkill {
+            session_token: None,
+            player: player_pda(&self.player.pubkey()),
+            signer: self.player.pubkey(),
+        };
+        let data = tufia::instruction::LearnSkill { counter: 1, skill };
+        self.run("learn_skill", accounts.to_account_metas(None), data.data())
+            .await
+    }
+
+    // The first name of the player, there is no old name to release
+    pub async fn set_profile(&mut self, floor: FloorAddress, name: &str, avatar: u8) -> u64 {
+        let accounts = tufia::accounts::SetProfile {
+            player: player_pda(&self.player.pubkey()),
+            config: config_pda(),
+            name_registry: name_pda(name),
+            old_name_registry: None,
+            game_data: floor_pda(&floor),
+            signer: self.player.pubkey(),
+            system_program: system_program::ID,
+        };
+        let data = tufia::instruction::SetProfile {
+            floor,
+            name: name.to_string(),
+            avatar,
+            title: String::new(),
+        };
+        self.run("set_profile", accounts.to_account_metas(None), data.data())
+            .await
+    }
+
+    pub async fn sync_floor_instance(&mut self, floor: FloorAddress) -> u64 {
+        let accounts = tufia::accounts::SyncFloorInstance {
+            config: config_pda(),
+            directory: directory_pda(floor.floor_id),
+            game_data: floor_pda(&floor),
+        };
+        let data = tufia::instruction::SyncFloorInstance { floor };
+        self.run(
+            "sync_floor_instance",
+            accounts.to_account_metas(None),
+            data.data(),
+        )
+        .await
+    }
+
+    pub async fn claim_boss_reward(&mut self, floor: FloorAddress) -> u64 {
+        let accounts = tufia::accounts::ClaimBossReward {
+            session_token: None,
+            player: player_pda(&self.player.pubkey()),
+            config: config_pda(),
+            boss: boss_pda(&floor),
+            game_data: floor_pda(&floor),
+            signer: self.player.pubkey(),
+        };
+        let data = tufia::instruction::ClaimBossReward { floor, counter: 1 };
+        self.run(
+            "claim_boss_reward",
+            accounts.to_account_metas(None),
+            data.data(),
+        )
+        .await
+    }
+
+    pub async fn rest(&mut self, floor: FloorAddress) -> u64 {
+        let accounts = tufia::accounts::Rest {
+            session_token: None,
+            player: player_pda(&self.player.pubkey()),
+            config: config_pda(),
+            game_data: floor_pda(&floor),
+            signer: self.player.pubkey(),
+        };
+        let data = tufia::instruction::Rest { floor, counter: 1 };
+        self.run("rest", accounts.to_account_metas(None), data.data())
+            .await
+    }
+
+    pub async fn start_daily_run(&mut self, day: u32) -> u64 {
+        let authority = self.player.pubkey();
+        let accounts = tufia::accounts::StartDailyRun {
+            session_token: None,
+            player: player_pda(&authority),
+            daily_run: daily_run_pda(day, &authority),
+            game_data: daily_floor_pda(day, &authority),
+            daily_leaderboard: daily_leaderboard_pda(day),
+            signer: authority,
+            system_program: system_program::ID,
+        };
+        let data = tufia::instruction::StartDailyRun { counter: 1, day };
+        self.run(
+            "start_daily_run",
+            accounts.to_account_metas(None),
+            data.data(),
+        )
+        .await
+    }
+
+    pub async fn daily_move(&mut self, day: u32, x: u64, y: u64) -> u64 {
+        let authority = self.player.pubkey();
+        let accounts = tufia::accounts::DailyMove {
+            session_token: None,
+            player: player_pda(&authority),
+            daily_run: daily_run_pda(day, &authority),
+            game_data: daily_floor_pda(day, &authority),
+            daily_leaderboard: daily_leaderboard_pda(day),
+            signer: authority,
+        };
+        let data = tufia::instruction::DailyMove {
+            counter: 1,
+            day,
+            x,
+            y,
+        };
+        self.run("daily_move", accounts.to_account_metas(None), data.data())
+            .await
+    }
+
+    pub async fn daily_combat_action(&mut self, day: u32, action: u8) -> u64 {
+        let authority = self.player.pubkey();
+        let accounts = tufia::accounts::DailyCombatAction {
+            session_token: None,
+            player: player_pda(&authority),
+            daily_run: daily_run_pda(day, &authority),
+            game_data: daily_floor_pda(day, &authority),
+            daily_leaderboard: daily_leaderboard_pda(day),
+            signer: authority,
+        };
+        let data = tufia::instruction::DailyCombatAction {
+            counter: 1,
+            day,
+            action,
+        };
+        self.run(
+            "daily_combat_action",
+            accounts.to_account_metas(None),
+            data.data(),
+        )
+        .await
+    }
+
+    pub async fn daily_next_floor(&mut self, day: u32) -> u64 {
+        let authority = self.player.pubkey();
+        let accounts = tufia::accounts::DailyNextFloor {
+            session_token: None,
+            player: player_pda(&authority),
+            daily_run: daily_run_pda(day, &authority),
+            game_data: daily_floor_pda(day, &authority),
+            daily_leaderboard: daily_leaderboard_pda(day),
+            signer: authority,
+        };
+        let data = tufia::instruction::DailyNextFloor { counter: 1, day };
+        self.run(
+            "daily_next_floor",
+            accounts.to_account_metas(None),
+            data.data(),
+        )
+        .await
+    }
+
+    pub async fn claim_daily_reward(&mut self, day: u32) -> u64 {
+        let authority = self.player.pubkey();
+        let accounts = tufia::accounts::ClaimDailyReward {
+            daily_leaderboard: daily_leaderboard_pda(day),
+            config: config_pda(),
+            gold_mint: gold_mint_pda(),
+            player_gold: gold_account(&authority),
+            signer: authority,
+            token_program: token::ID,
+            associated_token_program: associated_token::ID,
+            system_program: system_program::ID,
+        };
+        let data = tufia::instruction::ClaimDailyReward { day };
+        self.run(
+            "claim_daily_reward",
+            accounts.to_account_metas(None),
+            data.data(),
+        )
+        .await
+    }
+
+    pub async fn init_leaderboard(&mut self, metric: u8) -> u64 {
+        let accounts = tufia::accounts::InitLeaderboard {
+            config: config_pda(),
+            leaderboard: leaderboard_pda(SEASON, metric),
+            admin: self.player.pubkey(),
+            system_program: system_program::ID,
+        };
+        let data = tufia::instruction::InitLeaderboard { metric };
+        self.run(
+            "init_leaderboard",
+            accounts.to_account_metas(None),
+            data.data(),
+        )
+        .await
+    }
+
+    pub async fn update_leaderboard(&mut self, metric: u8, authority: &Pubkey) -> u64 {
+        let accounts = tufia::accounts::UpdateLeaderboard {
+            leaderboard: leaderboard_pda(SEASON, metric),
+            player: player_pda(authority),
+        };
+        let data = tufia::instruction::UpdateLeaderboard {};
+        self.run(
+            "update_leaderboard",
+            accounts.to_account_metas(None),
+            data.data(),
+        )
+        .await
+    }
+
+    // Archives the leaderboard of the metric, the config moves on to the
+    // next season
+    pub async fn end_season(&mut self, metric: u8) -> u64 {
+        let accounts = tufia::accounts::EndSeason {
+            config: config_pda(),
+            leaderboard: leaderboard_pda(SEASON, metric),
+            archive: season_pda(SEASON),
+            admin: self.player.pubkey(),
+            system_program: system_program::ID,
+        };
+        let data = tufia::instruction::EndSeason {};
+        self.run("end_season", accounts.to_account_metas(None), data.data())
+            .await
+    }
+
+    pub async fn claim_season_reward(&mut self, season: u16) -> u64 {
+        let authority = self.player.pubkey();
+        let accounts = tufia::accounts::ClaimSeasonReward {
+            player: player_pda(&authority),
+            archive: season_pda(season),
+            config: config_pda(),
+            gold_mint: gold_mint_pda(),
+            player_gold: gold_account(&authority),
+            signer: authority,
+            token_program: token::ID,
+            associated_token_program: associated_token::ID,
+            system_program: system_program::ID,
+        };
+        let data = tufia::instruction::ClaimSeasonReward { season };
+        self.run(
+            "claim_season_reward",
+            accounts.to_account_metas(None),
+            data.data(),
+        )
+        .await
+    }
+
+    async fn run(
+        &mut self,
+        name: &str,
+        accounts: Vec<solana_sdk::instruction::AccountMeta>,
+        data: Vec<u8>,
+    ) -> u64 {
+        self.run_signed(name, accounts, data, &[]).await
+    }
+
+    // Sends the instruction, prints the compute units and fails when the
+    // budget is exceeded
+    async fn run_signed(
+        &mut self,
+        name: &str,
+        accounts: Vec<solana_sdk::instruction::AccountMeta>,
+        data: Vec<u8>,
+        signers: &[&Keypair],
+    ) -> u64 {
+        let instruction = Instruction {
+            program_id: tufia::ID,
+            accounts,
+            data,
+        };
+        let blockhash = self.context.get_new_latest_blockhash().await.unwrap();
+        let transaction = Transaction::new_signed_with_payer(
+            &[
+                ComputeBudgetInstruction::set_compute_unit_limit(MAX_COMPUTE_UNITS),
+                instruction,
+            ],
+            Some(&self.player.pubkey()),
+            &[&[&self.player], signers].concat(),
+            blockhash,
+        );
+
+        let result = self
+            .context
+            .banks_client
+            .process_transaction_with_metadata(transaction)
+            .await
+            .unwrap();
+        let metadata = result.metadata.expect("no transaction metadata");
+        if let Err(err) = result.result {
+            panic!("{} failed: {}\n{:#?}", name, err, metadata.log_messages);
+        }
+
+        self.logs = metadata.log_messages;
+        let units = metadata.compute_units_consumed;
+        let budget = budget(name);
+        println!("{:<28} {:>9} CU  budget {:>9}", name, units, budget);
+        assert!(
+            units <= budget,
+            "{} used {} compute units, the budget is {}",
+            name,
+            units,
+            budget
+        );
+        units
+    }
+}
@@ -0,0 +1,17 @@
+use compute_units::{config_pda, Bench};
+use tufia::state::{game_config::GameConfig, player_class::CLASS_WARRIOR};
+
+// The settings of the config, only the admin can change them
+#[tokio::test]
+async fn config() {
+    let mut bench = Bench::new().start(CLASS_WARRIOR).await;
+    bench.update_config(10).await;
+    bench.set_armor_regen(2).await;
+    bench.set_health_regen(60).await;
+    bench.set_item_uri("https://tufia.example/items/").await;
+
+    let config: GameConfig = bench.account(&config_pda()).await;
+    assert_eq!(config.boss_floor_interval, 10);
+    assert_eq!(config.armor_regen_per_move, 2);
+    assert_eq!(config.health_regen_seconds, 60);
+}
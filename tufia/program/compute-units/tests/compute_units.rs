@@ -0,0 +1,249 @@
+use anchor_lang::prelude::Pubkey;
+use compute_units::{boss_pda, enemy_tile, player_pda, player_tile, Bench, BOSS_FLOOR_INTERVAL};
+use solana_sdk::signature::Signer;
+use tufia::{
+    constants::COMBAT_ACTION_ATTACK,
+    state::{
+        boss_data::BossData,
+        floor::FloorAddress,
+        game_data::{GameData, TileData, STATE_BOSS, STATE_STAIRS},
+        inventory::ITEM_STRENGTH_ELIXIR,
+        player_class::CLASS_WARRIOR,
+        player_data::PlayerData,
+    },
+};
+
+const FLOOR: FloorAddress = FloorAddress {
+    floor_id: 0,
+    instance: 0,
+};
+
+const BOSS_FLOOR: FloorAddress = FloorAddress {
+    floor_id: BOSS_FLOOR_INTERVAL as u32,
+    instance: 0,
+};
+
+// Stops a fight that does not end instead of running forever
+const MAX_ROUNDS: u32 = 200;
+
+// Every tile but the one next to the player is taken, the scans over the
+// board find something everywhere
+fn crowded_board(owner: Pubkey) -> GameData {
+    let mut game_data = GameData::default();
+    for column in game_data.data.iter_mut() {
+        for tile in column.iter_mut() {
+            *tile = enemy_tile(owner);
+        }
+    }
+    game_data.data[0][0] = player_tile(owner, 10);
+    game_data.data[0][1] = TileData::default();
+    game_data
+}
+
+// The first player on a floor creates the instance, it is filled with
+// enemies, chests and stairs around them. Recounting the players unpacks the
+// whole floor.
+#[tokio::test]
+async fn first_spawn() {
+    let mut bench = Bench::new().start(CLASS_WARRIOR).await;
+    bench.buy_next_floor("first_spawn", FLOOR).await;
+    bench.sync_floor_instance(FLOOR).await;
+}
+
+#[tokio::test]
+async fn empty_board() {
+    let mut bench = Bench::new();
+    let mut game_data = GameData::default();
+    game_data.data[0][0] = player_tile(bench.player.pubkey(), 10);
+    bench.add_floor(&FLOOR, &game_data);
+
+    let mut bench = bench.start(CLASS_WARRIOR).await;
+    bench.move_to_tile("empty_board", FLOOR, 0, 1).await;
+}
+
+// Moving on a crowded board, the scans over the board find something
+// everywhere
+#[tokio::test]
+async fn full_board() {
+    let mut bench = Bench::new();
+    let game_data = crowded_board(bench.player.pubkey());
+    bench.add_floor(&FLOOR, &game_data);
+
+    let mut bench = bench.start(CLASS_WARRIOR).await;
+    bench.move_to_tile("full_board", FLOOR, 0, 1).await;
+}
+
+// Stepping onto an enemy starts an encounter, every round of it is one
+// combat_action. The enemy has a lot of health and the player little
+// damage, so the fight takes many rounds.
+#[tokio::test]
+async fn long_fight() {
+    let mut bench = Bench::new();
+    let mut game_data = GameData::default();
+    game_data.data[0][0] = player_tile(bench.player.pubkey(), 1_000);
+    game_data.data[0][1] = TileData {
+        tile_health: 40,
+        tile_max_health: 40,
+        tile_defence: 0,
+        ..enemy_tile(Pubkey::new_unique())
+    };
+    bench.add_floor(&FLOOR, &game_data);
+
+    let mut bench = bench.start(CLASS_WARRIOR).await;
+    let player = player_pda(&bench.player.pubkey());
+    bench.move_to_tile("encounter", FLOOR, 0, 1).await;
+
+    let mut rounds = 0;
+    loop {
+        let player_data: PlayerData = bench.account(&player).await;
+        if !player_data.encounter.active {
+            break;
+        }
+        assert!(rounds < MAX_ROUNDS, "The fight did not end");
+        bench
+            .combat_action("long_fight", FLOOR, COMBAT_ACTION_ATTACK)
+            .await;
+        rounds += 1;
+    }
+    println!("The fight took {} rounds", rounds);
+}
+
+// Taking the stairs and joining the instance of the next floor
+#[tokio::test]
+async fn next_floor() {
+    let mut bench = Bench::new();
+    let mut game_data = GameData::default();
+    game_data.data[0][0] = player_tile(bench.player.pubkey(), 10);
+    game_data.data[1][1].tile_type = STATE_STAIRS;
+    bench.add_floor(&FLOOR, &game_data);
+
+    let mut bench = bench.start(CLASS_WARRIOR).await;
+    bench.move_to_next_floor(FLOOR).await;
+    let next = FloorAddress {
+        floor_id: FLOOR.floor_id + 1,
+        instance: 0,
+    };
+    bench.buy_next_floor("next_floor", next).await;
+}
+
+// The first attack spawns the boss account, later ones only update it. The
+// boss has far too much health for a benchmark, so it is defeated by hand
+// before the reward is claimed.
+#[tokio::test]
+async fn boss_fight() {
+    let mut bench = Bench::new();
+    let mut game_data = GameData::default();
+    game_data.floor_id = BOSS_FLOOR.floor_id;
+    game_data.data[0][0] = player_tile(bench.player.pubkey(), 1_000);
+    game_data.data[0][1] = TileData {
+        tile_type: STATE_BOSS,
+        ..enemy_tile(Pubkey::new_unique())
+    };
+    bench.add_floor(&BOSS_FLOOR, &game_data);
+
+    let mut bench = bench.start(CLASS_WARRIOR).await;
+    let authority = bench.player.pubkey();
+    bench
+        .update_player(&authority, |player| {
+            player.current_floor = BOSS_FLOOR_INTERVAL;
+        })
+        .await;
+    bench.attack_boss(BOSS_FLOOR).await;
+    bench.attack_boss(BOSS_FLOOR).await;
+
+    bench
+        .update_account(&boss_pda(&BOSS_FLOOR), |boss: &mut BossData| {
+            boss.health = 0;
+        })
+        .await;
+    bench.claim_boss_reward(BOSS_FLOOR).await;
+}
+
+// Resting heals on a tile without enemies next to it
+#[tokio::test]
+async fn rest() {
+    let mut bench = Bench::new();
+    let mut game_data = GameData::default();
+    game_data.data[0][0] = TileData {
+        tile_health: 1,
+        ..player_tile(bench.player.pubkey(), 10)
+    };
+    bench.add_floor(&FLOOR, &game_data);
+
+    let mut bench = bench.start(CLASS_WARRIOR).await;
+    bench.rest(FLOOR).await;
+}
+
+// The elixir adds a buff, the most work of the consumables
+#[tokio::test]
+async fn use_item() {
+    let mut bench = Bench::new();
+    let mut game_data = GameData::default();
+    game_data.data[0][0] = player_tile(bench.player.pubkey(), 10);
+    bench.add_floor(&FLOOR, &game_data);
+
+    let mut bench = bench.start(CLASS_WARRIOR).await;
+    let authority = bench.player.pubkey();
+    bench
+        .update_player(&authority, |player| {
+            player.add_item(ITEM_STRENGTH_ELIXIR, 1).unwrap();
+        })
+        .await;
+    bench.use_item(FLOOR, ITEM_STRENGTH_ELIXIR).await;
+}
+
+// reset_floor does little more than unpacking the floor and packing an empty
+// board, the difference between a full and an empty floor is what the
+// compact encoding costs for the tiles
+#[tokio::test]
+async fn floor_encoding() {
+    let mut bench = Bench::new();
+    let mut game_data = GameData::default();
+    game_data.data[0][0] = player_tile(bench.player.pubkey(), 10);
+    bench.add_floor(&FLOOR, &game_data);
+    let mut bench = bench.start(CLASS_WARRIOR).await;
+    let empty = bench.reset_floor("empty_board", FLOOR).await;
+
+    let mut bench = Bench::new();
+    let game_data = crowded_board(bench.player.pubkey());
+    bench.add_floor(&FLOOR, &game_data);
+    let mut bench = bench.start(CLASS_WARRIOR).await;
+    let full = bench.reset_floor("full_board", FLOOR).await;
+
+    println!("Unpacking a full board costs {} CU", full - empty);
+}
+
+// Units left as logged by sol_log_compute_units
+fn units_left(logs: &[String]) -> Vec<u64> {
+    logs.iter()
+        .filter_map(|log| log.strip_prefix("Program consumption: "))
+        .filter_map(|log| log.strip_suffix(" units remaining"))
+        .map(|units| units.parse().unwrap())
+        .collect()
+}
+
+// What unpacking and packing a full floor adds to an instruction. Before the
+// compact encoding the tiles were read in place, so this is the difference
+// between before and after. Needs the program built with the
+// measure-encoding feature, see the README.
+#[tokio::test]
+#[ignore]
+async fn encoding_cost() {
+    let mut bench = Bench::new();
+    let game_data = crowded_board(bench.player.pubkey());
+    bench.add_floor(&FLOOR, &game_data);
+    let mut bench = bench.start(CLASS_WARRIOR).await;
+    let total = bench.move_to_tile("full_board", FLOOR, 0, 1).await;
+
+    let units = units_left(&bench.logs);
+    assert_eq!(units.len(), 4, "Built without measure-encoding?");
+    let unpack = units[0] - units[1];
+    let pack = units[2] - units[3];
+    println!(
+        "Unpacking {} CU, packing {} CU, {} of {} CU of the move",
+        unpack,
+        pack,
+        unpack + pack,
+        total
+    );
+}
@@ -0,0 +1,56 @@
+use anchor_lang::prelude::Pubkey;
+use compute_units::{daily_floor_pda, daily_run_pda, enemy_tile, player_pda, player_tile, Bench};
+use solana_sdk::signature::Signer;
+use tufia::{
+    constants::COMBAT_ACTION_ATTACK,
+    state::{
+        daily::DailyRun,
+        game_data::{GameData, TileData, STATE_STAIRS},
+        player_class::CLASS_WARRIOR,
+        player_data::PlayerData,
+    },
+};
+
+// Stops a fight that does not end instead of running forever
+const MAX_ROUNDS: u32 = 200;
+
+// A whole daily run. The floor is generated from the seed of the day, the
+// bench replaces it with a board where the player walks to an enemy, fights
+// it and takes the stairs next to it.
+#[tokio::test]
+async fn daily_run() {
+    let mut bench = Bench::new().start(CLASS_WARRIOR).await;
+    bench.init_gold_mint().await;
+    let authority = bench.player.pubkey();
+    let day = bench.today().await;
+    bench.start_daily_run(day).await;
+
+    let mut game_data = GameData::default();
+    game_data.data[0][0] = player_tile(authority, 1_000);
+    game_data.data[2][0] = TileData {
+        tile_defence: 0,
+        ..enemy_tile(Pubkey::new_unique())
+    };
+    game_data.data[1][1].tile_type = STATE_STAIRS;
+    bench.set_board(&daily_floor_pda(day, &authority), &game_data);
+
+    bench.daily_move(day, 1, 0).await;
+    bench.daily_move(day, 2, 0).await;
+    let mut rounds = 0;
+    loop {
+        let player: PlayerData = bench.account(&player_pda(&authority)).await;
+        if !player.encounter.active {
+            break;
+        }
+        assert!(rounds < MAX_ROUNDS, "The fight did not end");
+        bench.daily_combat_action(day, COMBAT_ACTION_ATTACK).await;
+        rounds += 1;
+    }
+    bench.daily_next_floor(day).await;
+
+    let run: DailyRun = bench.account(&daily_run_pda(day, &authority)).await;
+    assert_eq!(run.depth, 2);
+
+    bench.next_day().await;
+    bench.claim_daily_reward(day).await;
+}
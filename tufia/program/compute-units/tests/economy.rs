@@ -0,0 +1,163 @@
+use anchor_lang::prelude::Pubkey;
+use compute_units::{player_pda, Bench};
+use solana_sdk::signature::Signer;
+use tufia::state::{
+    crafting::Recipe,
+    inventory::{InventoryItem, ITEM_CRYSTAL, ITEM_HEALTH_POTION, ITEM_SCRAP},
+    item::{GearItem, RARITY_RARE},
+    player_class::CLASS_WARRIOR,
+    player_data::PlayerData,
+    shop::{ShopItem, SHOP_LOBBY},
+    trade::{OfferArgs, TradeItems},
+};
+
+fn gear() -> GearItem {
+    GearItem {
+        rarity: RARITY_RARE,
+        damage: 3,
+        defence: 2,
+        armor: 1,
+        ..Default::default()
+    }
+}
+
+// Crafting takes every material of the recipe out of the inventory
+#[tokio::test]
+async fn crafting() {
+    let mut bench = Bench::new().start(CLASS_WARRIOR).await;
+    let recipe = Recipe {
+        materials: vec![
+            InventoryItem {
+                item_id: ITEM_SCRAP,
+                amount: 5,
+            },
+            InventoryItem {
+                item_id: ITEM_CRYSTAL,
+                amount: 2,
+            },
+        ],
+        result: gear(),
+    };
+    bench.set_recipe(0, recipe).await;
+
+    let authority = bench.player.pubkey();
+    bench
+        .update_player(&authority, |player| {
+            player.add_item(ITEM_SCRAP, 5).unwrap();
+            player.add_item(ITEM_CRYSTAL, 2).unwrap();
+        })
+        .await;
+    bench.craft_item(0).await;
+
+    let player: PlayerData = bench.account(&player_pda(&authority)).await;
+    assert_eq!(player.gear.len(), 1);
+}
+
+// Buying and selling in the lobby shop, the gold goes through the treasury
+#[tokio::test]
+async fn shop() {
+    let mut bench = Bench::new().start(CLASS_WARRIOR).await;
+    bench.init_gold_mint().await;
+    let item = ShopItem {
+        item_id: ITEM_HEALTH_POTION,
+        price: 10,
+        sell_price: 5,
+        stock: 100,
+    };
+    bench.set_shop_item(SHOP_LOBBY, item).await;
+
+    let authority = bench.player.pubkey();
+    bench.give_gold(&authority, 100);
+    bench.buy_item(SHOP_LOBBY, ITEM_HEALTH_POTION, 3).await;
+    bench.sell_item(SHOP_LOBBY, ITEM_HEALTH_POTION, 2).await;
+}
+
+// Both sides of a trade move gold, items and gear
+#[tokio::test]
+async fn trade() {
+    let mut bench = Bench::new();
+    let taker = bench.add_wallet();
+    let mut bench = bench.start(CLASS_WARRIOR).await;
+    bench.init_player_of(&taker, CLASS_WARRIOR).await;
+    bench.init_gold_mint().await;
+
+    let maker = bench.player.pubkey();
+    bench
+        .update_player(&maker, |player| {
+            player.add_item(ITEM_SCRAP, 5).unwrap();
+            player.gear.push(gear());
+        })
+        .await;
+    bench
+        .update_player(&taker.pubkey(), |player| {
+            player.add_item(ITEM_CRYSTAL, 2).unwrap();
+            player.gear.push(gear());
+        })
+        .await;
+    bench.give_gold(&maker, 100);
+    bench.give_gold(&taker.pubkey(), 100);
+
+    let args = OfferArgs {
+        items: vec![InventoryItem {
+            item_id: ITEM_SCRAP,
+            amount: 5,
+        }],
+        gear: vec![0],
+        gold: 50,
+        requested: TradeItems {
+            items: vec![InventoryItem {
+                item_id: ITEM_CRYSTAL,
+                amount: 2,
+            }],
+            gear: vec![gear()],
+            gold: 20,
+        },
+        taker: taker.pubkey(),
+        expires_at: i64::MAX,
+    };
+    bench.create_offer(1, args).await;
+    bench.accept_offer(&taker, 1).await;
+
+    let player: PlayerData = bench.account(&player_pda(&maker)).await;
+    assert_eq!(player.item_count(ITEM_CRYSTAL), 2);
+    assert_eq!(player.gear.len(), 1);
+    let player: PlayerData = bench.account(&player_pda(&taker.pubkey())).await;
+    assert_eq!(player.item_count(ITEM_SCRAP), 5);
+    assert_eq!(player.gear.len(), 1);
+}
+
+// Cancelling gives the maker the escrowed items and gold back
+#[tokio::test]
+async fn cancel_trade() {
+    let mut bench = Bench::new().start(CLASS_WARRIOR).await;
+    bench.init_gold_mint().await;
+
+    let maker = bench.player.pubkey();
+    bench
+        .update_player(&maker, |player| {
+            player.add_item(ITEM_SCRAP, 5).unwrap();
+        })
+        .await;
+    bench.give_gold(&maker, 100);
+
+    let args = OfferArgs {
+        items: vec![InventoryItem {
+            item_id: ITEM_SCRAP,
+            amount: 5,
+        }],
+        gear: vec![],
+        gold: 50,
+        requested: TradeItems {
+            items: vec![],
+            gear: vec![],
+            gold: 20,
+        },
+        taker: Pubkey::default(),
+        expires_at: i64::MAX,
+    };
+    bench.create_offer(1, args).await;
+    bench.cancel_offer(1).await;
+
+    let player: PlayerData = bench.account(&player_pda(&maker)).await;
+    assert_eq!(player.item_count(ITEM_SCRAP), 5);
+}
@@ -0,0 +1,41 @@
+use compute_units::{guild_pda, Bench};
+use solana_sdk::signature::Signer;
+use tufia::state::{
+    guild::{Guild, GUILD_ROLE_OFFICER},
+    player_class::CLASS_WARRIOR,
+};
+
+const NAME: &str = "Benchers";
+
+// A guild from its creation to the last member leaving. The treasury is
+// filled and spent on the way.
+#[tokio::test]
+async fn guild() {
+    let mut bench = Bench::new();
+    let member = bench.add_wallet();
+    let mut bench = bench.start(CLASS_WARRIOR).await;
+    bench.init_player_of(&member, CLASS_WARRIOR).await;
+    bench.init_gold_mint().await;
+
+    let leader = bench.player.insecure_clone();
+    bench.give_gold(&leader.pubkey(), 100);
+    bench.give_gold(&member.pubkey(), 0);
+
+    bench.create_guild(NAME).await;
+    bench.invite_to_guild(NAME, &member.pubkey()).await;
+    bench.join_guild(NAME, &member).await;
+    bench
+        .set_guild_role(NAME, &member.pubkey(), GUILD_ROLE_OFFICER)
+        .await;
+    bench.contribute_gold(NAME, 50).await;
+    bench.spend_guild_treasury(NAME, &member.pubkey(), 20).await;
+    bench.update_guild_ranking(NAME, &member.pubkey()).await;
+
+    let guild: Guild = bench.account(&guild_pda(NAME)).await;
+    assert_eq!(guild.members.len(), 2);
+    assert_eq!(guild.contributed, 50);
+
+    bench.leave_guild(NAME, &member).await;
+    bench.leave_guild(NAME, &leader).await;
+    assert!(!bench.account_exists(&guild_pda(NAME)).await);
+}
@@ -0,0 +1,47 @@
+use anchor_spl::metadata::MetadataAccount;
+use compute_units::{config_pda, item_record_pda, metadata_pda, player_pda, Bench};
+use solana_sdk::signature::{Keypair, Signer};
+use tufia::state::{
+    item::{GearItem, RARITY_RARE},
+    player_class::CLASS_WARRIOR,
+    player_data::PlayerData,
+};
+
+// Withdrawing mints the gear as an NFT through the token metadata program,
+// depositing burns it and gives the gear back
+#[tokio::test]
+async fn withdraw_and_deposit_item() {
+    let mut bench = Bench::new();
+    bench.add_token_metadata();
+    let mut bench = bench.start(CLASS_WARRIOR).await;
+    let player = player_pda(&bench.player.pubkey());
+    let gear = GearItem {
+        rarity: RARITY_RARE,
+        damage: 3,
+        defence: 2,
+        armor: 1,
+        floor: 4,
+        ..Default::default()
+    };
+    bench
+        .update_player(&bench.player.pubkey(), |player| player.gear.push(gear))
+        .await;
+
+    let item_mint = Keypair::new();
+    let mint = item_mint.pubkey();
+    bench.withdraw_item(0, &item_mint).await;
+
+    let player_data: PlayerData = bench.account(&player).await;
+    assert!(player_data.gear.is_empty());
+    let metadata: MetadataAccount = bench.account(&metadata_pda(&mint)).await;
+    assert_eq!(metadata.mint, mint);
+    assert_eq!(metadata.update_authority, config_pda());
+    assert_eq!(metadata.name.trim_end_matches('\0'), "Rare Gear of Floor 4");
+
+    bench.deposit_item(&mint).await;
+
+    let player_data: PlayerData = bench.account(&player).await;
+    assert_eq!(player_data.gear.len(), 1);
+    assert!(player_data.gear[0].same_item(&gear));
+    assert!(!bench.account_exists(&item_record_pda(&mint)).await);
+}
@@ -0,0 +1,31 @@
+use compute_units::{party_pda, Bench};
+use solana_sdk::signature::Signer;
+use tufia::state::{
+    party::{Party, PARTY_LOOT_ROUND_ROBIN, PARTY_XP_SHARED},
+    player_class::CLASS_WARRIOR,
+};
+
+// A second player is invited, joins, claims their share and both leave
+// again. The last one to leave closes the party.
+#[tokio::test]
+async fn party() {
+    let mut bench = Bench::new();
+    let member = bench.add_wallet();
+    let mut bench = bench.start(CLASS_WARRIOR).await;
+    bench.init_player_of(&member, CLASS_WARRIOR).await;
+
+    let founder = bench.player.insecure_clone();
+    bench
+        .create_party(PARTY_XP_SHARED, PARTY_LOOT_ROUND_ROBIN)
+        .await;
+    bench.invite(&member.pubkey()).await;
+    bench.join_party(&member).await;
+
+    let party: Party = bench.account(&party_pda(&founder.pubkey())).await;
+    assert_eq!(party.members.len(), 2);
+
+    bench.claim_party_share(&member).await;
+    bench.leave_party(&member).await;
+    bench.leave_party(&founder).await;
+    assert!(!bench.account_exists(&party_pda(&founder.pubkey())).await);
+}
@@ -0,0 +1,72 @@
+use compute_units::{player_tile, Bench};
+use solana_sdk::signature::Signer;
+use tufia::state::{
+    floor::FloorAddress,
+    game_data::GameData,
+    inventory::ITEM_CRYSTAL,
+    item::{GearItem, RARITY_RARE},
+    player_class::CLASS_WARRIOR,
+    player_data::StatAllocation,
+    skills::SKILL_ARMOR_REGEN,
+};
+
+const FLOOR: FloorAddress = FloorAddress {
+    floor_id: 0,
+    instance: 0,
+};
+
+// The instructions that change the player tile on the board as well as the
+// player account
+#[tokio::test]
+async fn stats_and_profile() {
+    let mut bench = Bench::new();
+    let mut game_data = GameData::default();
+    game_data.data[0][0] = player_tile(bench.player.pubkey(), 10);
+    bench.add_floor(&FLOOR, &game_data);
+
+    let mut bench = bench.start(CLASS_WARRIOR).await;
+    let authority = bench.player.pubkey();
+    bench
+        .update_player(&authority, |player| {
+            player.stat_points = 4;
+            player.gear.push(GearItem {
+                rarity: RARITY_RARE,
+                damage: 3,
+                defence: 2,
+                armor: 1,
+                ..Default::default()
+            });
+        })
+        .await;
+
+    let allocation = StatAllocation {
+        health: 1,
+        damage: 1,
+        defence: 1,
+        armor: 1,
+    };
+    bench.allocate_points(FLOOR, allocation).await;
+    bench.equip_item(FLOOR, 0, true).await;
+    bench.set_profile(FLOOR, "Bench", 3).await;
+}
+
+// Skills and upgrades only change the player account
+#[tokio::test]
+async fn skills_and_upgrades() {
+    let mut bench = Bench::new().start(CLASS_WARRIOR).await;
+    let authority = bench.player.pubkey();
+    bench
+        .update_player(&authority, |player| {
+            player.skill_points = 5;
+            player.gear.push(GearItem {
+                rarity: RARITY_RARE,
+                damage: 3,
+                ..Default::default()
+            });
+            player.add_item(ITEM_CRYSTAL, 10).unwrap();
+        })
+        .await;
+
+    bench.learn_skill(SKILL_ARMOR_REGEN).await;
+    bench.upgrade_item(0).await;
+}
@@ -0,0 +1,30 @@
+use compute_units::{player_pda, Bench};
+use solana_sdk::signature::Signer;
+use tufia::state::{
+    leaderboard::LEADERBOARD_METRIC_MAX_FLOOR, player_class::CLASS_WARRIOR, player_data::PlayerData,
+};
+
+// The leaderboard of a season is archived when it ends, the best players
+// claim their reward from the archive
+#[tokio::test]
+async fn season() {
+    let mut bench = Bench::new().start(CLASS_WARRIOR).await;
+    bench.init_gold_mint().await;
+    bench.init_leaderboard(LEADERBOARD_METRIC_MAX_FLOOR).await;
+
+    let authority = bench.player.pubkey();
+    bench
+        .update_player(&authority, |player| {
+            player.current_floor = 3;
+            player.record_floor();
+        })
+        .await;
+    bench
+        .update_leaderboard(LEADERBOARD_METRIC_MAX_FLOOR, &authority)
+        .await;
+    bench.end_season(LEADERBOARD_METRIC_MAX_FLOOR).await;
+    bench.claim_season_reward(0).await;
+
+    let player: PlayerData = bench.account(&player_pda(&authority)).await;
+    assert_eq!(player.badges.len(), 1);
+}
@@ -1,3 +1,80 @@
 pub const TIME_TO_REFILL_ENERGY: i64 = 60;
 pub const MAX_ENERGY: u32 = 100;
 pub const MAX_WOOD_PER_TREE: u32 = 100000;
+pub const SHRINE_DAMAGE_BONUS: u32 = 2;
+pub const SHRINE_BUFF_MOVES: u32 = 10;
+pub const BOSS_HEALTH_MULTIPLIER: u32 = 20;
+pub const BOSS_XP_MULTIPLIER: u32 = 10;
+pub const MAX_BOSS_CONTRIBUTORS: usize = 10;
+pub const COMBAT_ACTION_ATTACK: u8 = 0;
+pub const COMBAT_ACTION_DEFEND: u8 = 1;
+pub const COMBAT_ACTION_FLEE: u8 = 2;
+pub const COMBAT_ACTION_USE_ITEM: u8 = 3;
+pub const COMBAT_ACTION_ABILITY: u8 = 4;
+pub const DEFEND_ARMOR_BONUS: u32 = 2;
+pub const FLEE_BASE_CHANCE: u32 = 40;
+pub const POTION_HEAL: u32 = 5;
+pub const ENEMY_CRIT_CHANCE: u8 = 5;
+pub const ENEMY_CRIT_MULTIPLIER: u8 = 150;
+pub const ENEMY_EVASION: u8 = 5;
+pub const ENEMY_DAMAGE_RANGE: u8 = 1;
+pub const ENERGY_PER_MOVE: u32 = 1;
+pub const ENERGY_PER_COMBAT_ROUND: u32 = 1;
+pub const MAX_PVP_ROUNDS: u32 = 20; // player fights are resolved in one go
+pub const STAT_POINTS_PER_LEVEL: u16 = 2;
+pub const SKILL_POINTS_PER_LEVEL: u16 = 1;
+pub const HEALTH_PER_STAT_POINT: u32 = 2;
+pub const MIN_NAME_LENGTH: usize = 3;
+pub const MAX_NAME_LENGTH: usize = 12;
+pub const MAX_TITLE_LENGTH: usize = 24;
+pub const TILE_NAME_LENGTH: usize = MAX_NAME_LENGTH; // tiles keep the whole name
+pub const AVATAR_COUNT: u8 = 16;
+pub const LEADERBOARD_SIZE: usize = 20;
+pub const SEASON_REWARD_RANKS: usize = 10;
+pub const SEASON_REWARD_GOLD: u64 = 1000;
+pub const MAX_SEASON_BADGES: usize = 16;
+pub const SECONDS_PER_DAY: i64 = 86400;
+pub const DAILY_DUNGEON_SEED: u64 = 0x7475_6669_6164_6179;
+pub const DAILY_LEADERBOARD_SIZE: usize = 10;
+pub const DAILY_REWARD_RANKS: usize = 3;
+pub const DAILY_REWARD_GOLD: u64 = 300;
+pub const MAX_PLAYERS_PER_INSTANCE: usize = 8;
+pub const MAX_FLOOR_INSTANCES: usize = 64;
+pub const MAX_PARTY_SIZE: usize = 4;
+pub const PARTY_XP_SHARE_PERCENT: u32 = 50;
+pub const MAX_GUILD_MEMBERS: usize = 20;
+pub const MAX_GUILD_INVITES: usize = 5;
+pub const GUILD_LEADERBOARD_SIZE: usize = 20;
+pub const MERCHANT_FLOOR_INTERVAL: u32 = 5;
+pub const MAX_SHOP_ITEMS: usize = 16;
+pub const MAX_INVENTORY_ITEMS: usize = 20;
+pub const MAX_GEAR_ITEMS: usize = 8;
+pub const GEAR_DROP_CHANCE: u64 = 25;
+pub const ITEM_SYMBOL: &str = "TUFIA";
+pub const MAX_ITEM_URI_LENGTH: usize = 150; // leaves room for the mint in the 200 bytes of metaplex
+pub const MAX_EQUIPPED_GEAR: usize = 3;
+pub const MAX_TRADE_ITEMS: usize = 4;
+pub const MAX_RECIPES: usize = 8;
+pub const MAX_RECIPE_MATERIALS: usize = 3;
+pub const ENEMY_MATERIAL_CHANCE: u64 = 40;
+pub const CHEST_MATERIAL_CHANCE: u64 = 60;
+pub const MAX_GEAR_TIER: u8 = 10;
+pub const UPGRADE_BASE_CHANCE: u64 = 90;
+pub const UPGRADE_CHANCE_STEP: u64 = 10;
+pub const UPGRADE_MIN_CHANCE: u64 = 10;
+pub const MAX_BUFFS: usize = 4;
+pub const ARMOR_POTION_DEFENCE: u32 = 2;
+pub const ARMOR_POTION_MOVES: u16 = 10;
+pub const STRENGTH_ELIXIR_DAMAGE: u32 = 3;
+pub const STRENGTH_ELIXIR_SECONDS: i64 = 600;
+pub const ENERGY_TONIC_ENERGY: u32 = 20;
+pub const ENEMY_STATUS_CHANCE: u64 = 30;
+pub const STATUS_TURNS: u8 = 3;
+pub const POISON_DAMAGE: u32 = 1;
+pub const BURN_DAMAGE: u32 = 2;
+pub const GEAR_STATUS_CHANCE: u64 = 50;
+pub const ARMOR_REGEN_PER_MOVE: u32 = 1; // default of the config
+pub const CHEST_ARMOR: u32 = 2;
+pub const HEALTH_REGEN_SECONDS: i64 = 120; // default of the config
+pub const REST_ENERGY: u32 = 3;
+pub const REST_HEAL: u32 = 3;
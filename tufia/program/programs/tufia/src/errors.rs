@@ -19,4 +19,232 @@ pub enum GameErrorCode {
 
     #[msg("PlayerIsAlreadyOnThisTile")]
     PlayerIsAlreadyOnThisTile,
+
+    #[msg("DoorIsLocked")]
+    DoorIsLocked,
+
+    #[msg("WrongAdmin")]
+    WrongAdmin,
+
+    #[msg("NotABossFloor")]
+    NotABossFloor,
+
+    #[msg("NotNextToBoss")]
+    NotNextToBoss,
+
+    #[msg("BossAlreadyDefeated")]
+    BossAlreadyDefeated,
+
+    #[msg("BossNotDefeated")]
+    BossNotDefeated,
+
+    #[msg("TooManyBossContributors")]
+    TooManyBossContributors,
+
+    #[msg("NoBossDamageDealt")]
+    NoBossDamageDealt,
+
+    #[msg("BossRewardAlreadyClaimed")]
+    BossRewardAlreadyClaimed,
+
+    #[msg("InCombat")]
+    InCombat,
+
+    #[msg("NotInCombat")]
+    NotInCombat,
+
+    #[msg("InvalidCombatAction")]
+    InvalidCombatAction,
+
+    #[msg("NoConsumables")]
+    NoConsumables,
+
+    #[msg("InvalidClass")]
+    InvalidClass,
+
+    #[msg("NoClassAbility")]
+    NoClassAbility,
+
+    #[msg("AbilityAlreadyUsed")]
+    AbilityAlreadyUsed,
+
+    #[msg("NotEnoughStatPoints")]
+    NotEnoughStatPoints,
+
+    #[msg("NotEnoughSkillPoints")]
+    NotEnoughSkillPoints,
+
+    #[msg("InvalidSkill")]
+    InvalidSkill,
+
+    #[msg("SkillAlreadyLearned")]
+    SkillAlreadyLearned,
+
+    #[msg("SkillRequirementMissing")]
+    SkillRequirementMissing,
+
+    #[msg("InvalidName")]
+    InvalidName,
+
+    #[msg("InvalidTitle")]
+    InvalidTitle,
+
+    #[msg("InvalidAvatar")]
+    InvalidAvatar,
+
+    #[msg("NameTaken")]
+    NameTaken,
+
+    #[msg("WrongNameRegistry")]
+    WrongNameRegistry,
+
+    #[msg("InvalidLeaderboardMetric")]
+    InvalidLeaderboardMetric,
+
+    #[msg("WrongSeason")]
+    WrongSeason,
+
+    #[msg("NoSeasonReward")]
+    NoSeasonReward,
+
+    #[msg("SeasonRewardAlreadyClaimed")]
+    SeasonRewardAlreadyClaimed,
+
+    #[msg("WrongDay")]
+    WrongDay,
+
+    #[msg("DailyRunFinished")]
+    DailyRunFinished,
+
+    #[msg("NotNextToStairs")]
+    NotNextToStairs,
+
+    #[msg("DailyNotOver")]
+    DailyNotOver,
+
+    #[msg("NoDailyReward")]
+    NoDailyReward,
+
+    #[msg("DailyRewardAlreadyClaimed")]
+    DailyRewardAlreadyClaimed,
+
+    #[msg("WrongFloor")]
+    WrongFloor,
+
+    #[msg("WrongInstance")]
+    WrongInstance,
+
+    #[msg("TooManyInstances")]
+    TooManyInstances,
+
+    #[msg("InstanceFull")]
+    InstanceFull,
+
+    #[msg("InvalidPartyRule")]
+    InvalidPartyRule,
+
+    #[msg("PartyFull")]
+    PartyFull,
+
+    #[msg("AlreadyInParty")]
+    AlreadyInParty,
+
+    #[msg("NotInvited")]
+    NotInvited,
+
+    #[msg("NotInParty")]
+    NotInParty,
+
+    #[msg("NotPartyLeader")]
+    NotPartyLeader,
+
+    #[msg("WrongParty")]
+    WrongParty,
+
+    #[msg("FriendlyFire")]
+    FriendlyFire,
+
+    #[msg("AlreadyInGuild")]
+    AlreadyInGuild,
+
+    #[msg("NotInGuild")]
+    NotInGuild,
+
+    #[msg("GuildFull")]
+    GuildFull,
+
+    #[msg("NotGuildOfficer")]
+    NotGuildOfficer,
+
+    #[msg("NotGuildLeader")]
+    NotGuildLeader,
+
+    #[msg("InvalidGuildRole")]
+    InvalidGuildRole,
+
+    #[msg("InvalidShop")]
+    InvalidShop,
+
+    #[msg("ShopFull")]
+    ShopFull,
+
+    #[msg("ItemNotForSale")]
+    ItemNotForSale,
+
+    #[msg("OutOfStock")]
+    OutOfStock,
+
+    #[msg("NotAtShop")]
+    NotAtShop,
+
+    #[msg("InventoryFull")]
+    InventoryFull,
+
+    #[msg("NotEnoughItems")]
+    NotEnoughItems,
+
+    #[msg("GearFull")]
+    GearFull,
+
+    #[msg("InvalidItem")]
+    InvalidItem,
+
+    #[msg("InvalidItemUri")]
+    InvalidItemUri,
+
+    #[msg("ItemEquipped")]
+    ItemEquipped,
+
+    #[msg("TooManyEquipped")]
+    TooManyEquipped,
+
+    #[msg("TradeTooLarge")]
+    TradeTooLarge,
+
+    #[msg("OfferExpired")]
+    OfferExpired,
+
+    #[msg("NotOfferTaker")]
+    NotOfferTaker,
+
+    #[msg("InvalidRecipe")]
+    InvalidRecipe,
+
+    #[msg("MaxTierReached")]
+    MaxTierReached,
+
+    #[msg("NotConsumable")]
+    NotConsumable,
+
+    #[msg("TooManyBuffs")]
+    TooManyBuffs,
+
+    #[msg("NotSafeToRest")]
+    NotSafeToRest,
+
+    #[msg("ShopOverflow")]
+    ShopOverflow,
+
+    #[msg("StatOverflow")]
+    StatOverflow,
 }
@@ -0,0 +1,132 @@
+pub use crate::errors::GameErrorCode;
+use crate::state::{player_data::PlayerData, trade::Offer};
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{self, CloseAccount, Mint, Token, TokenAccount, Transfer},
+};
+
+// Swaps both sides at once. The requested items come from the taker, the
+// offered ones from the escrow, so neither side can back out half way.
+pub fn accept_offer(ctx: Context<AcceptOffer>) -> Result<()> {
+    let offer = &ctx.accounts.offer;
+    offer.check_taker(ctx.accounts.signer.key())?;
+
+    ctx.accounts.player.take_items(&offer.requested)?;
+    ctx.accounts.maker_player.give_items(&offer.requested)?;
+    ctx.accounts.player.give_items(&offer.offered)?;
+
+    if offer.requested.gold > 0 {
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.taker_gold.to_account_info(),
+                    to: ctx.accounts.maker_gold.to_account_info(),
+                    authority: ctx.accounts.signer.to_account_info(),
+                },
+            ),
+            offer.requested.gold,
+        )?;
+    }
+
+    let maker = offer.maker;
+    let id = offer.id.to_le_bytes();
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        b"offer".as_ref(),
+        maker.as_ref(),
+        id.as_ref(),
+        &[ctx.bumps.offer],
+    ]];
+    if offer.offered.gold > 0 {
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.escrow_gold.to_account_info(),
+                    to: ctx.accounts.taker_gold.to_account_info(),
+                    authority: ctx.accounts.offer.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            offer.offered.gold,
+        )?;
+    }
+    token::close_account(CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        CloseAccount {
+            account: ctx.accounts.escrow_gold.to_account_info(),
+            destination: ctx.accounts.maker.to_account_info(),
+            authority: ctx.accounts.offer.to_account_info(),
+        },
+        signer_seeds,
+    ))?;
+
+    msg!("Offer {} accepted", ctx.accounts.offer.id);
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct AcceptOffer<'info> {
+    #[account(
+        mut,
+        seeds = [b"player1".as_ref(), signer.key().as_ref()],
+        bump,
+    )]
+    pub player: Account<'info, PlayerData>,
+
+    #[account(
+        mut,
+        seeds = [b"player1".as_ref(), offer.maker.as_ref()],
+        bump,
+    )]
+    pub maker_player: Account<'info, PlayerData>,
+
+    #[account(
+        mut,
+        close = maker,
+        seeds = [b"offer".as_ref(), offer.maker.as_ref(), offer.id.to_le_bytes().as_ref()],
+        bump,
+        has_one = maker,
+    )]
+    pub offer: Account<'info, Offer>,
+
+    /// CHECK: Gets the rent of the offer back
+    #[account(mut)]
+    pub maker: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"gold".as_ref()],
+        bump,
+    )]
+    pub gold_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = gold_mint,
+        associated_token::authority = offer,
+    )]
+    pub escrow_gold: Account<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = signer,
+        associated_token::mint = gold_mint,
+        associated_token::authority = signer,
+    )]
+    pub taker_gold: Account<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = signer,
+        associated_token::mint = gold_mint,
+        associated_token::authority = maker,
+    )]
+    pub maker_gold: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,125 @@
+pub use crate::errors::GameErrorCode;
+use crate::state::floor_data::FloorData;
+pub use crate::state::game_data::GameData;
+use crate::state::{
+    boss_data::BossData,
+    floor::FloorAddress,
+    game_config::GameConfig,
+    leaderboard::{self, Leaderboard},
+    player_data::PlayerData,
+};
+use anchor_lang::prelude::*;
+use session_keys::{Session, SessionToken};
+
+pub fn attack_boss(mut ctx: Context<AttackBoss>, floor: FloorAddress, counter: u16) -> Result<()> {
+    let account: &mut &mut AttackBoss<'_> = &mut ctx.accounts;
+    account.player.update_energy()?;
+
+    if account.player.energy < 1 {
+        return err!(GameErrorCode::NotEnoughEnergy);
+    }
+
+    account.player.last_id = counter;
+    account.player.sync_season(account.config.season);
+    floor.check_player_instance(&account.player)?;
+
+    if account.player.encounter.active {
+        return err!(GameErrorCode::InCombat);
+    }
+
+    let floor_data = &mut account.game_data.load_mut()?;
+    let game_data = &mut floor_data.board();
+    game_data.update_buffs(&mut account.player)?;
+
+    if !account.config.is_boss_floor(game_data.floor_id) {
+        return err!(GameErrorCode::NotABossFloor);
+    }
+
+    // The first attack on a floor wakes the boss up
+    if account.boss.max_health == 0 {
+        account.boss.spawn(game_data.floor_id);
+    }
+
+    if account.boss.health == 0 {
+        return err!(GameErrorCode::BossAlreadyDefeated);
+    }
+
+    let deaths = account.player.stats.deaths;
+    game_data.attack_boss(&mut account.player, &mut account.boss)?;
+    if account.player.stats.deaths != deaths {
+        leaderboard::submit_if_present(&mut account.leaderboard, &account.player)?;
+    }
+
+    floor_data.save(game_data)?;
+    Ok(())
+}
+
+#[derive(Accounts, Session)]
+#[instruction(floor: FloorAddress)]
+pub struct AttackBoss<'info> {
+    #[session(
+        // The ephemeral key pair signing the transaction
+        signer = signer,
+        // The authority of the user account which must have created the session
+        authority = player.authority.key()
+    )]
+    // Session Tokens are passed as optional accounts
+    pub session_token: Option<Account<'info, SessionToken>>,
+
+    // There is one PlayerData account
+    #[account(
+        mut,
+        seeds = [b"player1".as_ref(), player.authority.key().as_ref()],
+        bump,
+    )]
+    pub player: Account<'info, PlayerData>,
+
+    #[account(
+        seeds = [b"config".as_ref()],
+        bump,
+    )]
+    pub config: Account<'info, GameConfig>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"floor".as_ref(),
+            config.season.to_le_bytes().as_ref(),
+            floor.floor_id.to_le_bytes().as_ref(),
+            floor.instance.to_le_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub game_data: AccountLoader<'info, FloorData>,
+
+    // The boss of the floor. First player attacking it pays for the account.
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = 500, // 8+4*6+8+4+10*(32+4+1) for up to 10 contributors
+        seeds = [
+            b"boss".as_ref(),
+            config.season.to_le_bytes().as_ref(),
+            floor.floor_id.to_le_bytes().as_ref(),
+            floor.instance.to_le_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub boss: Account<'info, BossData>,
+
+    // Optional, updated when the player dies
+    #[account(
+        mut,
+        seeds = [
+            b"leaderboard".as_ref(),
+            leaderboard.season.to_le_bytes().as_ref(),
+            &[leaderboard.metric],
+        ],
+        bump,
+    )]
+    pub leaderboard: Option<Account<'info, Leaderboard>>,
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,107 @@
+pub use crate::errors::GameErrorCode;
+use crate::state::floor_data::FloorData;
+pub use crate::state::game_data::GameData;
+use crate::state::{
+    floor::FloorAddress, game_config::GameConfig, player_data::PlayerData, shop::Shop,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+
+pub fn buy_item(
+    mut ctx: Context<BuyItem>,
+    floor: FloorAddress,
+    _kind: u8,
+    item_id: u16,
+    amount: u32,
+) -> Result<()> {
+    let account: &mut &mut BuyItem<'_> = &mut ctx.accounts;
+    let game_data = match &account.game_data {
+        Some(game_data) => {
+            floor.check_player_instance(&account.player)?;
+            Some(game_data.load()?.board())
+        }
+        None => None,
+    };
+    account
+        .shop
+        .check_access(&account.player, game_data.as_ref())?;
+
+    let price = account.shop.buy(item_id, amount)?;
+    account.player.add_item(item_id, amount)?;
+
+    token::transfer(
+        CpiContext::new(
+            account.token_program.to_account_info(),
+            Transfer {
+                from: account.player_gold.to_account_info(),
+                to: account.treasury.to_account_info(),
+                authority: account.signer.to_account_info(),
+            },
+        ),
+        price,
+    )?;
+
+    msg!("Bought {} of item {} for {} gold", amount, item_id, price);
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(floor: FloorAddress, kind: u8)]
+pub struct BuyItem<'info> {
+    // Shops are paid with gold, so they are used by the wallet itself
+    #[account(
+        mut,
+        seeds = [b"player1".as_ref(), signer.key().as_ref()],
+        bump,
+    )]
+    pub player: Account<'info, PlayerData>,
+
+    #[account(
+        seeds = [b"config".as_ref()],
+        bump,
+    )]
+    pub config: Account<'info, GameConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"shop".as_ref(), &[kind]],
+        bump,
+    )]
+    pub shop: Account<'info, Shop>,
+
+    // Only needed for the merchant shop, the player has to stand next to the
+    // merchant on this floor
+    #[account(
+        seeds = [
+            b"floor".as_ref(),
+            config.season.to_le_bytes().as_ref(),
+            floor.floor_id.to_le_bytes().as_ref(),
+            floor.instance.to_le_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub game_data: Option<AccountLoader<'info, FloorData>>,
+
+    #[account(
+        seeds = [b"gold".as_ref()],
+        bump,
+    )]
+    pub gold_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = gold_mint,
+        associated_token::authority = signer,
+    )]
+    pub player_gold: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = gold_mint,
+        associated_token::authority = shop,
+    )]
+    pub treasury: Account<'info, TokenAccount>,
+
+    pub signer: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
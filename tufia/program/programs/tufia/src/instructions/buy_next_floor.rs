@@ -1,43 +1,102 @@
 pub use crate::errors::GameErrorCode;
+use crate::state::floor_data::FloorData;
 pub use crate::state::game_data::GameData;
-use crate::state::{game_data::TileData, player_data::PlayerData};
-use anchor_lang::prelude::{borsh::de, *};
+use crate::state::{
+    floor::{FloorAddress, FloorDirectory},
+    game_config::GameConfig,
+    game_data::TileData,
+    party::{self, Party},
+    player_data::PlayerData,
+};
+use anchor_lang::prelude::*;
 use session_keys::{Session, SessionToken};
 
 pub fn buy_next_floor(
     mut ctx: Context<BuyNextFloor>,
     counter: u16,
-    _level_seed: String,
+    floor: FloorAddress,
 ) -> Result<()> {
     let account: &mut &mut BuyNextFloor<'_> = &mut ctx.accounts;
     account.player.last_id = counter;
+    account.player.sync_season(account.config.season);
+    floor.check_player_floor(account.player.current_floor)?;
 
-    let game_data = &mut account.game_data.load_init()?;
-    game_data.owner = account.signer.key();
-    game_data.floor_id = account.player.current_floor as u32;
+    if account.player.encounter.active {
+        return err!(GameErrorCode::InCombat);
+    }
 
-    msg!("Buy next floor {}", account.player.current_floor);
+    // The directory decides which instance the player goes to. Only when all
+    // instances are full a new one is created.
+    let directory = &mut account.directory;
+    directory.season = account.config.season;
+    directory.floor_id = floor.floor_id;
+    let new_instance = match &mut account.party {
+        Some(party) => {
+            party::check_member(party, &account.player)?;
+            // A party plays on one instance, the first member picks it
+            let new_instance = match party.instance_for(directory.season, floor.floor_id) {
+                Some(instance) => directory.route_to(floor.instance, instance)?,
+                None => directory.route(floor.instance, party.members.len())?,
+            };
+            party.set_instance(directory.season, floor.floor_id, floor.instance);
+            new_instance
+        }
+        None => directory.route(floor.instance, 1)?,
+    };
 
-    let mut tile_data_clone: TileData = TileData::default();
+    // A player is on one instance at a time, they leave the one they are on.
+    // When it is the instance they join again they are moved below.
+    let old_instance = account.player.current_instance;
+    if let Some(old_game_data) = &account.old_game_data {
+        if old_instance != floor.instance {
+            let floor_data = &mut old_game_data.load_mut()?;
+            let game_data = &mut floor_data.board();
+            game_data.remove_player(account.player.authority)?;
+            directory.update(old_instance, game_data.count_players());
+            floor_data.save(game_data)?;
+        }
+    }
+    account.player.current_instance = floor.instance;
 
-    tile_data_clone.tile_armor = account.player.tile_data.tile_armor;
-    tile_data_clone.tile_damage = account.player.tile_data.tile_damage;
-    tile_data_clone.tile_defence = account.player.tile_data.tile_defence;
-    tile_data_clone.tile_health = account.player.tile_data.tile_health;
-    tile_data_clone.tile_level = account.player.tile_data.tile_level;
-    tile_data_clone.tile_max_armor = account.player.tile_data.tile_max_armor;
-    tile_data_clone.tile_max_health = account.player.tile_data.tile_max_health;
-    tile_data_clone.tile_owner = account.player.tile_data.tile_owner;
-    tile_data_clone.tile_type = account.player.tile_data.tile_type;
-    tile_data_clone.tile_xp = account.player.tile_data.tile_xp;
+    msg!(
+        "Buy next floor {} instance {}",
+        account.player.current_floor,
+        floor.instance
+    );
 
-    game_data.spawn_player(account.signer.key(), tile_data_clone);
+    // Buffs that ran out must not be carried to the new floor
+    account.player.update_buffs()?;
+    account
+        .player
+        .update_health(account.config.health_regen_seconds)?;
+
+    let tile_data_clone: TileData = (&account.player.tile_data).into();
+
+    if new_instance {
+        let floor_data = &mut account.game_data.load_init()?;
+        let game_data = &mut floor_data.board();
+        game_data.check_floor(account.player.authority, &floor)?;
+
+        let boss_floor = account.config.is_boss_floor(game_data.floor_id);
+        game_data.spawn_player(account.player.authority, tile_data_clone, boss_floor)?;
+        directory.update(floor.instance, game_data.count_players());
+        floor_data.save(game_data)?;
+    } else {
+        let floor_data = &mut account.game_data.load_mut()?;
+        let game_data = &mut floor_data.board();
+        game_data.check_floor(account.player.authority, &floor)?;
+
+        game_data.remove_player(account.player.authority)?;
+        game_data.place_player(account.player.authority, tile_data_clone)?;
+        directory.update(floor.instance, game_data.count_players());
+        floor_data.save(game_data)?;
+    }
 
     Ok(())
 }
 
 #[derive(Accounts, Session)]
-#[instruction(level_seed: String)]
+#[instruction(floor: FloorAddress)]
 pub struct BuyNextFloor<'info> {
     #[session(
         // The ephemeral key pair signing the transaction
@@ -56,16 +115,64 @@ pub struct BuyNextFloor<'info> {
     )]
     pub player: Account<'info, PlayerData>,
 
-    // There can be multiple levels the seed for the level is passed in the instruction
-    // First player starting a new level will pay for the account in the current setup
     #[account(
-        init,
+        seeds = [b"config".as_ref()],
+        bump,
+    )]
+    pub config: Account<'info, GameConfig>,
+
+    // First player on a floor pays for the directory
+    #[account(
+        init_if_needed,
         payer = signer,
-        space = 10240,
-        seeds = [level_seed.as_ref()],
+        space = 100, // 8+2+4+4+64
+        seeds = [
+            b"directory".as_ref(),
+            config.season.to_le_bytes().as_ref(),
+            floor.floor_id.to_le_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub directory: Account<'info, FloorDirectory>,
+
+    // There can be multiple instances of every floor, the address is passed in the instruction
+    // First player starting a new instance will pay for the account in the current setup.
+    // Only the directory creates instances, see FloorDirectory::route.
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = FloorData::SPACE,
+        seeds = [
+            b"floor".as_ref(),
+            config.season.to_le_bytes().as_ref(),
+            floor.floor_id.to_le_bytes().as_ref(),
+            floor.instance.to_le_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub game_data: AccountLoader<'info, FloorData>,
+
+    // Optional, the instance of this floor the player is on now. Has to be
+    // passed when it exists so the player does not stay behind on it.
+    #[account(
+        mut,
+        seeds = [
+            b"floor".as_ref(),
+            config.season.to_le_bytes().as_ref(),
+            floor.floor_id.to_le_bytes().as_ref(),
+            player.current_instance.to_le_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub old_game_data: Option<AccountLoader<'info, FloorData>>,
+
+    // Optional, keeps the party on one instance
+    #[account(
+        mut,
+        seeds = [b"party".as_ref(), party.founder.as_ref()],
         bump,
     )]
-    pub game_data: AccountLoader<'info, GameData>,
+    pub party: Option<Account<'info, Party>>,
 
     #[account(mut)]
     pub signer: Signer<'info>,
@@ -0,0 +1,87 @@
+pub use crate::errors::GameErrorCode;
+use crate::state::{player_data::PlayerData, trade::Offer};
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, CloseAccount, Mint, Token, TokenAccount, Transfer};
+
+// The maker gets the escrow back. Expired offers can only be cancelled.
+pub fn cancel_offer(ctx: Context<CancelOffer>) -> Result<()> {
+    let offer = &ctx.accounts.offer;
+    ctx.accounts.player.give_items(&offer.offered)?;
+
+    let maker = offer.maker;
+    let id = offer.id.to_le_bytes();
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        b"offer".as_ref(),
+        maker.as_ref(),
+        id.as_ref(),
+        &[ctx.bumps.offer],
+    ]];
+    if offer.offered.gold > 0 {
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.escrow_gold.to_account_info(),
+                    to: ctx.accounts.maker_gold.to_account_info(),
+                    authority: ctx.accounts.offer.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            offer.offered.gold,
+        )?;
+    }
+    token::close_account(CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        CloseAccount {
+            account: ctx.accounts.escrow_gold.to_account_info(),
+            destination: ctx.accounts.signer.to_account_info(),
+            authority: ctx.accounts.offer.to_account_info(),
+        },
+        signer_seeds,
+    ))?;
+
+    msg!("Offer {} cancelled", ctx.accounts.offer.id);
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CancelOffer<'info> {
+    #[account(
+        mut,
+        seeds = [b"player1".as_ref(), signer.key().as_ref()],
+        bump,
+    )]
+    pub player: Account<'info, PlayerData>,
+
+    #[account(
+        mut,
+        close = signer,
+        seeds = [b"offer".as_ref(), signer.key().as_ref(), offer.id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub offer: Account<'info, Offer>,
+
+    #[account(
+        seeds = [b"gold".as_ref()],
+        bump,
+    )]
+    pub gold_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = gold_mint,
+        associated_token::authority = offer,
+    )]
+    pub escrow_gold: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = gold_mint,
+        associated_token::authority = signer,
+    )]
+    pub maker_gold: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
@@ -0,0 +1,83 @@
+pub use crate::errors::GameErrorCode;
+use crate::state::floor_data::FloorData;
+use crate::state::{
+    boss_data::BossData, floor::FloorAddress, game_config::GameConfig, player_data::PlayerData,
+};
+use anchor_lang::prelude::*;
+use session_keys::{Session, SessionToken};
+
+pub fn claim_boss_reward(
+    mut ctx: Context<ClaimBossReward>,
+    floor: FloorAddress,
+    counter: u16,
+) -> Result<()> {
+    let account: &mut &mut ClaimBossReward<'_> = &mut ctx.accounts;
+    account.player.last_id = counter;
+    // The reward is claimed on the floor of the boss
+    floor.check_player_instance(&account.player)?;
+
+    let xp = account.boss.claim_reward(account.player.authority)?;
+    let levels = account.player.add_xp(xp);
+    msg!("Boss reward {} xp", xp);
+
+    let floor_data = &mut account.game_data.load_mut()?;
+    let game_data = &mut floor_data.board();
+    game_data.apply_level_up(&mut account.player, levels);
+    floor_data.save(game_data)?;
+
+    Ok(())
+}
+
+#[derive(Accounts, Session)]
+#[instruction(floor: FloorAddress)]
+pub struct ClaimBossReward<'info> {
+    #[session(
+        // The ephemeral key pair signing the transaction
+        signer = signer,
+        // The authority of the user account which must have created the session
+        authority = player.authority.key()
+    )]
+    // Session Tokens are passed as optional accounts
+    pub session_token: Option<Account<'info, SessionToken>>,
+
+    // There is one PlayerData account
+    #[account(
+        mut,
+        seeds = [b"player1".as_ref(), player.authority.key().as_ref()],
+        bump,
+    )]
+    pub player: Account<'info, PlayerData>,
+
+    #[account(
+        seeds = [b"config".as_ref()],
+        bump,
+    )]
+    pub config: Account<'info, GameConfig>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"boss".as_ref(),
+            config.season.to_le_bytes().as_ref(),
+            floor.floor_id.to_le_bytes().as_ref(),
+            floor.instance.to_le_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub boss: Account<'info, BossData>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"floor".as_ref(),
+            config.season.to_le_bytes().as_ref(),
+            floor.floor_id.to_le_bytes().as_ref(),
+            floor.instance.to_le_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub game_data: AccountLoader<'info, FloorData>,
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+}
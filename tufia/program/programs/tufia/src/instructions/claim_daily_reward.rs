@@ -0,0 +1,69 @@
+pub use crate::errors::GameErrorCode;
+use crate::state::{daily::DailyLeaderboard, game_config::GameConfig};
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{self, Mint, MintTo, Token, TokenAccount},
+};
+
+pub fn claim_daily_reward(ctx: Context<ClaimDailyReward>, day: u32) -> Result<()> {
+    let (rank, gold) = ctx
+        .accounts
+        .daily_leaderboard
+        .claim_reward(ctx.accounts.signer.key())?;
+
+    let signer_seeds: &[&[&[u8]]] = &[&[b"config".as_ref(), &[ctx.bumps.config]]];
+    token::mint_to(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            MintTo {
+                mint: ctx.accounts.gold_mint.to_account_info(),
+                to: ctx.accounts.player_gold.to_account_info(),
+                authority: ctx.accounts.config.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        gold,
+    )?;
+
+    msg!("Daily {} rank {} reward {} gold", day, rank + 1, gold);
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(day: u32)]
+pub struct ClaimDailyReward<'info> {
+    #[account(
+        mut,
+        seeds = [b"daily_board".as_ref(), day.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub daily_leaderboard: Account<'info, DailyLeaderboard>,
+
+    #[account(
+        seeds = [b"config".as_ref()],
+        bump,
+    )]
+    pub config: Account<'info, GameConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"gold".as_ref()],
+        bump,
+    )]
+    pub gold_mint: Account<'info, Mint>,
+
+    #[account(
+        init_if_needed,
+        payer = signer,
+        associated_token::mint = gold_mint,
+        associated_token::authority = signer,
+    )]
+    pub player_gold: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,74 @@
+pub use crate::errors::GameErrorCode;
+use crate::state::floor_data::FloorData;
+use crate::state::{
+    game_config::GameConfig,
+    party::{self, Party},
+    player_data::PlayerData,
+};
+use anchor_lang::prelude::*;
+use session_keys::{Session, SessionToken};
+
+pub fn claim_party_share(mut ctx: Context<ClaimPartyShare>, counter: u16) -> Result<()> {
+    let account: &mut &mut ClaimPartyShare<'_> = &mut ctx.accounts;
+    account.player.last_id = counter;
+    party::check_member(&account.party, &account.player)?;
+
+    let levels = account.party.claim_share(&mut account.player)?;
+    if let Some(game_data) = &account.game_data {
+        let floor_data = &mut game_data.load_mut()?;
+        let game_data = &mut floor_data.board();
+        game_data.apply_level_up(&mut account.player, levels);
+        floor_data.save(game_data)?;
+    }
+    Ok(())
+}
+
+#[derive(Accounts, Session)]
+pub struct ClaimPartyShare<'info> {
+    #[session(
+        // The ephemeral key pair signing the transaction
+        signer = signer,
+        // The authority of the user account which must have created the session
+        authority = player.authority.key()
+    )]
+    // Session Tokens are passed as optional accounts
+    pub session_token: Option<Account<'info, SessionToken>>,
+
+    // There is one PlayerData account
+    #[account(
+        mut,
+        seeds = [b"player1".as_ref(), player.authority.key().as_ref()],
+        bump,
+    )]
+    pub player: Account<'info, PlayerData>,
+
+    #[account(
+        mut,
+        seeds = [b"party".as_ref(), party.founder.as_ref()],
+        bump,
+    )]
+    pub party: Account<'info, Party>,
+
+    #[account(
+        seeds = [b"config".as_ref()],
+        bump,
+    )]
+    pub config: Account<'info, GameConfig>,
+
+    // The floor the player is on, level ups from the share grow the board
+    // tile. Left out in the lobby.
+    #[account(
+        mut,
+        seeds = [
+            b"floor".as_ref(),
+            config.season.to_le_bytes().as_ref(),
+            (player.current_floor as u32).to_le_bytes().as_ref(),
+            player.current_instance.to_le_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub game_data: Option<AccountLoader<'info, FloorData>>,
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+}
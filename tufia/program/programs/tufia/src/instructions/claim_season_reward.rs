@@ -0,0 +1,82 @@
+pub use crate::errors::GameErrorCode;
+use crate::state::{
+    game_config::GameConfig,
+    player_data::PlayerData,
+    season::{SeasonArchive, SeasonBadge},
+};
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{self, Mint, MintTo, Token, TokenAccount},
+};
+
+pub fn claim_season_reward(ctx: Context<ClaimSeasonReward>, season: u16) -> Result<()> {
+    let (rank, gold) = ctx
+        .accounts
+        .archive
+        .claim_reward(ctx.accounts.player.authority)?;
+
+    ctx.accounts.player.add_badge(SeasonBadge { season, rank });
+
+    let signer_seeds: &[&[&[u8]]] = &[&[b"config".as_ref(), &[ctx.bumps.config]]];
+    token::mint_to(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            MintTo {
+                mint: ctx.accounts.gold_mint.to_account_info(),
+                to: ctx.accounts.player_gold.to_account_info(),
+                authority: ctx.accounts.config.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        gold,
+    )?;
+
+    msg!("Season {} rank {} reward {} gold", season, rank + 1, gold);
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(season: u16)]
+pub struct ClaimSeasonReward<'info> {
+    #[account(
+        mut,
+        seeds = [b"player1".as_ref(), signer.key().as_ref()],
+        bump,
+    )]
+    pub player: Account<'info, PlayerData>,
+
+    #[account(
+        mut,
+        seeds = [b"season".as_ref(), season.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub archive: Account<'info, SeasonArchive>,
+
+    #[account(
+        seeds = [b"config".as_ref()],
+        bump,
+    )]
+    pub config: Account<'info, GameConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"gold".as_ref()],
+        bump,
+    )]
+    pub gold_mint: Account<'info, Mint>,
+
+    #[account(
+        init_if_needed,
+        payer = signer,
+        associated_token::mint = gold_mint,
+        associated_token::authority = signer,
+    )]
+    pub player_gold: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
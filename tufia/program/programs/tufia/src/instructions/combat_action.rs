@@ -0,0 +1,116 @@
+use crate::constants::*;
+pub use crate::errors::GameErrorCode;
+use crate::state::floor_data::FloorData;
+pub use crate::state::game_data::GameData;
+use crate::state::{
+    floor::FloorAddress,
+    game_config::GameConfig,
+    leaderboard::{self, Leaderboard},
+    party::{self, LootSnapshot, Party},
+    player_data::PlayerData,
+};
+use anchor_lang::prelude::*;
+use session_keys::{Session, SessionToken};
+
+pub fn combat_action(
+    mut ctx: Context<CombatAction>,
+    floor: FloorAddress,
+    counter: u16,
+    action: u8,
+) -> Result<()> {
+    let account: &mut &mut CombatAction<'_> = &mut ctx.accounts;
+    account.player.update_energy()?;
+
+    // Every round costs energy, a fight can not be dragged out for free
+    account.player.energy = account
+        .player
+        .energy
+        .checked_sub(ENERGY_PER_COMBAT_ROUND)
+        .ok_or(GameErrorCode::NotEnoughEnergy)?;
+
+    account.player.last_id = counter;
+    account.player.sync_season(account.config.season);
+    floor.check_player_instance(&account.player)?;
+    party::check_party(&account.party, &account.player)?;
+    let floor_data = &mut account.game_data.load_mut()?;
+    let game_data = &mut floor_data.board();
+    game_data.update_buffs(&mut account.player)?;
+
+    let loot = LootSnapshot::of(&account.player);
+
+    let deaths = account.player.stats.deaths;
+    let floor_key = account.game_data.key();
+    game_data.combat_action(&mut account.player, floor_key, action)?;
+    if account.player.stats.deaths != deaths {
+        leaderboard::submit_if_present(&mut account.leaderboard, &account.player)?;
+    }
+
+    if let Some(party) = &mut account.party {
+        party.share(&mut account.player, loot);
+    }
+
+    floor_data.save(game_data)?;
+    Ok(())
+}
+
+#[derive(Accounts, Session)]
+#[instruction(floor: FloorAddress)]
+pub struct CombatAction<'info> {
+    #[session(
+        // The ephemeral key pair signing the transaction
+        signer = signer,
+        // The authority of the user account which must have created the session
+        authority = player.authority.key()
+    )]
+    // Session Tokens are passed as optional accounts
+    pub session_token: Option<Account<'info, SessionToken>>,
+
+    // There is one PlayerData account
+    #[account(
+        mut,
+        seeds = [b"player1".as_ref(), player.authority.key().as_ref()],
+        bump,
+    )]
+    pub player: Account<'info, PlayerData>,
+
+    #[account(
+        seeds = [b"config".as_ref()],
+        bump,
+    )]
+    pub config: Account<'info, GameConfig>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"floor".as_ref(),
+            config.season.to_le_bytes().as_ref(),
+            floor.floor_id.to_le_bytes().as_ref(),
+            floor.instance.to_le_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub game_data: AccountLoader<'info, FloorData>,
+
+    // Optional, updated when the player dies
+    #[account(
+        mut,
+        seeds = [
+            b"leaderboard".as_ref(),
+            leaderboard.season.to_le_bytes().as_ref(),
+            &[leaderboard.metric],
+        ],
+        bump,
+    )]
+    pub leaderboard: Option<Account<'info, Leaderboard>>,
+
+    // Required for players in a party, shares xp with the party
+    #[account(
+        mut,
+        seeds = [b"party".as_ref(), party.founder.as_ref()],
+        bump,
+    )]
+    pub party: Option<Account<'info, Party>>,
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+}
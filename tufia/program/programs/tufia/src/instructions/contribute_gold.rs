@@ -0,0 +1,64 @@
+pub use crate::errors::GameErrorCode;
+use crate::state::{guild::Guild, name_registry, player_data::PlayerData};
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+
+pub fn contribute_gold(ctx: Context<ContributeGold>, amount: u64) -> Result<()> {
+    token::transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.player_gold.to_account_info(),
+                to: ctx.accounts.treasury.to_account_info(),
+                authority: ctx.accounts.signer.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+
+    let guild = &mut ctx.accounts.guild;
+    guild.contribute(ctx.accounts.signer.key(), amount)?;
+
+    msg!("Contributed {} gold to {}", amount, guild.name);
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ContributeGold<'info> {
+    // Guild instructions are signed by the wallet itself
+    #[account(
+        mut,
+        seeds = [b"player1".as_ref(), signer.key().as_ref()],
+        bump,
+    )]
+    pub player: Account<'info, PlayerData>,
+
+    #[account(
+        mut,
+        seeds = [b"guild".as_ref(), name_registry::registry_seed(&guild.name).as_bytes()],
+        bump,
+    )]
+    pub guild: Account<'info, Guild>,
+
+    #[account(
+        seeds = [b"gold".as_ref()],
+        bump,
+    )]
+    pub gold_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = gold_mint,
+        associated_token::authority = signer,
+    )]
+    pub player_gold: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        address = guild.treasury,
+    )]
+    pub treasury: Account<'info, TokenAccount>,
+
+    pub signer: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
@@ -0,0 +1,48 @@
+pub use crate::errors::GameErrorCode;
+use crate::state::{game_config::GameConfig, player_data::PlayerData};
+use anchor_lang::prelude::*;
+use session_keys::{Session, SessionToken};
+
+pub fn craft_item(mut ctx: Context<CraftItem>, counter: u16, recipe: u8) -> Result<()> {
+    let account: &mut &mut CraftItem<'_> = &mut ctx.accounts;
+    account.player.last_id = counter;
+
+    let recipe = account
+        .config
+        .recipes
+        .get(recipe as usize)
+        .ok_or(GameErrorCode::InvalidRecipe)?;
+    account.player.craft(recipe)?;
+
+    msg!("Crafted gear of rarity {}", recipe.result.rarity);
+    Ok(())
+}
+
+#[derive(Accounts, Session)]
+pub struct CraftItem<'info> {
+    #[session(
+        // The ephemeral key pair signing the transaction
+        signer = signer,
+        // The authority of the user account which must have created the session
+        authority = player.authority.key()
+    )]
+    // Session Tokens are passed as optional accounts
+    pub session_token: Option<Account<'info, SessionToken>>,
+
+    // There is one PlayerData account
+    #[account(
+        mut,
+        seeds = [b"player1".as_ref(), player.authority.key().as_ref()],
+        bump,
+    )]
+    pub player: Account<'info, PlayerData>,
+
+    #[account(
+        seeds = [b"config".as_ref()],
+        bump,
+    )]
+    pub config: Account<'info, GameConfig>,
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+}
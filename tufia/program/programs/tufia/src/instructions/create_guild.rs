@@ -0,0 +1,75 @@
+pub use crate::errors::GameErrorCode;
+use crate::state::{
+    guild::{Guild, GuildMember, GUILD_ROLE_LEADER},
+    name_registry,
+    player_data::PlayerData,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{Mint, Token, TokenAccount},
+};
+
+pub fn create_guild(ctx: Context<CreateGuild>, name: String) -> Result<()> {
+    name_registry::validate_name(&name)?;
+
+    if ctx.accounts.player.guild != Pubkey::default() {
+        return err!(GameErrorCode::AlreadyInGuild);
+    }
+
+    let guild = &mut ctx.accounts.guild;
+    guild.name = name;
+    guild.leader = ctx.accounts.signer.key();
+    guild.treasury = ctx.accounts.treasury.key();
+    guild.members.push(GuildMember {
+        player: ctx.accounts.signer.key(),
+        role: GUILD_ROLE_LEADER,
+        ..Default::default()
+    });
+    ctx.accounts.player.guild = guild.key();
+
+    msg!("Guild {} created", guild.name);
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(name: String)]
+pub struct CreateGuild<'info> {
+    // Guild instructions are signed by the wallet itself
+    #[account(
+        mut,
+        seeds = [b"player1".as_ref(), signer.key().as_ref()],
+        bump,
+    )]
+    pub player: Account<'info, PlayerData>,
+
+    #[account(
+        init,
+        payer = signer,
+        space = 1500, // 8+4+12+32+4+20*(32+1+2+2+4+8)+4+5*32+32+8
+        seeds = [b"guild".as_ref(), name_registry::registry_seed(&name).as_bytes()],
+        bump,
+    )]
+    pub guild: Account<'info, Guild>,
+
+    #[account(
+        seeds = [b"gold".as_ref()],
+        bump,
+    )]
+    pub gold_mint: Account<'info, Mint>,
+
+    // The shared treasury, owned by the guild PDA
+    #[account(
+        init,
+        payer = signer,
+        associated_token::mint = gold_mint,
+        associated_token::authority = guild,
+    )]
+    pub treasury: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
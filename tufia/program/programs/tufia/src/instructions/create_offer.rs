@@ -0,0 +1,94 @@
+pub use crate::errors::GameErrorCode;
+use crate::state::{
+    player_data::PlayerData,
+    trade::{Offer, OfferArgs},
+};
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{self, Mint, Token, TokenAccount, Transfer},
+};
+
+pub fn create_offer(ctx: Context<CreateOffer>, id: u32, args: OfferArgs) -> Result<()> {
+    if args.expires_at <= Clock::get()?.unix_timestamp {
+        return err!(GameErrorCode::OfferExpired);
+    }
+    args.requested.validate()?;
+
+    let offered = ctx.accounts.player.escrow_items(&args)?;
+    offered.validate()?;
+
+    if args.gold > 0 {
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.maker_gold.to_account_info(),
+                    to: ctx.accounts.escrow_gold.to_account_info(),
+                    authority: ctx.accounts.signer.to_account_info(),
+                },
+            ),
+            args.gold,
+        )?;
+    }
+
+    let offer = &mut ctx.accounts.offer;
+    offer.maker = ctx.accounts.signer.key();
+    offer.id = id;
+    offer.taker = args.taker;
+    offer.offered = offered;
+    offer.requested = args.requested;
+    offer.expires_at = args.expires_at;
+
+    msg!("Offer {} created", id);
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(id: u32)]
+pub struct CreateOffer<'info> {
+    // Trades move gold, so they are signed by the wallet itself
+    #[account(
+        mut,
+        seeds = [b"player1".as_ref(), signer.key().as_ref()],
+        bump,
+    )]
+    pub player: Account<'info, PlayerData>,
+
+    #[account(
+        init,
+        payer = signer,
+        space = 500, // 8+32+4+32+2*(4+4*6+4+4*16+8)+8
+        seeds = [b"offer".as_ref(), signer.key().as_ref(), id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub offer: Account<'info, Offer>,
+
+    #[account(
+        seeds = [b"gold".as_ref()],
+        bump,
+    )]
+    pub gold_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = gold_mint,
+        associated_token::authority = signer,
+    )]
+    pub maker_gold: Account<'info, TokenAccount>,
+
+    // Holds the offered gold, closed when the offer ends
+    #[account(
+        init,
+        payer = signer,
+        associated_token::mint = gold_mint,
+        associated_token::authority = offer,
+    )]
+    pub escrow_gold: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
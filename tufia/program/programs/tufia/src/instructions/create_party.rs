@@ -0,0 +1,70 @@
+pub use crate::errors::GameErrorCode;
+use crate::state::{
+    party::{self, Party, PartyMember},
+    player_data::PlayerData,
+};
+use anchor_lang::prelude::*;
+use session_keys::{Session, SessionToken};
+
+pub fn create_party(
+    mut ctx: Context<CreateParty>,
+    counter: u16,
+    xp_rule: u8,
+    loot_rule: u8,
+) -> Result<()> {
+    let account: &mut &mut CreateParty<'_> = &mut ctx.accounts;
+    account.player.last_id = counter;
+    party::validate_rules(xp_rule, loot_rule)?;
+
+    if account.player.party != Pubkey::default() {
+        return err!(GameErrorCode::AlreadyInParty);
+    }
+
+    let party = &mut account.party;
+    party.founder = account.player.authority;
+    party.leader = account.player.authority;
+    party.xp_rule = xp_rule;
+    party.loot_rule = loot_rule;
+    party.members.push(PartyMember {
+        player: account.player.authority,
+        ..Default::default()
+    });
+    account.player.party = party.key();
+
+    msg!("Party created {}", party.key());
+    Ok(())
+}
+
+#[derive(Accounts, Session)]
+pub struct CreateParty<'info> {
+    #[session(
+        // The ephemeral key pair signing the transaction
+        signer = signer,
+        // The authority of the user account which must have created the session
+        authority = player.authority.key()
+    )]
+    // Session Tokens are passed as optional accounts
+    pub session_token: Option<Account<'info, SessionToken>>,
+
+    // There is one PlayerData account
+    #[account(
+        mut,
+        seeds = [b"player1".as_ref(), player.authority.key().as_ref()],
+        bump,
+    )]
+    pub player: Account<'info, PlayerData>,
+
+    // The founder pays for the party. It is closed when the last member leaves.
+    #[account(
+        init,
+        payer = signer,
+        space = 500, // 8+32+32+4+4*(32+12)+4+4*32+1+1+1+2+4+2
+        seeds = [b"party".as_ref(), player.authority.key().as_ref()],
+        bump,
+    )]
+    pub party: Account<'info, Party>,
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
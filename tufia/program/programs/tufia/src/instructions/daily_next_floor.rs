@@ -0,0 +1,90 @@
+pub use crate::errors::GameErrorCode;
+use crate::state::floor_data::FloorData;
+pub use crate::state::game_data::GameData;
+use crate::state::{
+    daily::{self, DailyLeaderboard, DailyRun},
+    player_data::PlayerData,
+};
+use anchor_lang::prelude::*;
+use session_keys::{Session, SessionToken};
+
+pub fn daily_next_floor(mut ctx: Context<DailyNextFloor>, counter: u16, day: u32) -> Result<()> {
+    let account: &mut &mut DailyNextFloor<'_> = &mut ctx.accounts;
+    account.player.last_id = counter;
+    account.daily_run.check_active()?;
+
+    if account.player.encounter.active {
+        return err!(GameErrorCode::InCombat);
+    }
+
+    let run = &mut account.daily_run;
+    run.depth += 1;
+
+    let floor_data = &mut account.game_data.load_mut()?;
+    let game_data = &mut floor_data.board();
+    game_data.next_daily_floor(
+        &account.player,
+        run.depth as u32,
+        daily::daily_seed(day, run.depth),
+    )?;
+
+    // Every new depth counts, even if the run is never finished
+    account.daily_leaderboard.submit(run)?;
+
+    msg!("Daily depth {}", run.depth);
+    floor_data.save(game_data)?;
+    Ok(())
+}
+
+#[derive(Accounts, Session)]
+#[instruction(counter: u16, day: u32)]
+pub struct DailyNextFloor<'info> {
+    #[session(
+        // The ephemeral key pair signing the transaction
+        signer = signer,
+        // The authority of the user account which must have created the session
+        authority = player.authority.key()
+    )]
+    // Session Tokens are passed as optional accounts
+    pub session_token: Option<Account<'info, SessionToken>>,
+
+    // There is one PlayerData account
+    #[account(
+        mut,
+        seeds = [b"player1".as_ref(), player.authority.key().as_ref()],
+        bump,
+    )]
+    pub player: Account<'info, PlayerData>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"daily_run".as_ref(),
+            day.to_le_bytes().as_ref(),
+            player.authority.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub daily_run: Account<'info, DailyRun>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"daily".as_ref(),
+            day.to_le_bytes().as_ref(),
+            player.authority.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub game_data: AccountLoader<'info, FloorData>,
+
+    #[account(
+        mut,
+        seeds = [b"daily_board".as_ref(), day.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub daily_leaderboard: Account<'info, DailyLeaderboard>,
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+}
@@ -0,0 +1,93 @@
+pub use crate::errors::GameErrorCode;
+use crate::state::{item::ItemRecord, player_data::PlayerData};
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    metadata::{burn_nft, BurnNft, Metadata},
+    token::{Mint, Token, TokenAccount},
+};
+
+// Burns an item NFT and puts the gear back into the inventory. Anyone holding
+// the NFT can deposit it, no matter who found the item.
+pub fn deposit_item(ctx: Context<DepositItem>) -> Result<()> {
+    let gear = ctx.accounts.item_record.item;
+    ctx.accounts.player.add_gear(gear)?;
+
+    burn_nft(
+        CpiContext::new(
+            ctx.accounts.token_metadata_program.to_account_info(),
+            BurnNft {
+                metadata: ctx.accounts.metadata.to_account_info(),
+                owner: ctx.accounts.signer.to_account_info(),
+                mint: ctx.accounts.item_mint.to_account_info(),
+                token: ctx.accounts.player_item.to_account_info(),
+                edition: ctx.accounts.master_edition.to_account_info(),
+                spl_token: ctx.accounts.token_program.to_account_info(),
+            },
+        ),
+        None,
+    )?;
+
+    msg!("Deposited item {}", ctx.accounts.item_mint.key());
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct DepositItem<'info> {
+    #[account(
+        mut,
+        seeds = [b"player1".as_ref(), signer.key().as_ref()],
+        bump,
+    )]
+    pub player: Account<'info, PlayerData>,
+
+    #[account(mut)]
+    pub item_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = item_mint,
+        associated_token::authority = signer,
+    )]
+    pub player_item: Account<'info, TokenAccount>,
+
+    // Closed with the NFT, the gear lives in the inventory again
+    #[account(
+        mut,
+        close = signer,
+        seeds = [b"item".as_ref(), item_mint.key().as_ref()],
+        bump,
+    )]
+    pub item_record: Account<'info, ItemRecord>,
+
+    /// CHECK: Closed by the token metadata program
+    #[account(
+        mut,
+        seeds = [
+            b"metadata".as_ref(),
+            token_metadata_program.key().as_ref(),
+            item_mint.key().as_ref(),
+        ],
+        seeds::program = token_metadata_program.key(),
+        bump,
+    )]
+    pub metadata: UncheckedAccount<'info>,
+
+    /// CHECK: Closed by the token metadata program
+    #[account(
+        mut,
+        seeds = [
+            b"metadata".as_ref(),
+            token_metadata_program.key().as_ref(),
+            item_mint.key().as_ref(),
+            b"edition".as_ref(),
+        ],
+        seeds::program = token_metadata_program.key(),
+        bump,
+    )]
+    pub master_edition: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    pub token_metadata_program: Program<'info, Metadata>,
+}
@@ -0,0 +1,46 @@
+pub use crate::errors::GameErrorCode;
+use crate::state::{game_config::GameConfig, leaderboard::Leaderboard, season::SeasonArchive};
+use anchor_lang::prelude::*;
+
+pub fn end_season(ctx: Context<EndSeason>) -> Result<()> {
+    ctx.accounts.archive.archive(&ctx.accounts.leaderboard)?;
+
+    ctx.accounts.config.season += 1;
+    msg!("Season {} started", ctx.accounts.config.season);
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct EndSeason<'info> {
+    #[account(
+        mut,
+        seeds = [b"config".as_ref()],
+        bump,
+        has_one = admin @ GameErrorCode::WrongAdmin,
+    )]
+    pub config: Account<'info, GameConfig>,
+
+    // The leaderboard that decides the rewards of the season
+    #[account(
+        seeds = [
+            b"leaderboard".as_ref(),
+            config.season.to_le_bytes().as_ref(),
+            &[leaderboard.metric],
+        ],
+        bump,
+    )]
+    pub leaderboard: Account<'info, Leaderboard>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 1500, // 8+2+1+8+4+20*(32+14+8+1)
+        seeds = [b"season".as_ref(), config.season.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub archive: Account<'info, SeasonArchive>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
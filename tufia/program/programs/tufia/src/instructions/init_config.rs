@@ -0,0 +1,39 @@
+pub use crate::errors::GameErrorCode;
+use crate::{constants::*, program::Tufia, state::game_config::GameConfig};
+use anchor_lang::prelude::*;
+
+pub fn init_config(ctx: Context<InitConfig>, boss_floor_interval: u16) -> Result<()> {
+    ctx.accounts.config.admin = ctx.accounts.signer.key();
+    ctx.accounts.config.boss_floor_interval = boss_floor_interval;
+    ctx.accounts.config.armor_regen_per_move = ARMOR_REGEN_PER_MOVE;
+    ctx.accounts.config.health_regen_seconds = HEALTH_REGEN_SECONDS;
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct InitConfig<'info> {
+    #[account(
+        init,
+        payer = signer,
+        space = 1000, // 8+32+2 But taking 1000 to have space to expand easily.
+        seeds = [b"config".as_ref()],
+        bump,
+    )]
+    pub config: Account<'info, GameConfig>,
+
+    // Only the upgrade authority of the program can create the config and
+    // become its admin
+    #[account(
+        constraint = program.programdata_address()? == Some(program_data.key()) @ GameErrorCode::WrongAdmin,
+    )]
+    pub program: Program<'info, Tufia>,
+
+    #[account(
+        constraint = program_data.upgrade_authority_address == Some(signer.key()) @ GameErrorCode::WrongAdmin,
+    )]
+    pub program_data: Account<'info, ProgramData>,
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,36 @@
+pub use crate::errors::GameErrorCode;
+use crate::state::game_config::GameConfig;
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token};
+
+// Gold is an SPL token. The config PDA is the mint authority, so only the
+// program can mint it.
+pub fn init_gold_mint(ctx: Context<InitGoldMint>) -> Result<()> {
+    msg!("Gold mint {}", ctx.accounts.gold_mint.key());
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct InitGoldMint<'info> {
+    #[account(
+        seeds = [b"config".as_ref()],
+        bump,
+        has_one = admin @ GameErrorCode::WrongAdmin,
+    )]
+    pub config: Account<'info, GameConfig>,
+
+    #[account(
+        init,
+        payer = admin,
+        seeds = [b"gold".as_ref()],
+        bump,
+        mint::decimals = 0,
+        mint::authority = config,
+    )]
+    pub gold_mint: Account<'info, Mint>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,46 @@
+pub use crate::errors::GameErrorCode;
+use crate::state::{
+    game_config::GameConfig,
+    leaderboard::{self, Leaderboard},
+};
+use anchor_lang::prelude::*;
+
+pub fn init_leaderboard(ctx: Context<InitLeaderboard>, metric: u8) -> Result<()> {
+    leaderboard::validate_metric(metric)?;
+    ctx.accounts.leaderboard.season = ctx.accounts.config.season;
+    ctx.accounts.leaderboard.metric = metric;
+    msg!(
+        "Leaderboard for metric {} in season {}",
+        metric,
+        ctx.accounts.config.season
+    );
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(metric: u8)]
+pub struct InitLeaderboard<'info> {
+    #[account(
+        seeds = [b"config".as_ref()],
+        bump,
+        has_one = admin @ GameErrorCode::WrongAdmin,
+    )]
+    pub config: Account<'info, GameConfig>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 1500, // 8+2+1+4+20*(32+14+8)
+        seeds = [
+            b"leaderboard".as_ref(),
+            config.season.to_le_bytes().as_ref(),
+            &[metric],
+        ],
+        bump,
+    )]
+    pub leaderboard: Account<'info, Leaderboard>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
@@ -1,22 +1,35 @@
+use crate::constants::*;
 pub use crate::errors::GameErrorCode;
-use crate::state::player_data::PlayerData;
-use crate::{constants::MAX_ENERGY, GameData};
+use crate::state::{game_config::GameConfig, player_class, player_data::PlayerData};
 use anchor_lang::prelude::*;
 
-pub fn init_player(ctx: Context<InitPlayer>) -> Result<()> {
+pub fn init_player(ctx: Context<InitPlayer>, class: u8) -> Result<()> {
+    player_class::validate_class(class)?;
+    let stats = player_class::base_stats(class);
+
     ctx.accounts.player.energy = MAX_ENERGY;
-    ctx.accounts.player.health = 10;
-    ctx.accounts.player.max_health = 10;
+    ctx.accounts.player.class = class;
+    ctx.accounts.player.season = ctx.accounts.config.season;
+    ctx.accounts.player.max_health = stats.max_health;
     ctx.accounts.player.level = 1;
-    ctx.accounts.player.damage = 1;
+    ctx.accounts.player.damage = stats.damage;
+    ctx.accounts.player.defence = stats.defence;
+    ctx.accounts.player.max_armor = stats.armor;
+    ctx.accounts.player.crit_chance = stats.crit_chance;
+    ctx.accounts.player.crit_multiplier = stats.crit_multiplier;
+    ctx.accounts.player.evasion = stats.evasion;
+    ctx.accounts.player.damage_range = stats.damage_range;
 
     ctx.accounts.player.last_login = Clock::get()?.unix_timestamp;
+    ctx.accounts.player.last_heal = ctx.accounts.player.last_login;
     ctx.accounts.player.authority = ctx.accounts.signer.key();
+    // New players start in the lobby and join the first floor with
+    // buy_next_floor
+    ctx.accounts.player.reset_tile();
     Ok(())
 }
 
 #[derive(Accounts)]
-#[instruction(level_seed: String)]
 pub struct InitPlayer<'info> {
     #[account(
         init,
@@ -28,13 +41,10 @@ pub struct InitPlayer<'info> {
     pub player: Account<'info, PlayerData>,
 
     #[account(
-        init_if_needed,
-        payer = signer,
-        space = 10240, // 8 + all the tiles a game config
-        seeds = [level_seed.as_ref()],
+        seeds = [b"config".as_ref()],
         bump,
     )]
-    pub game_data: AccountLoader<'info, GameData>,
+    pub config: Account<'info, GameConfig>,
 
     #[account(mut)]
     pub signer: Signer<'info>,
@@ -0,0 +1,51 @@
+pub use crate::errors::GameErrorCode;
+use crate::state::{
+    party::{self, Party},
+    player_data::PlayerData,
+};
+use anchor_lang::prelude::*;
+use session_keys::{Session, SessionToken};
+
+pub fn invite(mut ctx: Context<Invite>, counter: u16, invitee: Pubkey) -> Result<()> {
+    let account: &mut &mut Invite<'_> = &mut ctx.accounts;
+    account.player.last_id = counter;
+    party::check_member(&account.party, &account.player)?;
+
+    if account.party.leader != account.player.authority {
+        return err!(GameErrorCode::NotPartyLeader);
+    }
+
+    account.party.invite(invitee)?;
+    msg!("Invited {}", invitee);
+    Ok(())
+}
+
+#[derive(Accounts, Session)]
+pub struct Invite<'info> {
+    #[session(
+        // The ephemeral key pair signing the transaction
+        signer = signer,
+        // The authority of the user account which must have created the session
+        authority = player.authority.key()
+    )]
+    // Session Tokens are passed as optional accounts
+    pub session_token: Option<Account<'info, SessionToken>>,
+
+    // There is one PlayerData account
+    #[account(
+        mut,
+        seeds = [b"player1".as_ref(), player.authority.key().as_ref()],
+        bump,
+    )]
+    pub player: Account<'info, PlayerData>,
+
+    #[account(
+        mut,
+        seeds = [b"party".as_ref(), party.founder.as_ref()],
+        bump,
+    )]
+    pub party: Account<'info, Party>,
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+}
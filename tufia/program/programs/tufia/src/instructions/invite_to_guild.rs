@@ -0,0 +1,31 @@
+pub use crate::errors::GameErrorCode;
+use crate::state::{guild::Guild, name_registry, player_data::PlayerData};
+use anchor_lang::prelude::*;
+
+pub fn invite_to_guild(ctx: Context<InviteToGuild>, invitee: Pubkey) -> Result<()> {
+    ctx.accounts
+        .guild
+        .invite(ctx.accounts.signer.key(), invitee)?;
+    msg!("Invited {} to {}", invitee, ctx.accounts.guild.name);
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct InviteToGuild<'info> {
+    // Guild instructions are signed by the wallet itself
+    #[account(
+        mut,
+        seeds = [b"player1".as_ref(), signer.key().as_ref()],
+        bump,
+    )]
+    pub player: Account<'info, PlayerData>,
+
+    #[account(
+        mut,
+        seeds = [b"guild".as_ref(), name_registry::registry_seed(&guild.name).as_bytes()],
+        bump,
+    )]
+    pub guild: Account<'info, Guild>,
+
+    pub signer: Signer<'info>,
+}
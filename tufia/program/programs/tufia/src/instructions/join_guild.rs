@@ -0,0 +1,35 @@
+pub use crate::errors::GameErrorCode;
+use crate::state::{guild::Guild, name_registry, player_data::PlayerData};
+use anchor_lang::prelude::*;
+
+pub fn join_guild(ctx: Context<JoinGuild>) -> Result<()> {
+    if ctx.accounts.player.guild != Pubkey::default() {
+        return err!(GameErrorCode::AlreadyInGuild);
+    }
+
+    ctx.accounts.guild.join(ctx.accounts.signer.key())?;
+    ctx.accounts.player.guild = ctx.accounts.guild.key();
+
+    msg!("Joined guild {}", ctx.accounts.guild.name);
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct JoinGuild<'info> {
+    // Guild instructions are signed by the wallet itself
+    #[account(
+        mut,
+        seeds = [b"player1".as_ref(), signer.key().as_ref()],
+        bump,
+    )]
+    pub player: Account<'info, PlayerData>,
+
+    #[account(
+        mut,
+        seeds = [b"guild".as_ref(), name_registry::registry_seed(&guild.name).as_bytes()],
+        bump,
+    )]
+    pub guild: Account<'info, Guild>,
+
+    pub signer: Signer<'info>,
+}
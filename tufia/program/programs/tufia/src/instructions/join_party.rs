@@ -0,0 +1,49 @@
+pub use crate::errors::GameErrorCode;
+use crate::state::{party::Party, player_data::PlayerData};
+use anchor_lang::prelude::*;
+use session_keys::{Session, SessionToken};
+
+pub fn join_party(mut ctx: Context<JoinParty>, counter: u16) -> Result<()> {
+    let account: &mut &mut JoinParty<'_> = &mut ctx.accounts;
+    account.player.last_id = counter;
+
+    if account.player.party != Pubkey::default() {
+        return err!(GameErrorCode::AlreadyInParty);
+    }
+
+    account.party.join(account.player.authority)?;
+    account.player.party = account.party.key();
+
+    msg!("Joined party {}", account.party.key());
+    Ok(())
+}
+
+#[derive(Accounts, Session)]
+pub struct JoinParty<'info> {
+    #[session(
+        // The ephemeral key pair signing the transaction
+        signer = signer,
+        // The authority of the user account which must have created the session
+        authority = player.authority.key()
+    )]
+    // Session Tokens are passed as optional accounts
+    pub session_token: Option<Account<'info, SessionToken>>,
+
+    // There is one PlayerData account
+    #[account(
+        mut,
+        seeds = [b"player1".as_ref(), player.authority.key().as_ref()],
+        bump,
+    )]
+    pub player: Account<'info, PlayerData>,
+
+    #[account(
+        mut,
+        seeds = [b"party".as_ref(), party.founder.as_ref()],
+        bump,
+    )]
+    pub party: Account<'info, Party>,
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+}
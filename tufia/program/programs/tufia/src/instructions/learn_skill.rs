@@ -0,0 +1,34 @@
+pub use crate::errors::GameErrorCode;
+use crate::state::player_data::PlayerData;
+use anchor_lang::prelude::*;
+use session_keys::{Session, SessionToken};
+
+pub fn learn_skill(mut ctx: Context<LearnSkill>, counter: u16, skill: u8) -> Result<()> {
+    let account: &mut &mut LearnSkill<'_> = &mut ctx.accounts;
+    account.player.last_id = counter;
+
+    account.player.learn_skill(skill)
+}
+
+#[derive(Accounts, Session)]
+pub struct LearnSkill<'info> {
+    #[session(
+        // The ephemeral key pair signing the transaction
+        signer = signer,
+        // The authority of the user account which must have created the session
+        authority = player.authority.key()
+    )]
+    // Session Tokens are passed as optional accounts
+    pub session_token: Option<Account<'info, SessionToken>>,
+
+    // There is one PlayerData account
+    #[account(
+        mut,
+        seeds = [b"player1".as_ref(), player.authority.key().as_ref()],
+        bump,
+    )]
+    pub player: Account<'info, PlayerData>,
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+}
@@ -0,0 +1,94 @@
+pub use crate::errors::GameErrorCode;
+use crate::state::{guild::Guild, name_registry, player_data::PlayerData};
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, CloseAccount, Mint, Token, TokenAccount, Transfer};
+
+// The last member to leave closes the guild. They get what is left in the
+// treasury and the name is free again.
+pub fn leave_guild(ctx: Context<LeaveGuild>) -> Result<()> {
+    if ctx.accounts.player.guild != ctx.accounts.guild.key() {
+        return err!(GameErrorCode::NotInGuild);
+    }
+
+    let empty = ctx.accounts.guild.leave(ctx.accounts.signer.key())?;
+    ctx.accounts.player.guild = Pubkey::default();
+    msg!("Left guild {}", ctx.accounts.guild.name);
+
+    if !empty {
+        return Ok(());
+    }
+
+    let seed = name_registry::registry_seed(&ctx.accounts.guild.name);
+    let signer_seeds: &[&[&[u8]]] = &[&[b"guild".as_ref(), seed.as_bytes(), &[ctx.bumps.guild]]];
+    if ctx.accounts.treasury.amount > 0 {
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.treasury.to_account_info(),
+                    to: ctx.accounts.player_gold.to_account_info(),
+                    authority: ctx.accounts.guild.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            ctx.accounts.treasury.amount,
+        )?;
+    }
+    token::close_account(CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        CloseAccount {
+            account: ctx.accounts.treasury.to_account_info(),
+            destination: ctx.accounts.signer.to_account_info(),
+            authority: ctx.accounts.guild.to_account_info(),
+        },
+        signer_seeds,
+    ))?;
+    ctx.accounts
+        .guild
+        .close(ctx.accounts.signer.to_account_info())?;
+
+    msg!("Guild closed");
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct LeaveGuild<'info> {
+    // Guild instructions are signed by the wallet itself
+    #[account(
+        mut,
+        seeds = [b"player1".as_ref(), signer.key().as_ref()],
+        bump,
+    )]
+    pub player: Account<'info, PlayerData>,
+
+    #[account(
+        mut,
+        seeds = [b"guild".as_ref(), name_registry::registry_seed(&guild.name).as_bytes()],
+        bump,
+    )]
+    pub guild: Account<'info, Guild>,
+
+    #[account(
+        seeds = [b"gold".as_ref()],
+        bump,
+    )]
+    pub gold_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        address = guild.treasury,
+    )]
+    pub treasury: Account<'info, TokenAccount>,
+
+    // Gets the rest of the treasury when the guild is closed
+    #[account(
+        mut,
+        associated_token::mint = gold_mint,
+        associated_token::authority = signer,
+    )]
+    pub player_gold: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
@@ -0,0 +1,62 @@
+pub use crate::errors::GameErrorCode;
+use crate::state::{
+    party::{self, Party},
+    player_data::PlayerData,
+};
+use anchor_lang::prelude::*;
+use session_keys::{Session, SessionToken};
+
+pub fn leave_party(mut ctx: Context<LeaveParty>, counter: u16) -> Result<()> {
+    let account: &mut &mut LeaveParty<'_> = &mut ctx.accounts;
+    account.player.last_id = counter;
+    party::check_member(&account.party, &account.player)?;
+
+    // Unclaimed shares are lost when leaving
+    let empty = account.party.leave(account.player.authority)?;
+    account.player.party = Pubkey::default();
+
+    if empty {
+        msg!("Party closed");
+        account.party.close(account.authority.to_account_info())?;
+    }
+
+    Ok(())
+}
+
+#[derive(Accounts, Session)]
+pub struct LeaveParty<'info> {
+    #[session(
+        // The ephemeral key pair signing the transaction
+        signer = signer,
+        // The authority of the user account which must have created the session
+        authority = player.authority.key()
+    )]
+    // Session Tokens are passed as optional accounts
+    pub session_token: Option<Account<'info, SessionToken>>,
+
+    // There is one PlayerData account
+    #[account(
+        mut,
+        seeds = [b"player1".as_ref(), player.authority.key().as_ref()],
+        bump,
+    )]
+    pub player: Account<'info, PlayerData>,
+
+    #[account(
+        mut,
+        seeds = [b"party".as_ref(), party.founder.as_ref()],
+        bump,
+    )]
+    pub party: Account<'info, Party>,
+
+    /// CHECK: Gets the rent of the party back when the last member leaves,
+    /// the signer can be a session key
+    #[account(
+        mut,
+        address = player.authority,
+    )]
+    pub authority: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+}
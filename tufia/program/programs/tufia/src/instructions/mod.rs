@@ -1,12 +1,110 @@
 //! All instructions
+pub mod accept_offer;
+pub mod allocate_points;
+pub mod attack_boss;
+pub mod buy_item;
 pub mod buy_next_floor;
+pub mod cancel_offer;
+pub mod claim_boss_reward;
+pub mod claim_daily_reward;
+pub mod claim_party_share;
+pub mod claim_season_reward;
+pub mod combat_action;
+pub mod contribute_gold;
+pub mod craft_item;
+pub mod create_guild;
+pub mod create_offer;
+pub mod create_party;
+pub mod daily_combat_action;
+pub mod daily_move;
+pub mod daily_next_floor;
+pub mod deposit_item;
+pub mod end_season;
+pub mod equip_item;
+pub mod init_config;
+pub mod init_gold_mint;
+pub mod init_leaderboard;
 pub mod init_player;
+pub mod invite;
+pub mod invite_to_guild;
+pub mod join_guild;
+pub mod join_party;
+pub mod learn_skill;
+pub mod leave_guild;
+pub mod leave_party;
 pub mod move_to_tile;
 pub mod next_floor;
 pub mod reset_floor;
+pub mod rest;
+pub mod sell_item;
+pub mod set_armor_regen;
+pub mod set_guild_role;
+pub mod set_health_regen;
+pub mod set_item_uri;
+pub mod set_profile;
+pub mod set_recipe;
+pub mod set_shop_item;
+pub mod spend_guild_treasury;
+pub mod start_daily_run;
+pub mod sync_floor_instance;
+pub mod update_config;
+pub mod update_guild_ranking;
+pub mod update_leaderboard;
+pub mod upgrade_item;
+pub mod use_item;
+pub mod withdraw_item;
 
+pub use accept_offer::*;
+pub use allocate_points::*;
+pub use attack_boss::*;
+pub use buy_item::*;
 pub use buy_next_floor::*;
+pub use cancel_offer::*;
+pub use claim_boss_reward::*;
+pub use claim_daily_reward::*;
+pub use claim_party_share::*;
+pub use claim_season_reward::*;
+pub use combat_action::*;
+pub use contribute_gold::*;
+pub use craft_item::*;
+pub use create_guild::*;
+pub use create_offer::*;
+pub use create_party::*;
+pub use daily_combat_action::*;
+pub use daily_move::*;
+pub use daily_next_floor::*;
+pub use deposit_item::*;
+pub use end_season::*;
+pub use equip_item::*;
+pub use init_config::*;
+pub use init_gold_mint::*;
+pub use init_leaderboard::*;
 pub use init_player::*;
+pub use invite::*;
+pub use invite_to_guild::*;
+pub use join_guild::*;
+pub use join_party::*;
+pub use learn_skill::*;
+pub use leave_guild::*;
+pub use leave_party::*;
 pub use move_to_tile::*;
 pub use next_floor::*;
 pub use reset_floor::*;
+pub use rest::*;
+pub use sell_item::*;
+pub use set_armor_regen::*;
+pub use set_guild_role::*;
+pub use set_health_regen::*;
+pub use set_item_uri::*;
+pub use set_profile::*;
+pub use set_recipe::*;
+pub use set_shop_item::*;
+pub use spend_guild_treasury::*;
+pub use start_daily_run::*;
+pub use sync_floor_instance::*;
+pub use update_config::*;
+pub use update_guild_ranking::*;
+pub use update_leaderboard::*;
+pub use upgrade_item::*;
+pub use use_item::*;
+pub use withdraw_item::*;
@@ -1,12 +1,23 @@
-use std::borrow::BorrowMut;
-
 pub use crate::errors::GameErrorCode;
+use crate::state::floor_data::FloorData;
 pub use crate::state::game_data::GameData;
-use crate::state::player_data::PlayerData;
+use crate::state::{
+    floor::FloorAddress,
+    game_config::GameConfig,
+    leaderboard::{self, Leaderboard},
+    party::{self, LootSnapshot, Party},
+    player_data::PlayerData,
+};
 use anchor_lang::prelude::*;
 use session_keys::{Session, SessionToken};
 
-pub fn move_to_tile(mut ctx: Context<MoveToTile>, counter: u16, x: u64, y: u64) -> Result<()> {
+pub fn move_to_tile(
+    mut ctx: Context<MoveToTile>,
+    floor: FloorAddress,
+    counter: u16,
+    x: u64,
+    y: u64,
+) -> Result<()> {
     let account: &mut &mut MoveToTile<'_> = &mut ctx.accounts;
     account.player.update_energy()?;
     account.player.print()?;
@@ -18,17 +29,43 @@ pub fn move_to_tile(mut ctx: Context<MoveToTile>, counter: u16, x: u64, y: u64)
     }
 
     account.player.last_id = counter;
-    account.player.move_to_tile(x, y, authority)?;
+    account.player.sync_season(account.config.season);
+    floor.check_player_instance(&account.player)?;
+    account.player.move_to_tile()?;
+
+    let floor_data = &mut account.game_data.load_mut()?;
+    let game_data = &mut floor_data.board();
+    game_data.check_floor(authority, &floor)?;
+    game_data.update_buffs(&mut account.player)?;
+    game_data.update_health(&mut account.player, account.config.health_regen_seconds)?;
 
-    let game_data = &mut account.game_data.load_mut()?;
+    let allies = party::allies(&account.party, &account.player)?;
+    let loot = LootSnapshot::of(&account.player);
 
-    game_data.move_to_tile(x, y, &mut account.player, true)?;
+    let floor_key = account.game_data.key();
+    let deaths = account.player.stats.deaths;
+    game_data.move_to_tile(
+        x,
+        y,
+        &mut account.player,
+        floor_key,
+        &allies,
+        account.config.armor_regen_per_move,
+    )?;
+    if account.player.stats.deaths != deaths {
+        leaderboard::submit_if_present(&mut account.leaderboard, &account.player)?;
+    }
+
+    if let Some(party) = &mut account.party {
+        party.share(&mut account.player, loot);
+    }
 
+    floor_data.save(game_data)?;
     Ok(())
 }
 
 #[derive(Accounts, Session)]
-#[instruction(level_seed: String)]
+#[instruction(floor: FloorAddress)]
 pub struct MoveToTile<'info> {
     #[session(
         // The ephemeral key pair signing the transaction
@@ -47,16 +84,45 @@ pub struct MoveToTile<'info> {
     )]
     pub player: Account<'info, PlayerData>,
 
-    // There can be multiple levels the seed for the level is passed in the instruction
-    // First player starting a new level will pay for the account in the current setup
     #[account(
-        init_if_needed,
-        payer = signer,
-        space = 10240,
-        seeds = [level_seed.as_ref()],
+        seeds = [b"config".as_ref()],
+        bump,
+    )]
+    pub config: Account<'info, GameConfig>,
+
+    // There can be multiple instances of every floor, the address is passed in the instruction.
+    // Floors are created by buy_next_floor.
+    #[account(
+        mut,
+        seeds = [
+            b"floor".as_ref(),
+            config.season.to_le_bytes().as_ref(),
+            floor.floor_id.to_le_bytes().as_ref(),
+            floor.instance.to_le_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub game_data: AccountLoader<'info, FloorData>,
+
+    // Optional, updated when the player dies
+    #[account(
+        mut,
+        seeds = [
+            b"leaderboard".as_ref(),
+            leaderboard.season.to_le_bytes().as_ref(),
+            &[leaderboard.metric],
+        ],
+        bump,
+    )]
+    pub leaderboard: Option<Account<'info, Leaderboard>>,
+
+    // Required for players in a party, see party.rs
+    #[account(
+        mut,
+        seeds = [b"party".as_ref(), party.founder.as_ref()],
         bump,
     )]
-    pub game_data: AccountLoader<'info, GameData>,
+    pub party: Option<Account<'info, Party>>,
 
     #[account(mut)]
     pub signer: Signer<'info>,
@@ -1,25 +1,45 @@
 pub use crate::errors::GameErrorCode;
+use crate::state::floor_data::FloorData;
 pub use crate::state::game_data::GameData;
-use crate::state::player_data::PlayerData;
+use crate::state::{
+    floor::{FloorAddress, FloorDirectory},
+    game_config::GameConfig,
+    leaderboard::{self, Leaderboard},
+    player_data::PlayerData,
+};
 use anchor_lang::prelude::*;
 use session_keys::{Session, SessionToken};
 
-pub fn next_floor(mut ctx: Context<NextFloor>, counter: u16) -> Result<()> {
+pub fn next_floor(mut ctx: Context<NextFloor>, floor: FloorAddress, counter: u16) -> Result<()> {
     let account: &mut &mut NextFloor<'_> = &mut ctx.accounts;
     account.player.last_id = counter;
+    account.player.sync_season(account.config.season);
+    floor.check_player_instance(&account.player)?;
 
-    let game_data = &mut account.game_data.load_mut()?;
+    if account.player.encounter.active {
+        return err!(GameErrorCode::InCombat);
+    }
 
-    game_data.remove_player(account.player.authority.key())?;
+    let floor_data = &mut account.game_data.load_mut()?;
+    let game_data = &mut floor_data.board();
+
+    game_data.take_stairs(account.player.authority.key())?;
+    if let Some(directory) = &mut account.directory {
+        directory.update(floor.instance, game_data.count_players());
+    }
 
     account.player.current_floor += 1;
+    account.player.record_floor();
     msg!("Next floor {}", account.player.current_floor);
 
+    leaderboard::submit_if_present(&mut account.leaderboard, &account.player)?;
+
+    floor_data.save(game_data)?;
     Ok(())
 }
 
 #[derive(Accounts, Session)]
-#[instruction(level_seed: String)]
+#[instruction(floor: FloorAddress)]
 pub struct NextFloor<'info> {
     #[session(
         // The ephemeral key pair signing the transaction
@@ -38,14 +58,49 @@ pub struct NextFloor<'info> {
     )]
     pub player: Account<'info, PlayerData>,
 
-    // There can be multiple levels the seed for the level is passed in the instruction
+    #[account(
+        seeds = [b"config".as_ref()],
+        bump,
+    )]
+    pub config: Account<'info, GameConfig>,
+
+    // There can be multiple instances of every floor, the address is passed in the instruction
     // First player starting a new level will pay for the account in the current setup
     #[account(
         mut,
-        seeds = [level_seed.as_ref()],
+        seeds = [
+            b"floor".as_ref(),
+            config.season.to_le_bytes().as_ref(),
+            floor.floor_id.to_le_bytes().as_ref(),
+            floor.instance.to_le_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub game_data: AccountLoader<'info, FloorData>,
+
+    // Optional, frees the spot of the player on this instance
+    #[account(
+        mut,
+        seeds = [
+            b"directory".as_ref(),
+            config.season.to_le_bytes().as_ref(),
+            floor.floor_id.to_le_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub directory: Option<Account<'info, FloorDirectory>>,
+
+    // Optional, updated with the new floor
+    #[account(
+        mut,
+        seeds = [
+            b"leaderboard".as_ref(),
+            leaderboard.season.to_le_bytes().as_ref(),
+            &[leaderboard.metric],
+        ],
         bump,
     )]
-    pub game_data: AccountLoader<'info, GameData>,
+    pub leaderboard: Option<Account<'info, Leaderboard>>,
 
     #[account(mut)]
     pub signer: Signer<'info>,
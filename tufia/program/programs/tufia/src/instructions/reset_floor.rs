@@ -1,22 +1,31 @@
 pub use crate::errors::GameErrorCode;
+use crate::state::floor_data::FloorData;
 pub use crate::state::game_data::GameData;
-use crate::state::player_data::PlayerData;
+use crate::state::{floor::FloorAddress, game_config::GameConfig, player_data::PlayerData};
 use anchor_lang::prelude::*;
 use session_keys::{Session, SessionToken};
 
-pub fn reset_floor(mut ctx: Context<ResetFloor>, counter: u16) -> Result<()> {
+pub fn reset_floor(mut ctx: Context<ResetFloor>, floor: FloorAddress, counter: u16) -> Result<()> {
     let account: &mut &mut ResetFloor<'_> = &mut ctx.accounts;
 
+    // Resetting throws every player off the floor, only the admin can do it
+    if account.player.authority != account.config.admin {
+        return err!(GameErrorCode::WrongAdmin);
+    }
+
     account.player.last_id = counter;
-    let game_data = &mut account.game_data.load_mut()?;
+    let floor_data = &mut account.game_data.load_mut()?;
+    let game_data = &mut floor_data.board();
+    game_data.check_floor(account.player.authority, &floor)?;
 
     game_data.reset_game()?;
 
+    floor_data.save(game_data)?;
     Ok(())
 }
 
 #[derive(Accounts, Session)]
-#[instruction(level_seed: String)]
+#[instruction(floor: FloorAddress)]
 pub struct ResetFloor<'info> {
     #[session(
         // The ephemeral key pair signing the transaction
@@ -35,16 +44,25 @@ pub struct ResetFloor<'info> {
     )]
     pub player: Account<'info, PlayerData>,
 
-    // There can be multiple levels the seed for the level is passed in the instruction
-    // First player starting a new level will pay for the account in the current setup
     #[account(
-        init_if_needed,
-        payer = signer,
-        space = 10240,
-        seeds = [level_seed.as_ref()],
+        seeds = [b"config".as_ref()],
+        bump,
+    )]
+    pub config: Account<'info, GameConfig>,
+
+    // There can be multiple instances of every floor, the address is passed in the instruction.
+    // Floors are created by buy_next_floor.
+    #[account(
+        mut,
+        seeds = [
+            b"floor".as_ref(),
+            config.season.to_le_bytes().as_ref(),
+            floor.floor_id.to_le_bytes().as_ref(),
+            floor.instance.to_le_bytes().as_ref(),
+        ],
         bump,
     )]
-    pub game_data: AccountLoader<'info, GameData>,
+    pub game_data: AccountLoader<'info, FloorData>,
 
     #[account(mut)]
     pub signer: Signer<'info>,
@@ -0,0 +1,67 @@
+pub use crate::errors::GameErrorCode;
+use crate::state::floor_data::FloorData;
+pub use crate::state::game_data::GameData;
+use crate::state::{floor::FloorAddress, game_config::GameConfig, player_data::PlayerData};
+use anchor_lang::prelude::*;
+use session_keys::{Session, SessionToken};
+
+// Rests on the current tile to heal, see GameData::rest.
+pub fn rest(mut ctx: Context<Rest>, floor: FloorAddress, counter: u16) -> Result<()> {
+    let account: &mut &mut Rest<'_> = &mut ctx.accounts;
+    account.player.last_id = counter;
+    floor.check_player_instance(&account.player)?;
+    account.player.update_energy()?;
+
+    let floor_data = &mut account.game_data.load_mut()?;
+    let game_data = &mut floor_data.board();
+    game_data.update_buffs(&mut account.player)?;
+    game_data.update_health(&mut account.player, account.config.health_regen_seconds)?;
+    game_data.rest(&mut account.player)?;
+
+    msg!("Rested, health {}", account.player.health);
+    floor_data.save(game_data)?;
+    Ok(())
+}
+
+#[derive(Accounts, Session)]
+#[instruction(floor: FloorAddress)]
+pub struct Rest<'info> {
+    #[session(
+        // The ephemeral key pair signing the transaction
+        signer = signer,
+        // The authority of the user account which must have created the session
+        authority = player.authority.key()
+    )]
+    // Session Tokens are passed as optional accounts
+    pub session_token: Option<Account<'info, SessionToken>>,
+
+    // There is one PlayerData account
+    #[account(
+        mut,
+        seeds = [b"player1".as_ref(), player.authority.key().as_ref()],
+        bump,
+    )]
+    pub player: Account<'info, PlayerData>,
+
+    #[account(
+        seeds = [b"config".as_ref()],
+        bump,
+    )]
+    pub config: Account<'info, GameConfig>,
+
+    // The floor the player is on, the health goes on the board tile
+    #[account(
+        mut,
+        seeds = [
+            b"floor".as_ref(),
+            config.season.to_le_bytes().as_ref(),
+            floor.floor_id.to_le_bytes().as_ref(),
+            floor.instance.to_le_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub game_data: AccountLoader<'info, FloorData>,
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+}
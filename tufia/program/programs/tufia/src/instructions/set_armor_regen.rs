@@ -0,0 +1,22 @@
+pub use crate::errors::GameErrorCode;
+use crate::state::game_config::GameConfig;
+use anchor_lang::prelude::*;
+
+pub fn set_armor_regen(ctx: Context<SetArmorRegen>, armor_regen_per_move: u32) -> Result<()> {
+    ctx.accounts.config.armor_regen_per_move = armor_regen_per_move;
+    msg!("Armor regen {} per move", armor_regen_per_move);
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetArmorRegen<'info> {
+    #[account(
+        mut,
+        seeds = [b"config".as_ref()],
+        bump,
+        has_one = admin @ GameErrorCode::WrongAdmin,
+    )]
+    pub config: Account<'info, GameConfig>,
+
+    pub admin: Signer<'info>,
+}
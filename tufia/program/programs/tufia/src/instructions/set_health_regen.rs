@@ -0,0 +1,22 @@
+pub use crate::errors::GameErrorCode;
+use crate::state::game_config::GameConfig;
+use anchor_lang::prelude::*;
+
+pub fn set_health_regen(ctx: Context<SetHealthRegen>, health_regen_seconds: i64) -> Result<()> {
+    ctx.accounts.config.health_regen_seconds = health_regen_seconds;
+    msg!("Health regen every {} seconds", health_regen_seconds);
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetHealthRegen<'info> {
+    #[account(
+        mut,
+        seeds = [b"config".as_ref()],
+        bump,
+        has_one = admin @ GameErrorCode::WrongAdmin,
+    )]
+    pub config: Account<'info, GameConfig>,
+
+    pub admin: Signer<'info>,
+}
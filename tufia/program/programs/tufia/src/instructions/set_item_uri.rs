@@ -0,0 +1,23 @@
+pub use crate::errors::GameErrorCode;
+use crate::state::{game_config::GameConfig, item};
+use anchor_lang::prelude::*;
+
+pub fn set_item_uri(ctx: Context<SetItemUri>, item_uri: String) -> Result<()> {
+    item::validate_uri(&item_uri)?;
+    msg!("Item uri {}", item_uri);
+    ctx.accounts.config.item_uri = item_uri;
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetItemUri<'info> {
+    #[account(
+        mut,
+        seeds = [b"config".as_ref()],
+        bump,
+        has_one = admin @ GameErrorCode::WrongAdmin,
+    )]
+    pub config: Account<'info, GameConfig>,
+
+    pub admin: Signer<'info>,
+}
@@ -0,0 +1,110 @@
+pub use crate::errors::GameErrorCode;
+use crate::state::floor_data::FloorData;
+pub use crate::state::game_data::GameData;
+use crate::state::{
+    floor::FloorAddress,
+    game_config::GameConfig,
+    name_registry::{self, NameRegistry},
+    player_data::PlayerData,
+};
+use anchor_lang::prelude::*;
+
+pub fn set_profile(
+    ctx: Context<SetProfile>,
+    floor: FloorAddress,
+    name: String,
+    avatar: u8,
+    title: String,
+) -> Result<()> {
+    name_registry::validate_name(&name)?;
+    name_registry::validate_title(&title)?;
+    name_registry::validate_avatar(avatar)?;
+
+    let account = ctx.accounts;
+    floor.check_player_instance(&account.player)?;
+
+    // The old name has to be released when the name changes, and must not be
+    // passed when it stays the same since it would be closed.
+    let name_changed =
+        name_registry::registry_seed(&account.player.name) != name_registry::registry_seed(&name);
+    let must_release = name_changed && !account.player.name.is_empty();
+    if must_release != account.old_name_registry.is_some() {
+        return err!(GameErrorCode::WrongNameRegistry);
+    }
+
+    let registry = &mut account.name_registry;
+    if registry.authority != Pubkey::default() && registry.authority != account.signer.key() {
+        return err!(GameErrorCode::NameTaken);
+    }
+    registry.authority = account.signer.key();
+    registry.name = name.clone();
+
+    let player = &mut account.player;
+    player.name = name;
+    player.avatar = avatar;
+    player.title = title;
+    player.tile_data.tile_avatar = avatar;
+    player.tile_data.tile_name = name_registry::name_tag(&player.name);
+
+    let floor_data = &mut account.game_data.load_mut()?;
+    let game_data = &mut floor_data.board();
+    game_data.apply_profile(player);
+
+    msg!("Profile set: {} {}", player.name, player.avatar);
+    floor_data.save(game_data)?;
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(floor: FloorAddress, name: String)]
+pub struct SetProfile<'info> {
+    // Only the wallet itself can change the profile, since it pays for the
+    // name registry account.
+    #[account(
+        mut,
+        seeds = [b"player1".as_ref(), signer.key().as_ref()],
+        bump,
+    )]
+    pub player: Account<'info, PlayerData>,
+
+    #[account(
+        seeds = [b"config".as_ref()],
+        bump,
+    )]
+    pub config: Account<'info, GameConfig>,
+
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = 100, // 8+32+4+12
+        seeds = [b"name".as_ref(), name_registry::registry_seed(&name).as_bytes()],
+        bump,
+    )]
+    pub name_registry: Account<'info, NameRegistry>,
+
+    // The registry of the current name, closed so the name is free again
+    #[account(
+        mut,
+        close = signer,
+        seeds = [b"name".as_ref(), name_registry::registry_seed(&player.name).as_bytes()],
+        bump,
+    )]
+    pub old_name_registry: Option<Account<'info, NameRegistry>>,
+
+    // The floor the player is on, so the board tile shows the new profile
+    #[account(
+        mut,
+        seeds = [
+            b"floor".as_ref(),
+            config.season.to_le_bytes().as_ref(),
+            floor.floor_id.to_le_bytes().as_ref(),
+            floor.instance.to_le_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub game_data: AccountLoader<'info, FloorData>,
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,37 @@
+pub use crate::errors::GameErrorCode;
+use crate::{
+    constants::*,
+    state::{
+        crafting::{self, Recipe},
+        game_config::GameConfig,
+    },
+};
+use anchor_lang::prelude::*;
+
+// Replaces the recipe at the index or adds a new one at the end
+pub fn set_recipe(ctx: Context<SetRecipe>, index: u8, recipe: Recipe) -> Result<()> {
+    crafting::validate_recipe(&recipe)?;
+
+    let recipes = &mut ctx.accounts.config.recipes;
+    match index as usize {
+        i if i < recipes.len() => recipes[i] = recipe,
+        i if i == recipes.len() && i < MAX_RECIPES => recipes.push(recipe),
+        _ => return err!(GameErrorCode::InvalidRecipe),
+    }
+
+    msg!("Recipe {} set", index);
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetRecipe<'info> {
+    #[account(
+        mut,
+        seeds = [b"config".as_ref()],
+        bump,
+        has_one = admin @ GameErrorCode::WrongAdmin,
+    )]
+    pub config: Account<'info, GameConfig>,
+
+    pub admin: Signer<'info>,
+}
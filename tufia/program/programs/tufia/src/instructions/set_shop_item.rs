@@ -0,0 +1,69 @@
+pub use crate::errors::GameErrorCode;
+use crate::state::{
+    game_config::GameConfig,
+    shop::{self, Shop, ShopItem},
+};
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{Mint, Token, TokenAccount},
+};
+
+// Lists an item in one of the shops or updates its prices and stock. The shop
+// and its treasury are created with the first item.
+pub fn set_shop_item(ctx: Context<SetShopItem>, kind: u8, item: ShopItem) -> Result<()> {
+    shop::validate_kind(kind)?;
+
+    let shop = &mut ctx.accounts.shop;
+    shop.kind = kind;
+    shop.set_item(item)?;
+
+    msg!(
+        "Shop {} item {} price {} stock {}",
+        kind,
+        item.item_id,
+        item.price,
+        item.stock
+    );
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(kind: u8)]
+pub struct SetShopItem<'info> {
+    #[account(
+        seeds = [b"config".as_ref()],
+        bump,
+        has_one = admin @ GameErrorCode::WrongAdmin,
+    )]
+    pub config: Account<'info, GameConfig>,
+
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = 400, // 8+1+4+16*(2+8+8+4)
+        seeds = [b"shop".as_ref(), &[kind]],
+        bump,
+    )]
+    pub shop: Account<'info, Shop>,
+
+    #[account(
+        seeds = [b"gold".as_ref()],
+        bump,
+    )]
+    pub gold_mint: Account<'info, Mint>,
+
+    #[account(
+        init_if_needed,
+        payer = admin,
+        associated_token::mint = gold_mint,
+        associated_token::authority = shop,
+    )]
+    pub treasury: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
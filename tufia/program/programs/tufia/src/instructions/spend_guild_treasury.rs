@@ -0,0 +1,63 @@
+pub use crate::errors::GameErrorCode;
+use crate::state::{guild::Guild, name_registry};
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+
+// Only the leader decides what the treasury is spent on
+pub fn spend_guild_treasury(ctx: Context<SpendGuildTreasury>, amount: u64) -> Result<()> {
+    ctx.accounts.guild.check_leader(ctx.accounts.signer.key())?;
+
+    let seed = name_registry::registry_seed(&ctx.accounts.guild.name);
+    let signer_seeds: &[&[&[u8]]] = &[&[b"guild".as_ref(), seed.as_bytes(), &[ctx.bumps.guild]]];
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.treasury.to_account_info(),
+                to: ctx.accounts.recipient.to_account_info(),
+                authority: ctx.accounts.guild.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        amount,
+    )?;
+
+    msg!(
+        "Spent {} gold of {} on {}",
+        amount,
+        ctx.accounts.guild.name,
+        ctx.accounts.recipient.owner
+    );
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SpendGuildTreasury<'info> {
+    #[account(
+        seeds = [b"guild".as_ref(), name_registry::registry_seed(&guild.name).as_bytes()],
+        bump,
+    )]
+    pub guild: Account<'info, Guild>,
+
+    #[account(
+        seeds = [b"gold".as_ref()],
+        bump,
+    )]
+    pub gold_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        address = guild.treasury,
+    )]
+    pub treasury: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = gold_mint,
+    )]
+    pub recipient: Account<'info, TokenAccount>,
+
+    // Guild instructions are signed by the wallet itself
+    pub signer: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
@@ -0,0 +1,101 @@
+pub use crate::errors::GameErrorCode;
+use crate::state::floor_data::FloorData;
+pub use crate::state::game_data::GameData;
+use crate::state::{
+    daily::{self, DailyLeaderboard, DailyRun},
+    player_data::PlayerData,
+};
+use anchor_lang::prelude::*;
+use session_keys::{Session, SessionToken};
+
+pub fn start_daily_run(mut ctx: Context<StartDailyRun>, counter: u16, day: u32) -> Result<()> {
+    let account: &mut &mut StartDailyRun<'_> = &mut ctx.accounts;
+    account.player.last_id = counter;
+    daily::check_today(day)?;
+
+    if account.player.encounter.active {
+        return err!(GameErrorCode::InCombat);
+    }
+
+    account.daily_leaderboard.day = day;
+
+    let run = &mut account.daily_run;
+    run.player = account.player.authority;
+    run.day = day;
+    run.depth = 1;
+
+    let floor_data = &mut account.game_data.load_init()?;
+    let game_data = &mut floor_data.board();
+    game_data.generate_daily_floor(
+        &account.player,
+        run.depth as u32,
+        daily::daily_seed(day, run.depth),
+    )?;
+    floor_data.save(game_data)?;
+
+    msg!("Daily run started for day {}", day);
+    Ok(())
+}
+
+#[derive(Accounts, Session)]
+#[instruction(counter: u16, day: u32)]
+pub struct StartDailyRun<'info> {
+    #[session(
+        // The ephemeral key pair signing the transaction
+        signer = signer,
+        // The authority of the user account which must have created the session
+        authority = player.authority.key()
+    )]
+    // Session Tokens are passed as optional accounts
+    pub session_token: Option<Account<'info, SessionToken>>,
+
+    // There is one PlayerData account
+    #[account(
+        mut,
+        seeds = [b"player1".as_ref(), player.authority.key().as_ref()],
+        bump,
+    )]
+    pub player: Account<'info, PlayerData>,
+
+    // Can only be created once per day, which limits players to one attempt
+    #[account(
+        init,
+        payer = signer,
+        space = 100, // 8+32+4+2+4+4+1
+        seeds = [
+            b"daily_run".as_ref(),
+            day.to_le_bytes().as_ref(),
+            player.authority.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub daily_run: Account<'info, DailyRun>,
+
+    // Every player plays the daily floors on their own board
+    #[account(
+        init,
+        payer = signer,
+        space = FloorData::SPACE,
+        seeds = [
+            b"daily".as_ref(),
+            day.to_le_bytes().as_ref(),
+            player.authority.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub game_data: AccountLoader<'info, FloorData>,
+
+    // The first player of the day pays for the leaderboard
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = 700, // 8+4+4+10*(32+2+4+4+8+1)
+        seeds = [b"daily_board".as_ref(), day.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub daily_leaderboard: Account<'info, DailyLeaderboard>,
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,56 @@
+pub use crate::errors::GameErrorCode;
+use crate::state::floor_data::FloorData;
+pub use crate::state::game_data::GameData;
+use crate::state::{
+    floor::{FloorAddress, FloorDirectory},
+    game_config::GameConfig,
+};
+use anchor_lang::prelude::*;
+
+// Anyone can recount the players of an instance, for example after players
+// died on it.
+pub fn sync_floor_instance(ctx: Context<SyncFloorInstance>, floor: FloorAddress) -> Result<()> {
+    let game_data = &ctx.accounts.game_data.load()?.board();
+    let players = game_data.count_players();
+    ctx.accounts.directory.update(floor.instance, players);
+
+    msg!(
+        "Floor {} instance {} has {} players",
+        floor.floor_id,
+        floor.instance,
+        players
+    );
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(floor: FloorAddress)]
+pub struct SyncFloorInstance<'info> {
+    #[account(
+        seeds = [b"config".as_ref()],
+        bump,
+    )]
+    pub config: Account<'info, GameConfig>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"directory".as_ref(),
+            config.season.to_le_bytes().as_ref(),
+            floor.floor_id.to_le_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub directory: Account<'info, FloorDirectory>,
+
+    #[account(
+        seeds = [
+            b"floor".as_ref(),
+            config.season.to_le_bytes().as_ref(),
+            floor.floor_id.to_le_bytes().as_ref(),
+            floor.instance.to_le_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub game_data: AccountLoader<'info, FloorData>,
+}
@@ -0,0 +1,22 @@
+pub use crate::errors::GameErrorCode;
+use crate::state::game_config::GameConfig;
+use anchor_lang::prelude::*;
+
+pub fn update_config(ctx: Context<UpdateConfig>, boss_floor_interval: u16) -> Result<()> {
+    ctx.accounts.config.boss_floor_interval = boss_floor_interval;
+    msg!("Boss floor every {} floors", boss_floor_interval);
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    #[account(
+        mut,
+        seeds = [b"config".as_ref()],
+        bump,
+        has_one = admin @ GameErrorCode::WrongAdmin,
+    )]
+    pub config: Account<'info, GameConfig>,
+
+    pub admin: Signer<'info>,
+}
@@ -0,0 +1,69 @@
+pub use crate::errors::GameErrorCode;
+use crate::state::{
+    game_config::GameConfig,
+    guild::{Guild, GuildLeaderboard},
+    name_registry,
+    player_data::PlayerData,
+};
+use anchor_lang::prelude::*;
+
+// Anyone can push the season stats of a member into their guild and the guild
+// leaderboard, they are read from the PlayerData account.
+pub fn update_guild_ranking(ctx: Context<UpdateGuildRanking>) -> Result<()> {
+    let guild = &mut ctx.accounts.guild;
+    if ctx.accounts.player.guild != guild.key() {
+        return err!(GameErrorCode::NotInGuild);
+    }
+    guild.update_member(&ctx.accounts.player)?;
+
+    let season = ctx.accounts.config.season;
+    let (deepest_floor, kills) = guild.ranking(season);
+
+    let leaderboard = &mut ctx.accounts.guild_leaderboard;
+    leaderboard.season = season;
+    leaderboard.submit(guild.key(), deepest_floor, kills)?;
+
+    msg!(
+        "Guild {} deepest floor {} kills {}",
+        guild.name,
+        deepest_floor,
+        kills
+    );
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct UpdateGuildRanking<'info> {
+    #[account(
+        seeds = [b"config".as_ref()],
+        bump,
+    )]
+    pub config: Account<'info, GameConfig>,
+
+    #[account(
+        seeds = [b"player1".as_ref(), player.authority.key().as_ref()],
+        bump,
+    )]
+    pub player: Account<'info, PlayerData>,
+
+    #[account(
+        mut,
+        seeds = [b"guild".as_ref(), name_registry::registry_seed(&guild.name).as_bytes()],
+        bump,
+    )]
+    pub guild: Account<'info, Guild>,
+
+    // The first update of a season pays for the leaderboard
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = 1000, // 8+2+4+20*(32+2+4+8)
+        seeds = [b"guild_board".as_ref(), config.season.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub guild_leaderboard: Account<'info, GuildLeaderboard>,
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
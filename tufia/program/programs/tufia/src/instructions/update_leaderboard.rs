@@ -0,0 +1,29 @@
+pub use crate::errors::GameErrorCode;
+use crate::state::{leaderboard::Leaderboard, player_data::PlayerData};
+use anchor_lang::prelude::*;
+
+// Anyone can submit the stats of any player, they are read from the
+// PlayerData account so they can not be faked.
+pub fn update_leaderboard(ctx: Context<UpdateLeaderboard>) -> Result<()> {
+    ctx.accounts.leaderboard.submit(&ctx.accounts.player)
+}
+
+#[derive(Accounts)]
+pub struct UpdateLeaderboard<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"leaderboard".as_ref(),
+            leaderboard.season.to_le_bytes().as_ref(),
+            &[leaderboard.metric],
+        ],
+        bump,
+    )]
+    pub leaderboard: Account<'info, Leaderboard>,
+
+    #[account(
+        seeds = [b"player1".as_ref(), player.authority.key().as_ref()],
+        bump,
+    )]
+    pub player: Account<'info, PlayerData>,
+}
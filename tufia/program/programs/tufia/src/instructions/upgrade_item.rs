@@ -0,0 +1,40 @@
+pub use crate::errors::GameErrorCode;
+use crate::state::player_data::PlayerData;
+use anchor_lang::prelude::*;
+use session_keys::{Session, SessionToken};
+
+// Upgrades can fail, the crystals are used up either way
+pub fn upgrade_item(mut ctx: Context<UpgradeItem>, counter: u16, index: u8) -> Result<()> {
+    let account: &mut &mut UpgradeItem<'_> = &mut ctx.accounts;
+    account.player.last_id = counter;
+
+    if account.player.upgrade_gear(index)? {
+        msg!("Upgrade of item {} worked", index);
+    } else {
+        msg!("Upgrade of item {} failed", index);
+    }
+    Ok(())
+}
+
+#[derive(Accounts, Session)]
+pub struct UpgradeItem<'info> {
+    #[session(
+        // The ephemeral key pair signing the transaction
+        signer = signer,
+        // The authority of the user account which must have created the session
+        authority = player.authority.key()
+    )]
+    // Session Tokens are passed as optional accounts
+    pub session_token: Option<Account<'info, SessionToken>>,
+
+    // There is one PlayerData account
+    #[account(
+        mut,
+        seeds = [b"player1".as_ref(), player.authority.key().as_ref()],
+        bump,
+    )]
+    pub player: Account<'info, PlayerData>,
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+}
@@ -0,0 +1,73 @@
+pub use crate::errors::GameErrorCode;
+use crate::state::floor_data::FloorData;
+pub use crate::state::game_data::GameData;
+use crate::state::{floor::FloorAddress, game_config::GameConfig, player_data::PlayerData};
+use anchor_lang::prelude::*;
+use session_keys::{Session, SessionToken};
+
+// Drinks a potion or another consumable of the inventory, see
+// GameData::use_consumable.
+pub fn use_item(
+    mut ctx: Context<UseItem>,
+    floor: FloorAddress,
+    counter: u16,
+    item_id: u16,
+) -> Result<()> {
+    let account: &mut &mut UseItem<'_> = &mut ctx.accounts;
+    account.player.last_id = counter;
+    floor.check_player_instance(&account.player)?;
+    account.player.update_energy()?;
+
+    let floor_data = &mut account.game_data.load_mut()?;
+    let game_data = &mut floor_data.board();
+    game_data.update_buffs(&mut account.player)?;
+    game_data.update_health(&mut account.player, account.config.health_regen_seconds)?;
+    game_data.use_consumable(&mut account.player, item_id)?;
+
+    msg!("Used item {}", item_id);
+    floor_data.save(game_data)?;
+    Ok(())
+}
+
+#[derive(Accounts, Session)]
+#[instruction(floor: FloorAddress)]
+pub struct UseItem<'info> {
+    #[session(
+        // The ephemeral key pair signing the transaction
+        signer = signer,
+        // The authority of the user account which must have created the session
+        authority = player.authority.key()
+    )]
+    // Session Tokens are passed as optional accounts
+    pub session_token: Option<Account<'info, SessionToken>>,
+
+    // There is one PlayerData account
+    #[account(
+        mut,
+        seeds = [b"player1".as_ref(), player.authority.key().as_ref()],
+        bump,
+    )]
+    pub player: Account<'info, PlayerData>,
+
+    #[account(
+        seeds = [b"config".as_ref()],
+        bump,
+    )]
+    pub config: Account<'info, GameConfig>,
+
+    // The floor the player is on, buffs go on the board tile
+    #[account(
+        mut,
+        seeds = [
+            b"floor".as_ref(),
+            config.season.to_le_bytes().as_ref(),
+            floor.floor_id.to_le_bytes().as_ref(),
+            floor.instance.to_le_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub game_data: AccountLoader<'info, FloorData>,
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+}
@@ -0,0 +1,162 @@
+pub use crate::errors::GameErrorCode;
+use crate::state::{
+    game_config::GameConfig,
+    item::{self, ItemRecord},
+    player_data::PlayerData,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    metadata::{
+        create_master_edition_v3, create_metadata_accounts_v3, CreateMasterEditionV3,
+        CreateMetadataAccountsV3, Metadata,
+    },
+    token::{self, Mint, MintTo, Token, TokenAccount},
+};
+
+// Takes a gear item out of the inventory and mints it as an NFT to the wallet.
+// The config PDA is mint and update authority, the master edition makes sure
+// there is only ever one token.
+pub fn withdraw_item(ctx: Context<WithdrawItem>, index: u8) -> Result<()> {
+    let gear = ctx.accounts.player.remove_gear(index)?;
+    let mint = ctx.accounts.item_mint.key();
+
+    let record = &mut ctx.accounts.item_record;
+    record.mint = mint;
+    record.item = gear;
+
+    let signer_seeds: &[&[&[u8]]] = &[&[b"config".as_ref(), &[ctx.bumps.config]]];
+    token::mint_to(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            MintTo {
+                mint: ctx.accounts.item_mint.to_account_info(),
+                to: ctx.accounts.player_item.to_account_info(),
+                authority: ctx.accounts.config.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        1,
+    )?;
+
+    create_metadata_accounts_v3(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_metadata_program.to_account_info(),
+            CreateMetadataAccountsV3 {
+                metadata: ctx.accounts.metadata.to_account_info(),
+                mint: ctx.accounts.item_mint.to_account_info(),
+                mint_authority: ctx.accounts.config.to_account_info(),
+                payer: ctx.accounts.signer.to_account_info(),
+                update_authority: ctx.accounts.config.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+                rent: ctx.accounts.rent.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        item::metadata(&gear, &ctx.accounts.config.item_uri, mint),
+        false,
+        true,
+        None,
+    )?;
+
+    create_master_edition_v3(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_metadata_program.to_account_info(),
+            CreateMasterEditionV3 {
+                edition: ctx.accounts.master_edition.to_account_info(),
+                mint: ctx.accounts.item_mint.to_account_info(),
+                update_authority: ctx.accounts.config.to_account_info(),
+                mint_authority: ctx.accounts.config.to_account_info(),
+                payer: ctx.accounts.signer.to_account_info(),
+                metadata: ctx.accounts.metadata.to_account_info(),
+                token_program: ctx.accounts.token_program.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+                rent: ctx.accounts.rent.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        Some(0),
+    )?;
+
+    msg!("Withdrew item {} as {}", index, mint);
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct WithdrawItem<'info> {
+    // NFTs belong to the wallet, so this is not signed with a session key
+    #[account(
+        mut,
+        seeds = [b"player1".as_ref(), signer.key().as_ref()],
+        bump,
+    )]
+    pub player: Account<'info, PlayerData>,
+
+    #[account(
+        seeds = [b"config".as_ref()],
+        bump,
+    )]
+    pub config: Account<'info, GameConfig>,
+
+    // A new key pair for every item
+    #[account(
+        init,
+        payer = signer,
+        mint::decimals = 0,
+        mint::authority = config,
+        mint::freeze_authority = config,
+    )]
+    pub item_mint: Account<'info, Mint>,
+
+    #[account(
+        init,
+        payer = signer,
+        associated_token::mint = item_mint,
+        associated_token::authority = signer,
+    )]
+    pub player_item: Account<'info, TokenAccount>,
+
+    #[account(
+        init,
+        payer = signer,
+        space = 64, // 8+32+1+4+4+4+2+1+1+1
+        seeds = [b"item".as_ref(), item_mint.key().as_ref()],
+        bump,
+    )]
+    pub item_record: Account<'info, ItemRecord>,
+
+    /// CHECK: Created by the token metadata program
+    #[account(
+        mut,
+        seeds = [
+            b"metadata".as_ref(),
+            token_metadata_program.key().as_ref(),
+            item_mint.key().as_ref(),
+        ],
+        seeds::program = token_metadata_program.key(),
+        bump,
+    )]
+    pub metadata: UncheckedAccount<'info>,
+
+    /// CHECK: Created by the token metadata program
+    #[account(
+        mut,
+        seeds = [
+            b"metadata".as_ref(),
+            token_metadata_program.key().as_ref(),
+            item_mint.key().as_ref(),
+            b"edition".as_ref(),
+        ],
+        seeds::program = token_metadata_program.key(),
+        bump,
+    )]
+    pub master_edition: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_metadata_program: Program<'info, Metadata>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
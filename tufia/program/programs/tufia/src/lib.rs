@@ -6,6 +6,10 @@ pub mod errors;
 pub mod instructions;
 pub mod state;
 use instructions::*;
+use state::{
+    crafting::Recipe, floor::FloorAddress, player_data::StatAllocation, shop::ShopItem,
+    trade::OfferArgs,
+};
 
 declare_id!("Bip92wN115UuArG265UHWZJVwuL64ymthirNZAw5jHYJ");
 
@@ -14,8 +18,60 @@ pub mod tufia {
 
     use super::*;
 
-    pub fn init_player(ctx: Context<InitPlayer>, _level_seed: String) -> Result<()> {
-        init_player::init_player(ctx)
+    pub fn init_config(ctx: Context<InitConfig>, boss_floor_interval: u16) -> Result<()> {
+        init_config::init_config(ctx, boss_floor_interval)
+    }
+
+    pub fn update_config(ctx: Context<UpdateConfig>, boss_floor_interval: u16) -> Result<()> {
+        update_config::update_config(ctx, boss_floor_interval)
+    }
+
+    // Armor regenerated on every move, skills add to it.
+    pub fn set_armor_regen(ctx: Context<SetArmorRegen>, armor_regen_per_move: u32) -> Result<()> {
+        set_armor_regen::set_armor_regen(ctx, armor_regen_per_move)
+    }
+
+    // Seconds per point of health regenerated over time, 0 turns it off.
+    pub fn set_health_regen(ctx: Context<SetHealthRegen>, health_regen_seconds: i64) -> Result<()> {
+        set_health_regen::set_health_regen(ctx, health_regen_seconds)
+    }
+
+    // Creates the gold token mint, needed for season rewards.
+    pub fn init_gold_mint(ctx: Context<InitGoldMint>) -> Result<()> {
+        init_gold_mint::init_gold_mint(ctx)
+    }
+
+    // Lists an item in the lobby or merchant shop, see shop.rs.
+    pub fn set_shop_item(ctx: Context<SetShopItem>, kind: u8, item: ShopItem) -> Result<()> {
+        set_shop_item::set_shop_item(ctx, kind, item)
+    }
+
+    // Archives the passed leaderboard and starts the next season. All floors
+    // and leaderboards of the new season are new accounts.
+    pub fn end_season(ctx: Context<EndSeason>) -> Result<()> {
+        end_season::end_season(ctx)
+    }
+
+    // Top players of an ended season get gold and a badge.
+    pub fn claim_season_reward(ctx: Context<ClaimSeasonReward>, season: u16) -> Result<()> {
+        claim_season_reward::claim_season_reward(ctx, season)
+    }
+
+    // Creates the leaderboard of the current season for one of the
+    // LEADERBOARD_METRIC_* metrics.
+    pub fn init_leaderboard(ctx: Context<InitLeaderboard>, metric: u8) -> Result<()> {
+        init_leaderboard::init_leaderboard(ctx, metric)
+    }
+
+    // Submits the lifetime stats of a player to a leaderboard.
+    pub fn update_leaderboard(ctx: Context<UpdateLeaderboard>) -> Result<()> {
+        update_leaderboard::update_leaderboard(ctx)
+    }
+
+    // Creates the player account. The class (see player_class.rs) decides the
+    // base stats, the level up growth and the combat ability.
+    pub fn init_player(ctx: Context<InitPlayer>, class: u8) -> Result<()> {
+        init_player::init_player(ctx, class)
     }
 
     // This function moves the player to a new tile if he is on the board.
@@ -26,12 +82,27 @@ pub mod tufia {
     )]
     pub fn move_to_tile(
         ctx: Context<MoveToTile>,
-        _level_seed: String,
+        floor: FloorAddress,
         counter: u16,
         x: u64,
         y: u64,
     ) -> Result<()> {
-        move_to_tile::move_to_tile(ctx, counter, x, y)
+        move_to_tile::move_to_tile(ctx, floor, counter, x, y)
+    }
+
+    // Plays one round of the encounter the player is in. See COMBAT_ACTION_*
+    // in constants.rs for the possible actions.
+    #[session_auth_or(
+        ctx.accounts.player.authority.key() == ctx.accounts.signer.key(),
+        GameErrorCode::WrongAuthority
+    )]
+    pub fn combat_action(
+        ctx: Context<CombatAction>,
+        floor: FloorAddress,
+        counter: u16,
+        action: u8,
+    ) -> Result<()> {
+        combat_action::combat_action(ctx, floor, counter, action)
     }
 
     // This function moves the player to a new tile if he is on the board.
@@ -42,31 +113,348 @@ pub mod tufia {
     )]
     pub fn move_to_next_floor(
         ctx: Context<NextFloor>,
-        _level_seed: String,
+        floor: FloorAddress,
         counter: u16,
     ) -> Result<()> {
-        next_floor::next_floor(ctx, counter)
+        next_floor::next_floor(ctx, floor, counter)
     }
 
+    // Puts the player on the floor they are on. The floor directory routes
+    // them to an instance with space or a new instance is created.
     #[session_auth_or(
         ctx.accounts.player.authority.key() == ctx.accounts.signer.key(),
         GameErrorCode::WrongAuthority
     )]
     pub fn buy_next_floor(
         ctx: Context<BuyNextFloor>,
-        _level_seed: String,
+        floor: FloorAddress,
         counter: u16,
     ) -> Result<()> {
-        buy_next_floor::buy_next_floor(ctx, counter, _level_seed)
+        buy_next_floor::buy_next_floor(ctx, counter, floor)
     }
 
-    // This function moves the player to a new tile if he is on the board.
-    // TODO: add enemies and chests
+    // Clears the board of a floor instance. Only the admin can do this.
+    #[session_auth_or(
+        ctx.accounts.player.authority.key() == ctx.accounts.signer.key(),
+        GameErrorCode::WrongAuthority
+    )]
+    pub fn reset_floor(ctx: Context<ResetFloor>, floor: FloorAddress, counter: u16) -> Result<()> {
+        reset_floor::reset_floor(ctx, floor, counter)
+    }
+
+    // Deals one round of damage to the boss of the floor. The boss strikes back.
+    #[session_auth_or(
+        ctx.accounts.player.authority.key() == ctx.accounts.signer.key(),
+        GameErrorCode::WrongAuthority
+    )]
+    pub fn attack_boss(ctx: Context<AttackBoss>, floor: FloorAddress, counter: u16) -> Result<()> {
+        attack_boss::attack_boss(ctx, floor, counter)
+    }
+
+    // Pays out the xp share of a defeated boss, split by damage dealt.
+    #[session_auth_or(
+        ctx.accounts.player.authority.key() == ctx.accounts.signer.key(),
+        GameErrorCode::WrongAuthority
+    )]
+    pub fn claim_boss_reward(
+        ctx: Context<ClaimBossReward>,
+        floor: FloorAddress,
+        counter: u16,
+    ) -> Result<()> {
+        claim_boss_reward::claim_boss_reward(ctx, floor, counter)
+    }
+
+    // Spends unspent stat points from level ups on health, damage, defence and
+    // armor.
     #[session_auth_or(
         ctx.accounts.player.authority.key() == ctx.accounts.signer.key(),
         GameErrorCode::WrongAuthority
     )]
-    pub fn reset_floor(ctx: Context<ResetFloor>, _level_seed: String, counter: u16) -> Result<()> {
-        reset_floor::reset_floor(ctx, counter)
+    pub fn allocate_points(
+        ctx: Context<AllocatePoints>,
+        floor: FloorAddress,
+        counter: u16,
+        allocation: StatAllocation,
+    ) -> Result<()> {
+        allocate_points::allocate_points(ctx, floor, counter, allocation)
+    }
+
+    // Puts gear of the inventory on or takes it off.
+    #[session_auth_or(
+        ctx.accounts.player.authority.key() == ctx.accounts.signer.key(),
+        GameErrorCode::WrongAuthority
+    )]
+    pub fn equip_item(
+        ctx: Context<EquipItem>,
+        floor: FloorAddress,
+        counter: u16,
+        index: u8,
+        equip: bool,
+    ) -> Result<()> {
+        equip_item::equip_item(ctx, floor, counter, index, equip)
+    }
+
+    // Turns materials into gear with one of the recipes of the config.
+    #[session_auth_or(
+        ctx.accounts.player.authority.key() == ctx.accounts.signer.key(),
+        GameErrorCode::WrongAuthority
+    )]
+    pub fn craft_item(ctx: Context<CraftItem>, counter: u16, recipe: u8) -> Result<()> {
+        craft_item::craft_item(ctx, counter, recipe)
+    }
+
+    // Raises the tier of unequipped gear for crystals, can fail.
+    #[session_auth_or(
+        ctx.accounts.player.authority.key() == ctx.accounts.signer.key(),
+        GameErrorCode::WrongAuthority
+    )]
+    pub fn upgrade_item(ctx: Context<UpgradeItem>, counter: u16, index: u8) -> Result<()> {
+        upgrade_item::upgrade_item(ctx, counter, index)
+    }
+
+    // Uses a consumable outside of fights, potions and elixirs can give
+    // timed buffs.
+    #[session_auth_or(
+        ctx.accounts.player.authority.key() == ctx.accounts.signer.key(),
+        GameErrorCode::WrongAuthority
+    )]
+    pub fn use_item(
+        ctx: Context<UseItem>,
+        floor: FloorAddress,
+        counter: u16,
+        item_id: u16,
+    ) -> Result<()> {
+        use_item::use_item(ctx, floor, counter, item_id)
+    }
+
+    // Spends energy to heal on a tile without enemies next to it.
+    #[session_auth_or(
+        ctx.accounts.player.authority.key() == ctx.accounts.signer.key(),
+        GameErrorCode::WrongAuthority
+    )]
+    pub fn rest(ctx: Context<Rest>, floor: FloorAddress, counter: u16) -> Result<()> {
+        rest::rest(ctx, floor, counter)
+    }
+
+    // Learns a skill of the skill tree, see skills.rs.
+    #[session_auth_or(
+        ctx.accounts.player.authority.key() == ctx.accounts.signer.key(),
+        GameErrorCode::WrongAuthority
+    )]
+    pub fn learn_skill(ctx: Context<LearnSkill>, counter: u16, skill: u8) -> Result<()> {
+        learn_skill::learn_skill(ctx, counter, skill)
+    }
+
+    // Sets the name, avatar and title of the player. Names are unique, see
+    // name_registry.rs.
+    pub fn set_profile(
+        ctx: Context<SetProfile>,
+        floor: FloorAddress,
+        name: String,
+        avatar: u8,
+        title: String,
+    ) -> Result<()> {
+        set_profile::set_profile(ctx, floor, name, avatar, title)
+    }
+
+    // Starts today's daily dungeon. Every player gets one attempt per day, see
+    // daily.rs.
+    #[session_auth_or(
+        ctx.accounts.player.authority.key() == ctx.accounts.signer.key(),
+        GameErrorCode::WrongAuthority
+    )]
+    pub fn start_daily_run(ctx: Context<StartDailyRun>, counter: u16, day: u32) -> Result<()> {
+        start_daily_run::start_daily_run(ctx, counter, day)
+    }
+
+    #[session_auth_or(
+        ctx.accounts.player.authority.key() == ctx.accounts.signer.key(),
+        GameErrorCode::WrongAuthority
+    )]
+    pub fn daily_move(
+        ctx: Context<DailyMove>,
+        counter: u16,
+        day: u32,
+        x: u64,
+        y: u64,
+    ) -> Result<()> {
+        daily_move::daily_move(ctx, counter, day, x, y)
+    }
+
+    #[session_auth_or(
+        ctx.accounts.player.authority.key() == ctx.accounts.signer.key(),
+        GameErrorCode::WrongAuthority
+    )]
+    pub fn daily_combat_action(
+        ctx: Context<DailyCombatAction>,
+        counter: u16,
+        day: u32,
+        action: u8,
+    ) -> Result<()> {
+        daily_combat_action::daily_combat_action(ctx, counter, day, action)
+    }
+
+    // Goes one daily floor deeper. The player has to stand next to the stairs.
+    #[session_auth_or(
+        ctx.accounts.player.authority.key() == ctx.accounts.signer.key(),
+        GameErrorCode::WrongAuthority
+    )]
+    pub fn daily_next_floor(ctx: Context<DailyNextFloor>, counter: u16, day: u32) -> Result<()> {
+        daily_next_floor::daily_next_floor(ctx, counter, day)
+    }
+
+    // The best players of a finished day get gold.
+    pub fn claim_daily_reward(ctx: Context<ClaimDailyReward>, day: u32) -> Result<()> {
+        claim_daily_reward::claim_daily_reward(ctx, day)
+    }
+
+    // Recounts the players of a floor instance in the floor directory.
+    pub fn sync_floor_instance(ctx: Context<SyncFloorInstance>, floor: FloorAddress) -> Result<()> {
+        sync_floor_instance::sync_floor_instance(ctx, floor)
+    }
+
+    // Parties of up to MAX_PARTY_SIZE players share xp and loot by the rules
+    // in party.rs and play on the same floor instance.
+    #[session_auth_or(
+        ctx.accounts.player.authority.key() == ctx.accounts.signer.key(),
+        GameErrorCode::WrongAuthority
+    )]
+    pub fn create_party(
+        ctx: Context<CreateParty>,
+        counter: u16,
+        xp_rule: u8,
+        loot_rule: u8,
+    ) -> Result<()> {
+        create_party::create_party(ctx, counter, xp_rule, loot_rule)
+    }
+
+    // Only the party leader can invite.
+    #[session_auth_or(
+        ctx.accounts.player.authority.key() == ctx.accounts.signer.key(),
+        GameErrorCode::WrongAuthority
+    )]
+    pub fn invite(ctx: Context<Invite>, counter: u16, invitee: Pubkey) -> Result<()> {
+        invite::invite(ctx, counter, invitee)
+    }
+
+    #[session_auth_or(
+        ctx.accounts.player.authority.key() == ctx.accounts.signer.key(),
+        GameErrorCode::WrongAuthority
+    )]
+    pub fn join_party(ctx: Context<JoinParty>, counter: u16) -> Result<()> {
+        join_party::join_party(ctx, counter)
+    }
+
+    #[session_auth_or(
+        ctx.accounts.player.authority.key() == ctx.accounts.signer.key(),
+        GameErrorCode::WrongAuthority
+    )]
+    pub fn leave_party(ctx: Context<LeaveParty>, counter: u16) -> Result<()> {
+        leave_party::leave_party(ctx, counter)
+    }
+
+    // Collects the xp and loot other party members shared with the player.
+    #[session_auth_or(
+        ctx.accounts.player.authority.key() == ctx.accounts.signer.key(),
+        GameErrorCode::WrongAuthority
+    )]
+    pub fn claim_party_share(ctx: Context<ClaimPartyShare>, counter: u16) -> Result<()> {
+        claim_party_share::claim_party_share(ctx, counter)
+    }
+
+    // Base uri of the item NFT metadata, the mint is appended.
+    pub fn set_item_uri(ctx: Context<SetItemUri>, item_uri: String) -> Result<()> {
+        set_item_uri::set_item_uri(ctx, item_uri)
+    }
+
+    // Adds or replaces a crafting recipe, see crafting.rs.
+    pub fn set_recipe(ctx: Context<SetRecipe>, index: u8, recipe: Recipe) -> Result<()> {
+        set_recipe::set_recipe(ctx, index, recipe)
+    }
+
+    // Mints a gear item of the inventory as an NFT, see item.rs.
+    pub fn withdraw_item(ctx: Context<WithdrawItem>, index: u8) -> Result<()> {
+        withdraw_item::withdraw_item(ctx, index)
+    }
+
+    // Burns an item NFT and puts it back into the inventory.
+    pub fn deposit_item(ctx: Context<DepositItem>) -> Result<()> {
+        deposit_item::deposit_item(ctx)
+    }
+
+    // Trades between players, see trade.rs. The offered items and gold wait
+    // in the offer PDA until it is accepted or cancelled.
+    pub fn create_offer(ctx: Context<CreateOffer>, id: u32, args: OfferArgs) -> Result<()> {
+        create_offer::create_offer(ctx, id, args)
+    }
+
+    pub fn accept_offer(ctx: Context<AcceptOffer>) -> Result<()> {
+        accept_offer::accept_offer(ctx)
+    }
+
+    pub fn cancel_offer(ctx: Context<CancelOffer>) -> Result<()> {
+        cancel_offer::cancel_offer(ctx)
+    }
+
+    // The floor is only checked for the merchant shop, pass any address for
+    // the lobby shop.
+    pub fn buy_item(
+        ctx: Context<BuyItem>,
+        floor: FloorAddress,
+        kind: u8,
+        item_id: u16,
+        amount: u32,
+    ) -> Result<()> {
+        buy_item::buy_item(ctx, floor, kind, item_id, amount)
+    }
+
+    pub fn sell_item(
+        ctx: Context<SellItem>,
+        floor: FloorAddress,
+        kind: u8,
+        item_id: u16,
+        amount: u32,
+    ) -> Result<()> {
+        sell_item::sell_item(ctx, floor, kind, item_id, amount)
+    }
+
+    // Guilds have a gold treasury and a ranking per season, see guild.rs.
+    // They are managed by the wallet, not by session keys.
+    pub fn create_guild(ctx: Context<CreateGuild>, name: String) -> Result<()> {
+        create_guild::create_guild(ctx, name)
+    }
+
+    // Officers and the leader can invite.
+    pub fn invite_to_guild(ctx: Context<InviteToGuild>, invitee: Pubkey) -> Result<()> {
+        invite_to_guild::invite_to_guild(ctx, invitee)
+    }
+
+    pub fn join_guild(ctx: Context<JoinGuild>) -> Result<()> {
+        join_guild::join_guild(ctx)
+    }
+
+    pub fn leave_guild(ctx: Context<LeaveGuild>) -> Result<()> {
+        leave_guild::leave_guild(ctx)
+    }
+
+    // Only the leader can change roles. Passing GUILD_ROLE_LEADER hands over
+    // the guild.
+    pub fn set_guild_role(ctx: Context<SetGuildRole>, member: Pubkey, role: u8) -> Result<()> {
+        set_guild_role::set_guild_role(ctx, member, role)
+    }
+
+    pub fn contribute_gold(ctx: Context<ContributeGold>, amount: u64) -> Result<()> {
+        contribute_gold::contribute_gold(ctx, amount)
+    }
+
+    // Sends gold from the guild treasury. Only the leader can do this.
+    pub fn spend_guild_treasury(ctx: Context<SpendGuildTreasury>, amount: u64) -> Result<()> {
+        spend_guild_treasury::spend_guild_treasury(ctx, amount)
+    }
+
+    // Pushes the season stats of a member to the guild and the guild
+    // leaderboard.
+    pub fn update_guild_ranking(ctx: Context<UpdateGuildRanking>) -> Result<()> {
+        update_guild_ranking::update_guild_ranking(ctx)
     }
 }
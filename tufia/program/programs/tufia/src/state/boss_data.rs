@@ -0,0 +1,151 @@
+use crate::{constants::*, GameErrorCode};
+use anchor_lang::prelude::*;
+
+// One BossData account per boss floor. It outlives single transactions so the
+// fight can be spread over many attack_boss instructions and many players.
+#[account]
+pub struct BossData {
+    pub floor_id: u32,
+    pub health: u32,
+    pub max_health: u32,
+    pub damage: u32,
+    pub defence: u32,
+    pub reward_xp: u32,
+    pub total_damage: u64,
+    pub contributors: Vec<BossContributor>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct BossContributor {
+    pub player: Pubkey,
+    pub damage: u32,
+    pub claimed: bool,
+}
+
+impl BossData {
+    pub fn spawn(&mut self, floor_id: u32) {
+        self.floor_id = floor_id;
+        self.max_health = Self::health_for_floor(floor_id);
+        self.health = self.max_health;
+        self.damage = Self::damage_for_floor(floor_id);
+        self.defence = Self::defence_for_floor(floor_id);
+        self.reward_xp = (floor_id + 1) * BOSS_XP_MULTIPLIER;
+        msg!("Boss spawned on floor {}", floor_id);
+    }
+
+    pub fn health_for_floor(floor_id: u32) -> u32 {
+        (floor_id + 5) * BOSS_HEALTH_MULTIPLIER
+    }
+
+    pub fn damage_for_floor(floor_id: u32) -> u32 {
+        floor_id + 2
+    }
+
+    pub fn defence_for_floor(floor_id: u32) -> u32 {
+        floor_id + 1
+    }
+
+    // Only the MAX_BOSS_CONTRIBUTORS biggest contributors share the reward. A
+    // new attacker takes the place of the smallest one with a bigger hit, the
+    // damage of the one pushed out no longer counts for the shares.
+    pub fn add_damage(&mut self, player: Pubkey, damage: u32) {
+        if let Some(contributor) = self.contributors.iter_mut().find(|c| c.player == player) {
+            contributor.damage += damage;
+            self.total_damage += damage as u64;
+            return;
+        }
+
+        let new_contributor = BossContributor {
+            player,
+            damage,
+            claimed: false,
+        };
+        if self.contributors.len() < MAX_BOSS_CONTRIBUTORS {
+            self.contributors.push(new_contributor);
+            self.total_damage += damage as u64;
+            return;
+        }
+
+        let Some(smallest) = self.contributors.iter_mut().min_by_key(|c| c.damage) else {
+            return;
+        };
+        if damage <= smallest.damage {
+            msg!("Hit too small for a share of the boss reward");
+            return;
+        }
+        self.total_damage = self.total_damage - smallest.damage as u64 + damage as u64;
+        *smallest = new_contributor;
+    }
+
+    // Returns the xp share of the player, split by the damage everyone dealt.
+    pub fn claim_reward(&mut self, player: Pubkey) -> Result<u32> {
+        if self.health > 0 {
+            return Err(GameErrorCode::BossNotDefeated.into());
+        }
+
+        let total_damage = self.total_damage;
+        let reward_xp = self.reward_xp;
+        let contributor = self
+            .contributors
+            .iter_mut()
+            .find(|c| c.player == player)
+            .ok_or(GameErrorCode::NoBossDamageDealt)?;
+
+        if contributor.claimed {
+            return Err(GameErrorCode::BossRewardAlreadyClaimed.into());
+        }
+        contributor.claimed = true;
+
+        let share = reward_xp as u64 * contributor.damage as u64 / total_damage;
+        Ok(share as u32)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn boss() -> BossData {
+        BossData {
+            floor_id: 4,
+            health: 0,
+            max_health: 100,
+            damage: 1,
+            defence: 1,
+            reward_xp: 1000,
+            total_damage: 0,
+            contributors: Vec::new(),
+        }
+    }
+
+    // Attacks never fail because too many players hit the boss. Bigger hits
+    // push the smallest contributor out, smaller ones earn no share.
+    #[test]
+    fn full_contributor_list() {
+        let mut boss = boss();
+        let players: Vec<Pubkey> = (0..MAX_BOSS_CONTRIBUTORS)
+            .map(|_| Pubkey::new_unique())
+            .collect();
+        for (i, player) in players.iter().enumerate() {
+            boss.add_damage(*player, 10 + i as u32);
+        }
+
+        let small = Pubkey::new_unique();
+        boss.add_damage(small, 5);
+        assert_eq!(boss.contributors.len(), MAX_BOSS_CONTRIBUTORS);
+        assert!(boss.claim_reward(small).is_err());
+
+        let big = Pubkey::new_unique();
+        boss.add_damage(big, 50);
+        assert!(boss.claim_reward(players[0]).is_err());
+        let total: u64 = boss.contributors.iter().map(|c| c.damage as u64).sum();
+        assert_eq!(boss.total_damage, total);
+
+        // The shares of everyone left add up to the whole reward at most
+        let mut xp = boss.claim_reward(big).unwrap();
+        for player in &players[1..] {
+            xp += boss.claim_reward(*player).unwrap();
+        }
+        assert!(xp <= boss.reward_xp && xp > boss.reward_xp - MAX_BOSS_CONTRIBUTORS as u32);
+    }
+}
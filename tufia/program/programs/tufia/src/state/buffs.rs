@@ -0,0 +1,140 @@
+use crate::{constants::*, GameErrorCode};
+use anchor_lang::prelude::*;
+
+use super::{
+    game_data::{TileData, TileData2},
+    player_data::PlayerData,
+};
+
+// Like the shrine blessing the bonus of a buff is added to the player tile
+// while it lasts and taken away again when it runs out.
+pub const BUFF_STRENGTH: u8 = 0; // damage
+pub const BUFF_IRON_SKIN: u8 = 1; // defence
+
+// A buff runs out at a unix time or after a number of moves, 0 means not
+// limited by it.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct Buff {
+    pub kind: u8,
+    pub amount: u32,
+    pub expires_at: i64,
+    pub moves_left: u16,
+}
+
+impl Buff {
+    fn expired(&self, now: i64) -> bool {
+        self.expires_at != 0 && now >= self.expires_at
+    }
+}
+
+pub fn apply_buff(tile: &mut TileData, buff: &Buff, active: bool) {
+    let amount = buff.amount;
+    match (buff.kind, active) {
+        (BUFF_STRENGTH, true) => tile.tile_damage += amount,
+        (BUFF_STRENGTH, false) => tile.tile_damage = tile.tile_damage.saturating_sub(amount),
+        (BUFF_IRON_SKIN, true) => tile.tile_defence += amount,
+        (BUFF_IRON_SKIN, false) => tile.tile_defence = tile.tile_defence.saturating_sub(amount),
+        // Unknown kinds do nothing, so they can not be mistaken for another
+        _ => {}
+    }
+}
+
+// The same for the tile that is carried to the next floor
+fn remove_buff(tile: &mut TileData2, buff: &Buff) {
+    match buff.kind {
+        BUFF_STRENGTH => tile.tile_damage = tile.tile_damage.saturating_sub(buff.amount),
+        BUFF_IRON_SKIN => tile.tile_defence = tile.tile_defence.saturating_sub(buff.amount),
+        _ => {}
+    }
+}
+
+impl PlayerData {
+    pub fn add_buff(&mut self, buff: Buff) -> Result<()> {
+        if self.buffs.len() >= MAX_BUFFS {
+            return Err(GameErrorCode::TooManyBuffs.into());
+        }
+        self.buffs.push(buff);
+        Ok(())
+    }
+
+    // Removes the buffs that ran out by time and returns them
+    pub fn take_expired_buffs(&mut self, now: i64) -> Vec<Buff> {
+        let (expired, active) = self.buffs.iter().partition(|buff| buff.expired(now));
+        self.buffs = active;
+        expired
+    }
+
+    // Counts down the buffs that last a number of moves and returns the ones
+    // that ran out
+    pub fn tick_buffs(&mut self) -> Vec<Buff> {
+        let mut expired = Vec::new();
+        self.buffs.retain_mut(|buff| {
+            if buff.moves_left == 0 {
+                return true;
+            }
+            buff.moves_left -= 1;
+            if buff.moves_left == 0 {
+                expired.push(*buff);
+                return false;
+            }
+            true
+        });
+        expired
+    }
+
+    // Housekeeping for players that are not on a board, like update_energy.
+    // Has to run before the tile is copied to a new floor.
+    pub fn update_buffs(&mut self) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        for buff in self.take_expired_buffs(now) {
+            remove_buff(&mut self.tile_data, &buff);
+        }
+        Ok(())
+    }
+
+    // Dead players lose their buffs
+    pub fn clear_buffs(&mut self) {
+        for buff in std::mem::take(&mut self.buffs) {
+            remove_buff(&mut self.tile_data, &buff);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::game_data::tests::sample_tile;
+
+    // Every kind changes its own stat, unknown kinds leave the tile alone
+    #[test]
+    fn buffs_change_their_own_stat() {
+        let base = sample_tile();
+        let mut tile = base;
+        let strength = Buff {
+            kind: BUFF_STRENGTH,
+            amount: 3,
+            ..Default::default()
+        };
+        let iron_skin = Buff {
+            kind: BUFF_IRON_SKIN,
+            amount: 2,
+            ..Default::default()
+        };
+        let unknown = Buff {
+            kind: 99,
+            amount: 5,
+            ..Default::default()
+        };
+
+        apply_buff(&mut tile, &strength, true);
+        apply_buff(&mut tile, &iron_skin, true);
+        apply_buff(&mut tile, &unknown, true);
+        assert_eq!({ tile.tile_damage }, 7);
+        assert_eq!({ tile.tile_defence }, 7);
+
+        apply_buff(&mut tile, &unknown, false);
+        apply_buff(&mut tile, &iron_skin, false);
+        apply_buff(&mut tile, &strength, false);
+        assert_eq!(tile, base);
+    }
+}
@@ -0,0 +1,203 @@
+use crate::{constants::*, GameErrorCode};
+use anchor_lang::prelude::*;
+
+use super::{
+    game_data::XorShift64,
+    inventory::{InventoryItem, ITEM_CRYSTAL},
+    item::GearItem,
+    player_data::PlayerData,
+};
+
+// Crafts the result gear out of the materials. Recipes are set by the admin in
+// the GameConfig account.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct Recipe {
+    pub materials: Vec<InventoryItem>,
+    pub result: GearItem,
+}
+
+pub fn validate_recipe(recipe: &Recipe) -> Result<()> {
+    if recipe.materials.is_empty()
+        || recipe.materials.len() > MAX_RECIPE_MATERIALS
+        || recipe.materials.iter().any(|material| material.amount == 0)
+    {
+        return Err(GameErrorCode::InvalidRecipe.into());
+    }
+    Ok(())
+}
+
+// Every tier costs one crystal more and is less likely to work out. The
+// crystals are gone either way.
+pub fn upgrade_cost(tier: u8) -> u32 {
+    tier as u32 + 1
+}
+
+pub fn upgrade_chance(tier: u8) -> u64 {
+    UPGRADE_BASE_CHANCE
+        .saturating_sub(tier as u64 * UPGRADE_CHANCE_STEP)
+        .max(UPGRADE_MIN_CHANCE)
+}
+
+// Each tier makes every stat of the gear grow by a fifth, at least by one
+fn upgrade_stat(stat: u32) -> u32 {
+    if stat == 0 {
+        0
+    } else {
+        stat + stat / 5 + 1
+    }
+}
+
+impl PlayerData {
+    pub fn craft(&mut self, recipe: &Recipe) -> Result<()> {
+        for material in &recipe.materials {
+            self.remove_item(material.item_id, material.amount)?;
+        }
+        self.add_gear(GearItem {
+            floor: self.current_floor,
+            ..recipe.result
+        })
+    }
+
+    // Returns if the upgrade worked
+    pub fn upgrade_gear(&mut self, index: u8) -> Result<bool> {
+        let item = *self
+            .gear
+            .get(index as usize)
+            .ok_or(GameErrorCode::InvalidItem)?;
+        // Equipped stats are already on the player, take it off first
+        if item.equipped {
+            return Err(GameErrorCode::ItemEquipped.into());
+        }
+        if item.tier >= MAX_GEAR_TIER {
+            return Err(GameErrorCode::MaxTierReached.into());
+        }
+
+        self.remove_item(ITEM_CRYSTAL, upgrade_cost(item.tier))?;
+
+        if XorShift64::for_player(self)?.next() % 100 >= upgrade_chance(item.tier) {
+            return Ok(false);
+        }
+
+        let gear = &mut self.gear[index as usize];
+        gear.tier += 1;
+        gear.damage = upgrade_stat(gear.damage);
+        gear.defence = upgrade_stat(gear.defence);
+        gear.armor = upgrade_stat(gear.armor);
+        Ok(true)
+    }
+
+    // Enemies drop scrap, chests crystals. Drops are lost when the inventory
+    // is full.
+    pub fn drop_material(&mut self, item_id: u16, chance: u64) -> Result<()> {
+        if XorShift64::for_player(self)?.next() % 100 >= chance {
+            return Ok(());
+        }
+        match self.add_item(item_id, 1) {
+            Ok(()) => msg!("Found material {}", item_id),
+            Err(_) => msg!("Found material {} but the inventory is full", item_id),
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::inventory::ITEM_SCRAP;
+
+    fn recipe() -> Recipe {
+        Recipe {
+            materials: vec![
+                InventoryItem {
+                    item_id: ITEM_SCRAP,
+                    amount: 3,
+                },
+                InventoryItem {
+                    item_id: ITEM_CRYSTAL,
+                    amount: 1,
+                },
+            ],
+            result: GearItem {
+                damage: 2,
+                ..Default::default()
+            },
+        }
+    }
+
+    #[test]
+    fn recipes_need_materials() {
+        assert!(validate_recipe(&recipe()).is_ok());
+        assert!(validate_recipe(&Recipe::default()).is_err());
+
+        let mut free = recipe();
+        free.materials[0].amount = 0;
+        assert!(validate_recipe(&free).is_err());
+
+        let mut large = recipe();
+        large.materials = vec![large.materials[0]; MAX_RECIPE_MATERIALS + 1];
+        assert!(validate_recipe(&large).is_err());
+    }
+
+    // Crafting uses up the materials, the gear remembers the floor
+    #[test]
+    fn craft_uses_the_materials() {
+        let mut poor = PlayerData::default();
+        poor.add_item(ITEM_SCRAP, 3).unwrap();
+        assert!(poor.craft(&recipe()).is_err());
+
+        let mut player = PlayerData {
+            current_floor: 4,
+            ..Default::default()
+        };
+        player.add_item(ITEM_SCRAP, 3).unwrap();
+        player.add_item(ITEM_CRYSTAL, 1).unwrap();
+        player.craft(&recipe()).unwrap();
+        assert_eq!(player.item_count(ITEM_SCRAP), 0);
+        assert_eq!(player.item_count(ITEM_CRYSTAL), 0);
+        assert_eq!(player.gear.len(), 1);
+        assert_eq!(player.gear[0].damage, 2);
+        assert_eq!(player.gear[0].floor, 4);
+    }
+
+    // Higher tiers cost more and fail more often, but never always
+    #[test]
+    fn upgrades_get_harder() {
+        assert_eq!(upgrade_cost(0), 1);
+        assert_eq!(upgrade_cost(3), 4);
+        assert_eq!(upgrade_chance(0), UPGRADE_BASE_CHANCE);
+        assert!(upgrade_chance(3) < upgrade_chance(2));
+        assert_eq!(upgrade_chance(MAX_GEAR_TIER), UPGRADE_MIN_CHANCE);
+    }
+
+    #[test]
+    fn stats_grow_by_a_fifth() {
+        assert_eq!(upgrade_stat(0), 0);
+        assert_eq!(upgrade_stat(1), 2);
+        assert_eq!(upgrade_stat(10), 13);
+    }
+
+    // Nothing is rolled and no crystal is spent for gear that can not be
+    // upgraded
+    #[test]
+    fn upgrade_checks_come_first() {
+        let mut player = PlayerData::default();
+        player.add_item(ITEM_CRYSTAL, 20).unwrap();
+        player.add_gear(GearItem::default()).unwrap();
+        player
+            .add_gear(GearItem {
+                tier: MAX_GEAR_TIER,
+                ..Default::default()
+            })
+            .unwrap();
+
+        player.gear[0].equipped = true;
+        assert!(player.upgrade_gear(0).is_err());
+        assert!(player.upgrade_gear(1).is_err());
+        assert!(player.upgrade_gear(2).is_err());
+        assert_eq!(player.item_count(ITEM_CRYSTAL), 20);
+
+        let mut poor = PlayerData::default();
+        poor.add_gear(GearItem::default()).unwrap();
+        assert!(poor.upgrade_gear(0).is_err());
+    }
+}
@@ -0,0 +1,257 @@
+use std::cmp::Ordering;
+
+use crate::{constants::*, GameErrorCode};
+use anchor_lang::prelude::*;
+
+use super::game_data::GameData;
+
+// One attempt per player and day. The account is created by start_daily_run,
+// so a second start on the same day fails.
+#[account]
+pub struct DailyRun {
+    pub player: Pubkey,
+    pub day: u32,
+    pub depth: u16,
+    pub turns: u32,
+    pub damage_taken: u32,
+    pub finished: bool,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct DailyEntry {
+    pub player: Pubkey,
+    pub depth: u16,
+    pub turns: u32,
+    pub damage_taken: u32,
+    pub reached_at: u64, // slot of the last submit
+    pub claimed: bool,
+}
+
+// Deeper is better, then fewer turns, then less damage taken. Remaining ties
+// go to the player who got there first.
+#[account]
+pub struct DailyLeaderboard {
+    pub day: u32,
+    pub entries: Vec<DailyEntry>,
+}
+
+pub fn current_day() -> Result<u32> {
+    Ok((Clock::get()?.unix_timestamp / SECONDS_PER_DAY) as u32)
+}
+
+pub fn check_today(day: u32) -> Result<()> {
+    if day != current_day()? {
+        return Err(GameErrorCode::WrongDay.into());
+    }
+    Ok(())
+}
+
+// The seed of a daily floor only depends on the day and the depth
+pub fn daily_seed(day: u32, depth: u16) -> u64 {
+    (DAILY_DUNGEON_SEED ^ ((day as u64) << 16) ^ depth as u64) | 1
+}
+
+pub fn compare_entries(a: &DailyEntry, b: &DailyEntry) -> Ordering {
+    b.depth
+        .cmp(&a.depth)
+        .then(a.turns.cmp(&b.turns))
+        .then(a.damage_taken.cmp(&b.damage_taken))
+        .then(a.reached_at.cmp(&b.reached_at))
+        .then(a.player.to_bytes().cmp(&b.player.to_bytes()))
+}
+
+impl DailyRun {
+    pub fn check_active(&self) -> Result<()> {
+        if self.finished {
+            return Err(GameErrorCode::DailyRunFinished.into());
+        }
+        Ok(())
+    }
+
+    // Counts the turn and the health lost during it. The run is over when the
+    // player is no longer on the board.
+    pub fn end_turn(&mut self, game_data: &GameData, health_before: u32) {
+        self.turns += 1;
+
+        let health_after = match game_data.find_player_position(self.player) {
+            Some((x, y)) => game_data.data[x][y].tile_health,
+            None => {
+                self.finished = true;
+                msg!("Daily run over at depth {}", self.depth);
+                0
+            }
+        };
+        self.damage_taken += health_before.saturating_sub(health_after);
+    }
+}
+
+pub fn player_health(game_data: &GameData, player: Pubkey) -> Result<u32> {
+    let (x, y) = game_data
+        .find_player_position(player)
+        .ok_or(GameErrorCode::PlayerNotOnBoard)?;
+    Ok(game_data.data[x][y].tile_health)
+}
+
+impl DailyLeaderboard {
+    pub fn submit(&mut self, run: &DailyRun) -> Result<()> {
+        let entry = DailyEntry {
+            player: run.player,
+            depth: run.depth,
+            turns: run.turns,
+            damage_taken: run.damage_taken,
+            reached_at: Clock::get()?.slot,
+            claimed: false,
+        };
+
+        if let Some(existing) = self
+            .entries
+            .iter_mut()
+            .find(|existing| existing.player == run.player)
+        {
+            *existing = entry;
+        } else if self.entries.len() < DAILY_LEADERBOARD_SIZE {
+            self.entries.push(entry);
+        } else {
+            let last = self.entries.len() - 1;
+            if compare_entries(&entry, &self.entries[last]) != Ordering::Less {
+                return Ok(());
+            }
+            self.entries[last] = entry;
+        }
+
+        self.entries.sort_by(compare_entries);
+        Ok(())
+    }
+
+    // Returns the rank of the player and the gold they get
+    pub fn claim_reward(&mut self, player: Pubkey) -> Result<(u8, u64)> {
+        if self.day >= current_day()? {
+            return Err(GameErrorCode::DailyNotOver.into());
+        }
+
+        let rank = self
+            .entries
+            .iter()
+            .position(|entry| entry.player == player)
+            .filter(|rank| *rank < DAILY_REWARD_RANKS)
+            .ok_or(GameErrorCode::NoDailyReward)?;
+
+        let entry = &mut self.entries[rank];
+        if entry.claimed {
+            return Err(GameErrorCode::DailyRewardAlreadyClaimed.into());
+        }
+        entry.claimed = true;
+
+        let gold =
+            DAILY_REWARD_GOLD * (DAILY_REWARD_RANKS - rank) as u64 / DAILY_REWARD_RANKS as u64;
+        Ok((rank as u8, gold))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::{player_class::CLASS_WARRIOR, player_data::PlayerData};
+
+    fn player() -> PlayerData {
+        PlayerData {
+            authority: Pubkey::new_unique(),
+            class: CLASS_WARRIOR,
+            ..Default::default()
+        }
+    }
+
+    fn layout(game_data: &GameData) -> Vec<u8> {
+        game_data
+            .data
+            .iter()
+            .flat_map(|row| row.iter().map(|tile| tile.tile_type))
+            .collect()
+    }
+
+    // The seed changes with the day and the depth and is never 0, which
+    // would stop the xorshift generator
+    #[test]
+    fn seed_depends_on_day_and_depth() {
+        assert_eq!(daily_seed(100, 1), daily_seed(100, 1));
+        assert_ne!(daily_seed(100, 1), daily_seed(101, 1));
+        assert_ne!(daily_seed(100, 1), daily_seed(100, 2));
+        assert_ne!(daily_seed(0, 0), 0);
+    }
+
+    // Everyone gets the same floor on the same day
+    #[test]
+    fn same_seed_same_floor() {
+        let seed = daily_seed(100, 1);
+        let mut first = GameData::default();
+        first.generate_daily_floor(&player(), 1, seed).unwrap();
+        let mut second = GameData::default();
+        second.generate_daily_floor(&player(), 1, seed).unwrap();
+        assert_eq!(layout(&first), layout(&second));
+
+        let mut other_day = GameData::default();
+        other_day
+            .generate_daily_floor(&player(), 1, daily_seed(101, 1))
+            .unwrap();
+        assert_ne!(layout(&first), layout(&other_day));
+    }
+
+    #[test]
+    fn deeper_then_faster_then_healthier() {
+        let entry = DailyEntry {
+            depth: 3,
+            turns: 10,
+            damage_taken: 5,
+            ..Default::default()
+        };
+        let deeper = DailyEntry { depth: 4, ..entry };
+        let faster = DailyEntry { turns: 9, ..entry };
+        let healthier = DailyEntry {
+            damage_taken: 4,
+            ..entry
+        };
+        let earlier = DailyEntry {
+            reached_at: 1,
+            ..entry
+        };
+        let later = DailyEntry {
+            reached_at: 2,
+            ..entry
+        };
+
+        assert_eq!(compare_entries(&deeper, &entry), Ordering::Less);
+        assert_eq!(compare_entries(&faster, &entry), Ordering::Less);
+        assert_eq!(compare_entries(&healthier, &entry), Ordering::Less);
+        assert_eq!(compare_entries(&earlier, &later), Ordering::Less);
+    }
+
+    // Turns count the health lost, leaving the board ends the run
+    #[test]
+    fn end_turn_tracks_damage_until_the_player_is_gone() {
+        let player = player();
+        let mut game_data = GameData::default();
+        game_data
+            .generate_daily_floor(&player, 1, daily_seed(100, 1))
+            .unwrap();
+        let mut run = DailyRun {
+            player: player.authority,
+            day: 100,
+            depth: 1,
+            turns: 0,
+            damage_taken: 0,
+            finished: false,
+        };
+
+        let health = player_health(&game_data, player.authority).unwrap();
+        run.end_turn(&game_data, health + 3);
+        assert_eq!(run.turns, 1);
+        assert_eq!(run.damage_taken, 3);
+        assert!(run.check_active().is_ok());
+
+        game_data.remove_player(player.authority).unwrap();
+        run.end_turn(&game_data, health);
+        assert_eq!(run.turns, 2);
+        assert_eq!(run.damage_taken, 3 + health);
+        assert!(run.check_active().is_err());
+    }
+}
@@ -0,0 +1,85 @@
+use crate::{constants::*, GameErrorCode};
+use anchor_lang::prelude::*;
+
+use super::player_data::PlayerData;
+
+// Floors are addressed by season, floor id and instance instead of a free
+// form string. The PDA seeds are
+// [b"floor", season, floor_id, instance] with all numbers little endian.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct FloorAddress {
+    pub floor_id: u32,
+    pub instance: u16,
+}
+
+impl FloorAddress {
+    // Players can only act on the floor they are on
+    pub fn check_player_floor(&self, current_floor: u16) -> Result<()> {
+        if self.floor_id != current_floor as u32 {
+            return Err(GameErrorCode::WrongFloor.into());
+        }
+        Ok(())
+    }
+
+    // Instructions on a floor have to be sent for the instance the player
+    // joined with buy_next_floor
+    pub fn check_player_instance(&self, player: &PlayerData) -> Result<()> {
+        self.check_player_floor(player.current_floor)?;
+        if self.instance != player.current_instance {
+            return Err(GameErrorCode::WrongInstance.into());
+        }
+        Ok(())
+    }
+}
+
+// One directory per floor and season. It keeps the number of players on
+// every instance, the index in the list is the instance id.
+#[account]
+pub struct FloorDirectory {
+    pub season: u16,
+    pub floor_id: u32,
+    pub instances: Vec<u8>,
+}
+
+impl FloorDirectory {
+    // Players go to the first instance with space for them and their party.
+    // Returns true if the instance is new and still has to be created.
+    pub fn route(&mut self, instance: u16, players: usize) -> Result<bool> {
+        let expected = self
+            .instances
+            .iter()
+            .position(|count| *count as usize + players <= MAX_PLAYERS_PER_INSTANCE)
+            .unwrap_or(self.instances.len());
+        if instance as usize != expected {
+            return Err(GameErrorCode::WrongInstance.into());
+        }
+
+        if expected < self.instances.len() {
+            return Ok(false);
+        }
+        if self.instances.len() >= MAX_FLOOR_INSTANCES {
+            return Err(GameErrorCode::TooManyInstances.into());
+        }
+        self.instances.push(0);
+        Ok(true)
+    }
+
+    // Party members follow the first member to their instance
+    pub fn route_to(&mut self, instance: u16, party_instance: u16) -> Result<bool> {
+        if instance != party_instance {
+            return Err(GameErrorCode::WrongInstance.into());
+        }
+        if instance as usize >= self.instances.len() {
+            return self.route(instance, 1);
+        }
+        Ok(false)
+    }
+
+    // Counts are taken from the board, so players who died or left without
+    // the directory are corrected the next time the instance is touched.
+    pub fn update(&mut self, instance: u16, players: usize) {
+        if let Some(count) = self.instances.get_mut(instance as usize) {
+            *count = players as u8;
+        }
+    }
+}
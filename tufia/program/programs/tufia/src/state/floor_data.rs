@@ -0,0 +1,455 @@
+use crate::{constants::*, GameErrorCode};
+use anchor_lang::prelude::*;
+
+use super::game_data::{
+    GameAction, GameData, TileData, BOARD_SIZE_X, BOARD_SIZE_Y, GAME_ACTIONS, STATE_EMPTY,
+    STATE_PLAYER,
+};
+
+// Tiles point into the player table with the slot + 1, 0 is no owner
+const NO_ENTITY: u8 = 0;
+
+// The players on the floor, the player who spawned the enemies and chests of
+// the instance and the owners of the tiles in the action log. Owners keep
+// their slot after they left the floor as long as a tile still points to them.
+pub const PLAYER_TABLE_SIZE: usize = MAX_PLAYERS_PER_INSTANCE + 1 + GAME_ACTIONS;
+
+// The floor account. Instead of a full TileData with a Pubkey on every tile
+// the owners of the tiles are kept once in a small table and tiles only store
+// the index into it. The level is stored as u16, stats that keep growing with
+// levels, gear and buffs stay u32. The game logic works on the unpacked
+// GameData:
+//
+//     let floor_data = &mut account.game_data.load_mut()?;
+//     let game_data = &mut floor_data.board();
+//     ...
+//     floor_data.save(game_data)?;
+#[account(zero_copy(unsafe))]
+#[repr(C, packed)]
+#[derive(Default)]
+pub struct FloorData {
+    pub id_counter: u64,
+    pub action_index: u8,
+    pub total_wood_collected: u64,
+    pub floor_id: u32,
+    pub owner: Pubkey,
+    pub mode: u8,
+    pub players: [PlayerSlot; PLAYER_TABLE_SIZE],
+    pub tiles: [[PackedTile; BOARD_SIZE_X]; BOARD_SIZE_Y],
+    pub actions: [PackedAction; GAME_ACTIONS],
+}
+
+// The name and the avatar belong to the player, not to the tile
+#[zero_copy(unsafe)]
+#[repr(C, packed)]
+#[derive(Default)]
+pub struct PlayerSlot {
+    pub player: Pubkey,
+    pub avatar: u8,
+    pub name: [u8; TILE_NAME_LENGTH],
+}
+
+#[zero_copy(unsafe)]
+#[repr(C, packed)]
+#[derive(Default, PartialEq, Debug)]
+pub struct PackedTile {
+    pub tile_type: u8,
+    pub entity: u8, // slot + 1 in the player table
+    pub level: u16,
+    pub xp: u32,
+    pub damage: u32,
+    pub defence: u32,
+    pub armor: u32,
+    pub max_armor: u32,
+    pub health: u32,
+    pub max_health: u32,
+    pub crit_chance: u8,
+    pub crit_multiplier: u8,
+    pub evasion: u8,
+    pub damage_range: u8,
+    pub class: u8,
+    pub status: u8,
+    pub status_turns: u8,
+}
+
+// Positions are packed as x << 4 | y, the board is smaller than 16x16. The
+// tile is the packed tile the action happened on, clients show its stats.
+#[zero_copy(unsafe)]
+#[repr(C, packed)]
+#[derive(Default, PartialEq, Debug)]
+pub struct PackedAction {
+    pub action_id: u64,
+    pub action_type: u8,
+    pub from: u8,
+    pub to: u8,
+    pub tile: PackedTile,
+    pub amount: u64,
+}
+
+impl FloorData {
+    pub const SPACE: usize = 8 + std::mem::size_of::<FloorData>();
+
+    // Unpacks the account into the board the game logic works on
+    pub fn board(&self) -> GameData {
+        log_compute_units();
+        let mut game_data = GameData {
+            id_counter: self.id_counter,
+            action_index: self.action_index as u64,
+            total_wood_collected: self.total_wood_collected,
+            floor_id: self.floor_id,
+            owner: self.owner,
+            mode: self.mode,
+            ..Default::default()
+        };
+
+        for i in 0..BOARD_SIZE_X {
+            for j in 0..BOARD_SIZE_Y {
+                game_data.data[i][j] = self.unpack_tile(&self.tiles[i][j]);
+            }
+        }
+        for (index, action) in self.actions.iter().enumerate() {
+            game_data.game_actions[index] = self.unpack_action(action);
+        }
+
+        log_compute_units();
+        game_data
+    }
+
+    // Packs the board back into the account. The player table is built again
+    // every time so owners no tile points to anymore free their slot.
+    pub fn save(&mut self, game_data: &GameData) -> Result<()> {
+        log_compute_units();
+        self.id_counter = game_data.id_counter;
+        self.action_index = game_data.action_index as u8;
+        self.total_wood_collected = game_data.total_wood_collected;
+        self.floor_id = game_data.floor_id;
+        self.owner = game_data.owner;
+        self.mode = game_data.mode;
+
+        self.players = [PlayerSlot::default(); PLAYER_TABLE_SIZE];
+        // Players on the board come first, their tile has the current name
+        let mut used = 0;
+        for tile in game_data.data.iter().flatten() {
+            if tile.tile_type != STATE_PLAYER {
+                continue;
+            }
+            if used == MAX_PLAYERS_PER_INSTANCE {
+                return Err(GameErrorCode::InstanceFull.into());
+            }
+            self.players[used] = PlayerSlot {
+                player: tile.tile_owner,
+                avatar: tile.tile_avatar,
+                name: tile.tile_name,
+            };
+            used += 1;
+        }
+        // Enemies and chests keep the player that spawned them, actions the
+        // owner of their tile, even when that player left the floor. Empty
+        // tiles have no owner, whatever stood there before.
+        let board_tiles = game_data
+            .data
+            .iter()
+            .flatten()
+            .filter(|tile| tile.tile_type != STATE_EMPTY);
+        let action_tiles = game_data.game_actions.iter().map(|action| &action.tile);
+        for tile in board_tiles.chain(action_tiles) {
+            if tile.tile_owner == Pubkey::default() || self.find_slot(tile.tile_owner).is_some() {
+                continue;
+            }
+            if used == PLAYER_TABLE_SIZE {
+                return Err(GameErrorCode::InstanceFull.into());
+            }
+            self.players[used] = PlayerSlot {
+                player: tile.tile_owner,
+                ..Default::default()
+            };
+            if tile.tile_type == STATE_PLAYER {
+                self.players[used].avatar = tile.tile_avatar;
+                self.players[used].name = tile.tile_name;
+            }
+            used += 1;
+        }
+
+        for i in 0..BOARD_SIZE_X {
+            for j in 0..BOARD_SIZE_Y {
+                let mut tile = game_data.data[i][j];
+                if tile.tile_type == STATE_EMPTY {
+                    tile.tile_owner = Pubkey::default();
+                }
+                self.tiles[i][j] = self.pack_tile(&tile)?;
+            }
+        }
+        for (index, action) in game_data.game_actions.iter().enumerate() {
+            self.actions[index] = self.pack_action(action)?;
+        }
+
+        log_compute_units();
+        Ok(())
+    }
+
+    fn find_slot(&self, owner: Pubkey) -> Option<usize> {
+        self.players.iter().position(|slot| slot.player == owner)
+    }
+
+    // Every owner got a slot in save, so a missing one is a bug and not a
+    // tile without owner
+    fn entity(&self, owner: Pubkey) -> Result<u8> {
+        if owner == Pubkey::default() {
+            return Ok(NO_ENTITY);
+        }
+        match self.find_slot(owner) {
+            Some(slot) => Ok(slot as u8 + 1),
+            None => Err(GameErrorCode::InstanceFull.into()),
+        }
+    }
+
+    fn slot(&self, entity: u8) -> Option<&PlayerSlot> {
+        if entity == NO_ENTITY {
+            return None;
+        }
+        self.players.get(entity as usize - 1)
+    }
+
+    fn pack_tile(&self, tile: &TileData) -> Result<PackedTile> {
+        Ok(PackedTile {
+            tile_type: tile.tile_type,
+            entity: self.entity(tile.tile_owner)?,
+            level: compress(tile.tile_level)?,
+            xp: tile.tile_xp,
+            damage: tile.tile_damage,
+            defence: tile.tile_defence,
+            armor: tile.tile_armor,
+            max_armor: tile.tile_max_armor,
+            health: tile.tile_health,
+            max_health: tile.tile_max_health,
+            crit_chance: tile.tile_crit_chance,
+            crit_multiplier: tile.tile_crit_multiplier,
+            evasion: tile.tile_evasion,
+            damage_range: tile.tile_damage_range,
+            class: tile.tile_class,
+            status: tile.tile_status,
+            status_turns: tile.tile_status_turns,
+        })
+    }
+
+    fn unpack_tile(&self, tile: &PackedTile) -> TileData {
+        let slot = self.slot(tile.entity);
+        let mut tile_data = TileData {
+            tile_type: tile.tile_type,
+            tile_level: tile.level as u32,
+            tile_owner: slot.map(|slot| slot.player).unwrap_or_default(),
+            tile_xp: tile.xp,
+            tile_damage: tile.damage,
+            tile_defence: tile.defence,
+            tile_armor: tile.armor,
+            tile_max_armor: tile.max_armor,
+            tile_health: tile.health,
+            tile_max_health: tile.max_health,
+            tile_crit_chance: tile.crit_chance,
+            tile_crit_multiplier: tile.crit_multiplier,
+            tile_evasion: tile.evasion,
+            tile_damage_range: tile.damage_range,
+            tile_class: tile.class,
+            tile_status: tile.status,
+            tile_status_turns: tile.status_turns,
+            ..Default::default()
+        };
+        if let Some(slot) = slot.filter(|_| tile.tile_type == STATE_PLAYER) {
+            tile_data.tile_avatar = slot.avatar;
+            tile_data.tile_name = slot.name;
+        }
+        tile_data
+    }
+
+    fn pack_action(&self, action: &GameAction) -> Result<PackedAction> {
+        Ok(PackedAction {
+            action_id: action.action_id,
+            action_type: action.action_type,
+            from: pack_position(action.from_x, action.from_y),
+            to: pack_position(action.to_x, action.to_y),
+            tile: self.pack_tile(&action.tile)?,
+            amount: action.amount,
+        })
+    }
+
+    fn unpack_action(&self, action: &PackedAction) -> GameAction {
+        let (from_x, from_y) = unpack_position(action.from);
+        let (to_x, to_y) = unpack_position(action.to);
+        GameAction {
+            action_id: action.action_id,
+            action_type: action.action_type,
+            from_x,
+            from_y,
+            to_x,
+            to_y,
+            tile: self.unpack_tile(&{ action.tile }),
+            amount: action.amount,
+        }
+    }
+}
+
+// The old floor account was read in place. With the measure-encoding feature
+// the units left before and after unpacking and packing are logged, the
+// difference is what the encoding adds to an instruction. See the
+// encoding_cost benchmark in compute-units.
+#[cfg(feature = "measure-encoding")]
+fn log_compute_units() {
+    anchor_lang::solana_program::log::sol_log_compute_units();
+}
+
+#[cfg(not(feature = "measure-encoding"))]
+fn log_compute_units() {}
+
+// Levels do not get anywhere near u16::MAX. Should one ever do it the
+// instruction fails instead of storing a wrong level.
+fn compress(value: u32) -> Result<u16> {
+    u16::try_from(value).map_err(|_| GameErrorCode::StatOverflow.into())
+}
+
+fn pack_position(x: u8, y: u8) -> u8 {
+    x << 4 | (y & 0x0f)
+}
+
+fn unpack_position(position: u8) -> (u8, u8) {
+    (position >> 4, position & 0x0f)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::game_data::tests::sample_tile;
+
+    // The floor account before the compact encoding with the tiles of
+    // today, only kept to measure it. The 10240 bytes it was allocated with
+    // are not what it takes, the tile fields added since do not even fit.
+    #[allow(dead_code)]
+    #[repr(C, packed)]
+    struct OldGameData {
+        id_counter: u64,
+        action_index: u64,
+        data: [[TileData; BOARD_SIZE_X]; BOARD_SIZE_Y],
+        total_wood_collected: u64,
+        game_actions: [GameAction; GAME_ACTIONS],
+        floor_id: u32,
+        owner: Pubkey,
+        mode: u8,
+    }
+
+    const OLD_FLOOR_SPACE: usize = 8 + std::mem::size_of::<OldGameData>();
+
+    fn player_tile(owner: Pubkey) -> TileData {
+        TileData {
+            tile_owner: owner,
+            ..sample_tile()
+        }
+    }
+
+    #[test]
+    fn board_round_trips() {
+        let player = Pubkey::new_unique();
+        let mut game_data = GameData {
+            floor_id: 3,
+            owner: player,
+            ..Default::default()
+        };
+        game_data.data[1][2] = player_tile(player);
+        game_data.data[4][5] = player_tile(Pubkey::new_unique());
+        // An enemy spawned by the player, it has no name of its own
+        game_data.data[7][9] = TileData {
+            tile_type: 2,
+            tile_owner: player,
+            tile_health: 300,
+            tile_max_health: 300,
+            tile_damage: 100_000,
+            ..Default::default()
+        };
+        game_data.add_new_game_action(GameAction {
+            action_id: 0,
+            action_type: 1,
+            from_x: 1,
+            from_y: 2,
+            to_x: 7,
+            to_y: 9,
+            tile: game_data.data[7][9],
+            amount: 42,
+        });
+        game_data.add_new_game_action(GameAction {
+            action_id: 1,
+            action_type: 4,
+            from_x: 1,
+            from_y: 2,
+            to_x: 1,
+            to_y: 2,
+            tile: game_data.data[1][2],
+            amount: 0,
+        });
+
+        let mut floor_data = FloorData::default();
+        floor_data.save(&game_data).unwrap();
+        let board = floor_data.board();
+
+        assert_eq!(board.data, game_data.data);
+        assert_eq!({ board.floor_id }, 3);
+        assert_eq!(board.owner, player);
+        assert_eq!(board.id_counter, game_data.id_counter);
+        assert_eq!(board.action_index, game_data.action_index);
+        assert_eq!(board.game_actions, game_data.game_actions);
+    }
+
+    // The enemies of a player who left the floor keep their owner
+    #[test]
+    fn owner_outlives_the_player() {
+        let player = Pubkey::new_unique();
+        let mut game_data = GameData::default();
+        game_data.data[1][1] = TileData {
+            tile_type: 2,
+            tile_owner: player,
+            ..Default::default()
+        };
+
+        let mut floor_data = FloorData::default();
+        floor_data.save(&game_data).unwrap();
+
+        assert_eq!({ floor_data.board().data[1][1].tile_owner }, player);
+    }
+
+    #[test]
+    fn player_table_is_limited() {
+        let mut game_data = GameData::default();
+        for i in 0..=MAX_PLAYERS_PER_INSTANCE {
+            game_data.data[i][0] = player_tile(Pubkey::new_unique());
+        }
+
+        let mut floor_data = FloorData::default();
+        assert!(floor_data.save(&game_data).is_err());
+    }
+
+    #[test]
+    fn level_overflow_fails() {
+        let mut game_data = GameData::default();
+        game_data.data[0][0] = TileData {
+            tile_level: u16::MAX as u32 + 1,
+            ..player_tile(Pubkey::new_unique())
+        };
+
+        let mut floor_data = FloorData::default();
+        assert!(floor_data.save(&game_data).is_err());
+    }
+
+    // Rent of a floor before and after the compact encoding, run with
+    // --nocapture to see the numbers
+    #[test]
+    fn floor_rent() {
+        let rent = Rent::default();
+        let before = rent.minimum_balance(OLD_FLOOR_SPACE);
+        let after = rent.minimum_balance(FloorData::SPACE);
+        println!(
+            "Floor account {} -> {} bytes, rent {} -> {} lamports",
+            OLD_FLOOR_SPACE,
+            FloorData::SPACE,
+            before,
+            after
+        );
+        assert!(after < before);
+    }
+}
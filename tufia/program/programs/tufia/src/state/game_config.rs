@@ -0,0 +1,26 @@
+use anchor_lang::prelude::*;
+
+use super::crafting::Recipe;
+
+// There is one GameConfig account for the whole program. Only the upgrade
+// authority of the program can call init_config. It becomes the admin and is
+// the only one who can change the config later.
+// The season is part of the seeds of every floor and leaderboard, so ending a
+// season starts everyone on fresh floors.
+#[account]
+pub struct GameConfig {
+    pub admin: Pubkey,
+    pub boss_floor_interval: u16,
+    pub season: u16,
+    pub item_uri: String, // base of the item NFT metadata uris
+    pub recipes: Vec<Recipe>,
+    pub armor_regen_per_move: u32,
+    pub health_regen_seconds: i64,
+}
+
+impl GameConfig {
+    // An interval of 0 turns boss floors off. Floor 0 is never a boss floor.
+    pub fn is_boss_floor(&self, floor_id: u32) -> bool {
+        floor_id > 0 && floor_id.checked_rem(self.boss_floor_interval as u32) == Some(0)
+    }
+}
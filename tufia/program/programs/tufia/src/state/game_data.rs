@@ -1,58 +1,130 @@
-use std::{cmp, slice::SliceIndex};
+use std::cmp;
 
 use anchor_lang::prelude::*;
-use solana_program::{program_pack::Sealed, pubkey};
 
-use crate::{GameErrorCode, __private::__global::move_to_tile};
-
-use super::player_data::{self, PlayerData};
-
-const BOARD_SIZE_X: usize = 10;
-const BOARD_SIZE_Y: usize = 10;
-
-const STATE_EMPTY: u8 = 0;
-const STATE_PLAYER: u8 = 1;
-const STATE_ENEMY: u8 = 2;
+use crate::{constants::*, GameErrorCode};
+
+use super::{
+    boss_data::BossData,
+    buffs::{self, Buff, BUFF_IRON_SKIN, BUFF_STRENGTH},
+    floor::FloorAddress,
+    inventory::{
+        ITEM_ARMOR_POTION, ITEM_CRYSTAL, ITEM_ENERGY_TONIC, ITEM_HEALTH_POTION, ITEM_SCRAP,
+        ITEM_STRENGTH_ELIXIR,
+    },
+    item::{self, GearItem},
+    name_registry, player_class,
+    player_data::{Encounter, PlayerData, StatAllocation},
+    skills, status,
+};
+
+pub(crate) const BOARD_SIZE_X: usize = 10;
+pub(crate) const BOARD_SIZE_Y: usize = 10;
+pub(crate) const GAME_ACTIONS: usize = 20;
+
+pub(crate) const STATE_EMPTY: u8 = 0;
+pub const STATE_PLAYER: u8 = 1;
+pub const STATE_ENEMY: u8 = 2;
 const STATE_CHEST_GOLD: u8 = 3;
 const STATE_CHEST_BLUE: u8 = 4;
-const STATE_STAIRS: u8 = 5;
+pub const STATE_STAIRS: u8 = 5;
+const STATE_TRAP: u8 = 6;
+const STATE_FOUNTAIN: u8 = 7;
+const STATE_SHRINE: u8 = 8;
+const STATE_DOOR: u8 = 9;
+pub const STATE_BOSS: u8 = 10;
+const STATE_MERCHANT: u8 = 11;
+
+// Daily dungeon floors are generated from a fixed seed. The run is played
+// with its own tile, nothing that happens on a daily floor changes the
+// character: no rewards, no lost progress.
+pub const GAME_MODE_NORMAL: u8 = 0;
+pub const GAME_MODE_DAILY: u8 = 1;
 
 const ACTION_TYPE_MOVE: u8 = 0;
 const ACTION_TYPE_FIGHT: u8 = 1;
 const ACTION_TYPE_OPEN_CHEST: u8 = 2;
+// Not logged anymore, the clients still know it from old floors
+#[allow(dead_code)]
 const ACTION_TYPE_RESET: u8 = 3;
 const ACTION_TYPE_PLAYER_DIED: u8 = 4;
+const ACTION_TYPE_TRAP: u8 = 5;
+const ACTION_TYPE_FOUNTAIN: u8 = 6;
+const ACTION_TYPE_SHRINE: u8 = 7;
+const ACTION_TYPE_OPEN_DOOR: u8 = 8;
+const ACTION_TYPE_BOSS_ATTACK: u8 = 9;
+const ACTION_TYPE_BOSS_DEFEATED: u8 = 10;
+const ACTION_TYPE_ENCOUNTER: u8 = 11;
+const ACTION_TYPE_ENEMY_ATTACK: u8 = 12;
+const ACTION_TYPE_DEFEND: u8 = 13;
+const ACTION_TYPE_FLEE: u8 = 14;
+const ACTION_TYPE_USE_ITEM: u8 = 15;
+const ACTION_TYPE_EVADED: u8 = 16;
+const ACTION_TYPE_CRITICAL_HIT: u8 = 17;
+const ACTION_TYPE_ABILITY: u8 = 18;
+const ACTION_TYPE_STATUS_DAMAGE: u8 = 19;
+const ACTION_TYPE_REST: u8 = 20;
+const ACTION_TYPE_STUNNED: u8 = 21;
 
 #[zero_copy(unsafe)]
-#[repr(packed)]
-#[derive(Default)]
+#[repr(C, packed)]
+#[derive(Default, PartialEq, Debug)]
 pub struct GameAction {
-    action_id: u64,  // 4
-    action_type: u8, // 1
-    from_x: u8,      // 1
-    from_y: u8,      // 1
-    to_x: u8,        // 1
-    to_y: u8,        // 1
-    tile: TileData,  // 32
-    amount: u64,     // 4
-}
-
-#[account(zero_copy(unsafe))]
-#[repr(packed)]
-#[derive(Default)]
+    pub(crate) action_id: u64,
+    pub(crate) action_type: u8,
+    pub(crate) from_x: u8,
+    pub(crate) from_y: u8,
+    pub(crate) to_x: u8,
+    pub(crate) to_y: u8,
+    pub(crate) tile: TileData,
+    pub(crate) amount: u64,
+}
+
+// The board the game logic works on. It is unpacked from the FloorData
+// account at the start of an instruction and packed again at the end, see
+// floor_data.rs. The arrays live on the heap, they do not fit on the stack.
 pub struct GameData {
-    id_counter: u64,
-    action_index: u64,
-    pub data: [[TileData; BOARD_SIZE_X]; BOARD_SIZE_Y],
+    pub(crate) id_counter: u64,
+    pub(crate) action_index: u64,
+    pub data: Box<[[TileData; BOARD_SIZE_X]; BOARD_SIZE_Y]>,
     pub total_wood_collected: u64,
-    pub game_actions: [GameAction; 20],
+    pub game_actions: Box<[GameAction; GAME_ACTIONS]>,
     pub floor_id: u32,
     pub owner: Pubkey,
+    pub mode: u8,
+}
+
+impl Default for GameData {
+    fn default() -> Self {
+        GameData {
+            id_counter: 0,
+            action_index: 0,
+            data: boxed_array(),
+            total_wood_collected: 0,
+            game_actions: boxed_array(),
+            floor_id: 0,
+            owner: Pubkey::default(),
+            mode: GAME_MODE_NORMAL,
+        }
+    }
+}
+
+// Allocates the array on the heap directly instead of building it on the
+// stack first
+fn boxed_array<T: Clone + Default, const N: usize>() -> Box<[T; N]> {
+    match vec![T::default(); N].into_boxed_slice().try_into() {
+        Ok(array) => array,
+        Err(_) => unreachable!(),
+    }
 }
 
+// Everything on the board is a tile. TileData is the tile of the GameData
+// board, TileData2 is the same tile serialized into the PlayerData to be
+// carried between floors. Convert with From/Into. The floor account stores
+// tiles as PackedTile.
 #[zero_copy(unsafe)]
-#[repr(packed)]
-#[derive(Default)]
+#[repr(C, packed)]
+#[derive(Default, PartialEq, Debug)]
 pub struct TileData {
     pub tile_type: u8,
     pub tile_level: u32,
@@ -64,9 +136,18 @@ pub struct TileData {
     pub tile_max_armor: u32,
     pub tile_health: u32,
     pub tile_max_health: u32,
+    pub tile_crit_chance: u8,     // in percent
+    pub tile_crit_multiplier: u8, // in percent, 150 means 1.5x damage
+    pub tile_evasion: u8,         // in percent
+    pub tile_damage_range: u8,    // damage varies by up to this in both directions
+    pub tile_class: u8,
+    pub tile_avatar: u8,
+    pub tile_name: [u8; TILE_NAME_LENGTH], // the player name, zero padded
+    pub tile_status: u8,                   // see status.rs
+    pub tile_status_turns: u8,
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default, PartialEq, Debug)]
 pub struct TileData2 {
     pub tile_type: u8,
     pub tile_level: u32,
@@ -78,6 +159,75 @@ pub struct TileData2 {
     pub tile_max_armor: u32,
     pub tile_health: u32,
     pub tile_max_health: u32,
+    pub tile_crit_chance: u8,     // in percent
+    pub tile_crit_multiplier: u8, // in percent, 150 means 1.5x damage
+    pub tile_evasion: u8,         // in percent
+    pub tile_damage_range: u8,    // damage varies by up to this in both directions
+    pub tile_class: u8,
+    pub tile_avatar: u8,
+    pub tile_name: [u8; TILE_NAME_LENGTH], // the player name, zero padded
+    pub tile_status: u8,                   // see status.rs
+    pub tile_status_turns: u8,
+}
+
+// Both conversions list every field, adding a field to only one of the
+// structs does not compile.
+impl From<TileData> for TileData2 {
+    fn from(tile: TileData) -> Self {
+        TileData2 {
+            tile_type: tile.tile_type,
+            tile_level: tile.tile_level,
+            tile_owner: tile.tile_owner,
+            tile_xp: tile.tile_xp,
+            tile_damage: tile.tile_damage,
+            tile_defence: tile.tile_defence,
+            tile_armor: tile.tile_armor,
+            tile_max_armor: tile.tile_max_armor,
+            tile_health: tile.tile_health,
+            tile_max_health: tile.tile_max_health,
+            tile_crit_chance: tile.tile_crit_chance,
+            tile_crit_multiplier: tile.tile_crit_multiplier,
+            tile_evasion: tile.tile_evasion,
+            tile_damage_range: tile.tile_damage_range,
+            tile_class: tile.tile_class,
+            tile_avatar: tile.tile_avatar,
+            tile_name: tile.tile_name,
+            tile_status: tile.tile_status,
+            tile_status_turns: tile.tile_status_turns,
+        }
+    }
+}
+
+impl From<&TileData2> for TileData {
+    fn from(tile: &TileData2) -> Self {
+        TileData {
+            tile_type: tile.tile_type,
+            tile_level: tile.tile_level,
+            tile_owner: tile.tile_owner,
+            tile_xp: tile.tile_xp,
+            tile_damage: tile.tile_damage,
+            tile_defence: tile.tile_defence,
+            tile_armor: tile.tile_armor,
+            tile_max_armor: tile.tile_max_armor,
+            tile_health: tile.tile_health,
+            tile_max_health: tile.tile_max_health,
+            tile_crit_chance: tile.tile_crit_chance,
+            tile_crit_multiplier: tile.tile_crit_multiplier,
+            tile_evasion: tile.tile_evasion,
+            tile_damage_range: tile.tile_damage_range,
+            tile_class: tile.tile_class,
+            tile_avatar: tile.tile_avatar,
+            tile_name: tile.tile_name,
+            tile_status: tile.tile_status,
+            tile_status_turns: tile.tile_status_turns,
+        }
+    }
+}
+
+impl From<TileData2> for TileData {
+    fn from(tile: TileData2) -> Self {
+        TileData::from(&tile)
+    }
 }
 
 impl GameData {
@@ -86,59 +236,72 @@ impl GameData {
         x: u64,
         y: u64,
         player: &mut PlayerData,
-        spawn: bool,
+        floor: Pubkey,
+        allies: &[Pubkey],
+        armor_regen: u32,
     ) -> Result<()> {
         // Check if the player is on the board
         if x as usize >= BOARD_SIZE_X || y as usize >= BOARD_SIZE_Y {
             return Err(GameErrorCode::OutOfBounds.into());
         }
 
+        if player.encounter.active {
+            return Err(GameErrorCode::InCombat.into());
+        }
+
         let mut current_player_tile: Option<TileData> = None;
-        let mut empty_slots: Vec<(usize, usize)> = Vec::new();
         let mut current_player_pos_x: usize = 0;
         let mut current_player_pos_y: usize = 0;
 
-        let mut end_player_pos_x: usize = x as usize;
-        let mut end_player_pos_y: usize = y as usize;
-
-        let mut has_stairs: bool = false;
-
-        let mut num_enemies: u32 = 0;
-
         for i in 0..BOARD_SIZE_X {
             for j in 0..BOARD_SIZE_Y {
                 let tile: TileData = self.data[i][j];
 
-                if tile.tile_type == STATE_STAIRS {
-                    has_stairs = true;
-                }
-                if tile.tile_type == STATE_ENEMY {
-                    num_enemies += 1;
-                }
-
-                if tile.tile_type == STATE_EMPTY {
-                    empty_slots.push((i, j));
-                } else if tile.tile_owner == player.authority && tile.tile_type == STATE_PLAYER {
+                if tile.tile_owner == player.authority && tile.tile_type == STATE_PLAYER {
                     current_player_tile = Some(tile);
                     current_player_pos_x = i;
                     current_player_pos_y = j;
-                    let floorId = self.floor_id;
-                    msg!("Found player tile {}{} floor: {}", i, j, floorId);
-                } else if tile.tile_owner == player.authority
-                    && tile.tile_type == STATE_PLAYER
-                    && spawn
-                {
-                    return Err(GameErrorCode::PlayerAlreadyExists.into());
+                    let floor_id = self.floor_id;
+                    msg!("Found player tile {}{} floor: {}", i, j, floor_id);
                 }
             }
         }
 
         match current_player_tile {
-            Some(mut tile) => {
+            Some(_) => {
                 if x as usize == current_player_pos_x && y as usize == current_player_pos_y {
                     return Err(GameErrorCode::PlayerIsAlreadyOnThisTile.into());
                 }
 
+                // Shrine blessings and buffs belong to the character, the
+                // daily tile has neither
+                if !self.is_daily() {
+                    fade_shrine_blessing(
+                        player,
+                        &mut self.data[current_player_pos_x][current_player_pos_y],
+                    );
+                    for buff in player.tick_buffs() {
+                        buffs::apply_buff(
+                            &mut self.data[current_player_pos_x][current_player_pos_y],
+                            &buff,
+                            false,
+                        );
+                    }
+                }
+                regenerate_armor(
+                    player,
+                    &mut self.data[current_player_pos_x][current_player_pos_y],
+                    armor_regen,
+                );
+
+                // Poison and burn hurt on every move as well
+                self.tick_status(current_player_pos_x, current_player_pos_y);
+                if self.data[current_player_pos_x][current_player_pos_y].tile_health == 0 {
+                    kill_player(player, self, current_player_pos_x, current_player_pos_y);
+                    self.save_player_tile(player);
+                    return Ok(());
+                }
+
                 let target_tile = self.data[x as usize][y as usize];
                 msg!("Target tile: {} ", target_tile.tile_type);
 
@@ -149,9 +312,9 @@ impl GameData {
                         action_id: self.id_counter,
                         action_type: ACTION_TYPE_OPEN_CHEST,
                         from_x: current_player_pos_x as u8,
-                        from_y: current_player_pos_x as u8,
+                        from_y: current_player_pos_y as u8,
                         to_x: x as u8,
-                        to_y: x as u8,
+                        to_y: y as u8,
                         tile: target_tile,
                         amount: 0,
                     };
@@ -160,6 +323,7 @@ impl GameData {
 
                     open_chest(
                         player,
+                        self.is_daily(),
                         &mut self.data,
                         current_player_pos_x,
                         current_player_pos_y,
@@ -181,20 +345,23 @@ impl GameData {
                 }
 
                 if target_tile.tile_type == STATE_ENEMY {
-                    fight_enemy(
+                    self.start_encounter(
                         player,
-                        self,
-                        //&mut self.data,
+                        floor,
                         current_player_pos_x,
                         current_player_pos_y,
                         x as usize,
                         y as usize,
-                    )?;
-                    // Fight enemy
+                    );
                 }
 
                 msg!("Fight player?");
                 if target_tile.tile_type == STATE_PLAYER {
+                    // No friendly fire between party members
+                    if allies.contains(&{ target_tile.tile_owner }) {
+                        return Err(GameErrorCode::FriendlyFire.into());
+                    }
+
                     // Fight player
                     msg!("Fight player");
                     fight_enemy(
@@ -211,184 +378,108 @@ impl GameData {
                 if target_tile.tile_type == STATE_STAIRS {
                     // GO one floor down
                 }
-            }
-            None => {
-                if spawn {
-                    if empty_slots.is_empty() {
-                        return Err(GameErrorCode::BoardIsFull.into());
-                    }
 
-                    let mut rng = XorShift64 {
-                        a: empty_slots.len() as u64 + player.health as u64,
+                if target_tile.tile_type == STATE_TRAP {
+                    let new_game_action = GameAction {
+                        action_id: self.id_counter,
+                        action_type: ACTION_TYPE_TRAP,
+                        from_x: current_player_pos_x as u8,
+                        from_y: current_player_pos_y as u8,
+                        to_x: x as u8,
+                        to_y: y as u8,
+                        tile: target_tile,
+                        amount: target_tile.tile_damage as u64,
                     };
 
-                    let random_index = (rng.next() % (empty_slots.len() as u64)) as usize;
-                    let random_empty_slot = empty_slots[random_index];
-                    msg!(
-                        "Player spawn at {} {}",
-                        random_empty_slot.0,
-                        random_empty_slot.1
-                    );
-                    end_player_pos_x = random_empty_slot.0 as usize;
-                    end_player_pos_y = random_empty_slot.1 as usize;
-                    empty_slots.remove(random_index);
-
-                    self.data[random_empty_slot.0][random_empty_slot.1] = TileData {
-                        tile_owner: player.authority.clone(),
-                        tile_type: STATE_PLAYER,
-                        tile_health: player.max_health,
-                        tile_max_health: player.max_health,
-                        tile_damage: player.damage,
-                        tile_defence: player.defence,
-                        tile_level: player.level,
-                        tile_xp: player.xp,
-                        ..Default::default()
+                    self.add_new_game_action(new_game_action);
+
+                    trigger_trap(
+                        player,
+                        self,
+                        current_player_pos_x,
+                        current_player_pos_y,
+                        x as usize,
+                        y as usize,
+                    )?;
+                }
+
+                if target_tile.tile_type == STATE_FOUNTAIN {
+                    let new_game_action = GameAction {
+                        action_id: self.id_counter,
+                        action_type: ACTION_TYPE_FOUNTAIN,
+                        from_x: current_player_pos_x as u8,
+                        from_y: current_player_pos_y as u8,
+                        to_x: x as u8,
+                        to_y: y as u8,
+                        tile: target_tile,
+                        amount: 0,
                     };
 
-                    current_player_tile = Some(self.data[random_empty_slot.0][random_empty_slot.1]);
-
-                    // Spawn enemy
-                    if (num_enemies < 6) {
-                        if empty_slots.len() > 0 {
-                            let random_index = (rng.next() % (empty_slots.len() as u64)) as usize;
-                            let random_empty_slot = empty_slots[random_index];
-                            msg!(
-                                "Enemy spawn at {} {}",
-                                random_empty_slot.0,
-                                random_empty_slot.1
-                            );
-
-                            empty_slots.remove(random_index);
-
-                            self.data[random_empty_slot.0][random_empty_slot.1] = TileData {
-                                tile_owner: player.authority.clone(),
-                                tile_type: STATE_ENEMY,
-                                tile_health: player.current_floor as u32 + 5,
-                                tile_max_health: player.current_floor as u32 + 5,
-                                tile_damage: player.current_floor as u32 + 1,
-                                tile_defence: player.current_floor as u32 + 1,
-                                tile_armor: player.current_floor as u32 + 1,
-                                tile_max_armor: player.current_floor as u32 + 1,
-                                tile_level: player.current_floor as u32 + 1,
-                                tile_xp: player.current_floor as u32 + 1,
-                                ..Default::default()
-                            };
-                        }
-                        if empty_slots.len() > 0 {
-                            let random_index = (rng.next() % (empty_slots.len() as u64)) as usize;
-                            let random_empty_slot = empty_slots[random_index];
-                            msg!(
-                                "Enemy spawn at {} {}",
-                                random_empty_slot.0,
-                                random_empty_slot.1
-                            );
-
-                            empty_slots.remove(random_index);
-
-                            self.data[random_empty_slot.0][random_empty_slot.1] = TileData {
-                                tile_owner: player.authority.clone(),
-                                tile_type: STATE_ENEMY,
-                                tile_health: player.current_floor as u32 + 5,
-                                tile_max_health: player.current_floor as u32 + 5,
-                                tile_damage: player.current_floor as u32 + 1,
-                                tile_defence: player.current_floor as u32 + 1,
-                                tile_armor: player.current_floor as u32 + 1,
-                                tile_max_armor: player.current_floor as u32 + 1,
-                                tile_level: player.current_floor as u32 + 1,
-                                tile_xp: player.current_floor as u32 + 1,
-                                ..Default::default()
-                            };
-                        }
-                    }
+                    self.add_new_game_action(new_game_action);
 
-                    // Spawn stairs
-                    if empty_slots.len() > 0 && !has_stairs {
-                        let random_index = (rng.next() % (empty_slots.len() as u64)) as usize;
-                        let random_empty_slot = empty_slots[random_index];
-                        msg!(
-                            "Stairs spawn at {} {}",
-                            random_empty_slot.0,
-                            random_empty_slot.1
-                        );
+                    drink_from_fountain(
+                        &mut self.data,
+                        current_player_pos_x,
+                        current_player_pos_y,
+                        x as usize,
+                        y as usize,
+                    )?;
+                }
 
-                        empty_slots.remove(random_index);
-
-                        self.data[random_empty_slot.0][random_empty_slot.1] = TileData {
-                            tile_owner: player.authority.clone(),
-                            tile_type: STATE_STAIRS,
-                            tile_health: player.current_floor as u32 + 5,
-                            tile_max_health: player.current_floor as u32 + 5,
-                            tile_damage: player.current_floor as u32 + 1,
-                            tile_defence: player.current_floor as u32 + 1,
-                            tile_armor: player.current_floor as u32 + 1,
-                            tile_max_armor: player.current_floor as u32 + 1,
-                            tile_level: player.current_floor as u32 + 1,
-                            tile_xp: player.current_floor as u32 + 1,
-                            ..Default::default()
-                        };
-                    }
+                if target_tile.tile_type == STATE_SHRINE {
+                    let new_game_action = GameAction {
+                        action_id: self.id_counter,
+                        action_type: ACTION_TYPE_SHRINE,
+                        from_x: current_player_pos_x as u8,
+                        from_y: current_player_pos_y as u8,
+                        to_x: x as u8,
+                        to_y: y as u8,
+                        tile: target_tile,
+                        amount: target_tile.tile_damage as u64,
+                    };
 
-                    let random_index = (rng.next() % (100)) as usize;
-
-                    msg!("Random index chest: {}", random_index);
-                    // Spawn Super chest
-                    if empty_slots.len() > 0 && random_index > 70 {
-                        let random_index = (rng.next() % (empty_slots.len() as u64)) as usize;
-                        let random_empty_slot = empty_slots[random_index];
-                        msg!(
-                            "Player spawn at {} {}",
-                            random_empty_slot.0,
-                            random_empty_slot.1
-                        );
+                    self.add_new_game_action(new_game_action);
 
-                        empty_slots.remove(random_index);
-
-                        self.data[random_empty_slot.0][random_empty_slot.1] = TileData {
-                            tile_owner: player.authority.clone(),
-                            tile_type: STATE_CHEST_BLUE,
-                            tile_health: 0,
-                            tile_damage: 0,
-                            tile_defence: 0,
-                            tile_level: 1,
-                            tile_xp: 0,
-                            ..Default::default()
-                        };
-                    }
+                    pray_at_shrine(
+                        player,
+                        self.is_daily(),
+                        &mut self.data,
+                        current_player_pos_x,
+                        current_player_pos_y,
+                        x as usize,
+                        y as usize,
+                    )?;
+                }
 
-                    let random_index = (rng.next() % (100)) as usize;
-
-                    msg!("Random index chest: {}", random_index);
-                    // Spawn Super chest
-                    if empty_slots.len() > 0 {
-                        let random_index = (rng.next() % (empty_slots.len() as u64)) as usize;
-                        let random_empty_slot = empty_slots[random_index];
-                        msg!(
-                            "Player spawn at {} {}",
-                            random_empty_slot.0,
-                            random_empty_slot.1
-                        );
+                if target_tile.tile_type == STATE_DOOR {
+                    open_door(
+                        player,
+                        self.is_daily(),
+                        &mut self.data,
+                        current_player_pos_x,
+                        current_player_pos_y,
+                        x as usize,
+                        y as usize,
+                    )?;
 
-                        empty_slots.remove(random_index);
-
-                        self.data[random_empty_slot.0][random_empty_slot.1] = TileData {
-                            tile_owner: player.authority.clone(),
-                            tile_type: STATE_CHEST_GOLD,
-                            tile_health: 0,
-                            tile_damage: 0,
-                            tile_defence: 0,
-                            tile_level: 1,
-                            tile_xp: 0,
-                            ..Default::default()
-                        };
-                    }
-                } else {
-                    return Err(GameErrorCode::PlayerNotOnBoard.into());
+                    let new_game_action = GameAction {
+                        action_id: self.id_counter,
+                        action_type: ACTION_TYPE_OPEN_DOOR,
+                        from_x: current_player_pos_x as u8,
+                        from_y: current_player_pos_y as u8,
+                        to_x: x as u8,
+                        to_y: y as u8,
+                        tile: target_tile,
+                        amount: player.keys as u64,
+                    };
+
+                    self.add_new_game_action(new_game_action);
                 }
             }
-        }
-
-        if current_player_tile.is_none() {
-            return Err(GameErrorCode::PlayerNotOnBoard.into());
+            // Players join a floor with buy_next_floor, moving does not spawn
+            None => {
+                return Err(GameErrorCode::PlayerNotOnBoard.into());
+            }
         }
 
         let new_game_action = GameAction {
@@ -404,42 +495,503 @@ impl GameData {
 
         self.add_new_game_action(new_game_action);
 
-        let mut tile_data_clone: TileData2 = TileData2::default();
-        tile_data_clone.tile_armor =
-            self.data[end_player_pos_x as usize][end_player_pos_y as usize].tile_armor;
-        tile_data_clone.tile_damage =
-            self.data[end_player_pos_x as usize][end_player_pos_y as usize].tile_damage;
-        tile_data_clone.tile_defence =
-            self.data[end_player_pos_x as usize][end_player_pos_y as usize].tile_defence;
-        tile_data_clone.tile_health =
-            self.data[end_player_pos_x as usize][end_player_pos_y as usize].tile_health;
-        tile_data_clone.tile_level =
-            self.data[end_player_pos_x as usize][end_player_pos_y as usize].tile_level;
-        tile_data_clone.tile_max_armor =
-            self.data[end_player_pos_x as usize][end_player_pos_y as usize].tile_max_armor;
-        tile_data_clone.tile_max_health =
-            self.data[end_player_pos_x as usize][end_player_pos_y as usize].tile_max_health;
-        tile_data_clone.tile_owner =
-            self.data[end_player_pos_x as usize][end_player_pos_y as usize].tile_owner;
-        tile_data_clone.tile_type =
-            self.data[end_player_pos_x as usize][end_player_pos_y as usize].tile_type;
-        tile_data_clone.tile_xp =
-            self.data[end_player_pos_x as usize][end_player_pos_y as usize].tile_xp;
-
-        player.tile_data = tile_data_clone;
+        self.save_player_tile(player);
 
         Ok(())
     }
 
+    // Copies the board tile of the player into the player account so the stats
+    // can be carried over to the next floor. Dead players keep their last tile.
+    // The daily tile is never carried anywhere.
+    fn save_player_tile(&self, player: &mut PlayerData) {
+        if self.is_daily() {
+            return;
+        }
+        if let Some((player_x, player_y)) = self.find_player_position(player.authority) {
+            let tile = self.data[player_x][player_y];
+            player.tile_data = tile.into();
+            player.health = tile.tile_health;
+        }
+    }
+
+    // Stepping onto an enemy does not resolve the fight right away. It locks
+    // the player into an encounter that is played out with combat_action.
+    fn start_encounter(
+        &mut self,
+        player: &mut PlayerData,
+        floor: Pubkey,
+        player_x: usize,
+        player_y: usize,
+        enemy_x: usize,
+        enemy_y: usize,
+    ) {
+        // Damaged player armor carries over from the last fight, only the
+        // enemy starts fresh
+        self.data[enemy_x][enemy_y].tile_armor = self.data[enemy_x][enemy_y].tile_max_armor;
+
+        player.encounter = Encounter {
+            active: true,
+            enemy_x: enemy_x as u8,
+            enemy_y: enemy_y as u8,
+            round: 0,
+            ability_used: false,
+            floor,
+        };
+
+        self.log_action(
+            ACTION_TYPE_ENCOUNTER,
+            player_x,
+            player_y,
+            enemy_x,
+            enemy_y,
+            0,
+        );
+        msg!("Encounter started at {} {}", enemy_x, enemy_y);
+    }
+
+    // One round of an encounter. The player acts first, then the enemy strikes
+    // back if it is still standing and the player did not get away.
+    pub fn combat_action(
+        &mut self,
+        player: &mut PlayerData,
+        floor: Pubkey,
+        action: u8,
+    ) -> Result<()> {
+        if !player.encounter.active {
+            return Err(GameErrorCode::NotInCombat.into());
+        }
+        if player.encounter.floor != floor {
+            return Err(GameErrorCode::WrongFloor.into());
+        }
+
+        // The player can be killed or the enemy taken by someone else between
+        // two rounds. The encounter is just over then.
+        let Some((player_x, player_y)) = self.find_player_position(player.authority) else {
+            player.encounter = Encounter::default();
+            msg!("Encounter ended, player not on board");
+            return Ok(());
+        };
+        let enemy_x = player.encounter.enemy_x as usize;
+        let enemy_y = player.encounter.enemy_y as usize;
+        if self.data[enemy_x][enemy_y].tile_type != STATE_ENEMY {
+            end_encounter(player, &mut self.data[player_x][player_y]);
+            msg!("Encounter ended, enemy is gone");
+            return Ok(());
+        }
+
+        player.encounter.round += 1;
+        msg!("Round {} action {}", player.encounter.round, action);
+        regenerate_armor(player, &mut self.data[player_x][player_y], 0);
+
+        // Status effects of both sides tick every round
+        self.tick_status(player_x, player_y);
+        self.tick_status(enemy_x, enemy_y);
+        if self.data[player_x][player_y].tile_health == 0 {
+            kill_player(player, self, player_x, player_y);
+            self.save_player_tile(player);
+            return Ok(());
+        }
+        if self.data[enemy_x][enemy_y].tile_health == 0 {
+            end_encounter(player, &mut self.data[player_x][player_y]);
+            win_fight(player, self, player_x, player_y, enemy_x, enemy_y)?;
+            self.save_player_tile(player);
+            return Ok(());
+        }
+        let mut rng = XorShift64::from_slot(player.encounter.round as u64)?;
+
+        match action {
+            COMBAT_ACTION_ATTACK => {
+                let roll = self.resolve_attack((player_x, player_y), (enemy_x, enemy_y), &mut rng);
+                self.log_action(
+                    roll.action_type(ACTION_TYPE_FIGHT),
+                    player_x,
+                    player_y,
+                    enemy_x,
+                    enemy_y,
+                    roll.damage as u64,
+                );
+            }
+            COMBAT_ACTION_DEFEND => {
+                let bonus = defend(&mut self.data[player_x][player_y]);
+                self.log_action(
+                    ACTION_TYPE_DEFEND,
+                    player_x,
+                    player_y,
+                    player_x,
+                    player_y,
+                    bonus as u64,
+                );
+            }
+            COMBAT_ACTION_FLEE => {
+                let chance =
+                    flee_chance(&self.data[player_x][player_y], &self.data[enemy_x][enemy_y]);
+                let roll = rng.next() % 100;
+                let fled = roll < chance;
+                msg!("Flee roll {} chance {}", roll, chance);
+                self.log_action(
+                    ACTION_TYPE_FLEE,
+                    player_x,
+                    player_y,
+                    enemy_x,
+                    enemy_y,
+                    fled as u64,
+                );
+
+                if fled {
+                    end_encounter(player, &mut self.data[player_x][player_y]);
+                    self.save_player_tile(player);
+                    return Ok(());
+                }
+            }
+            COMBAT_ACTION_USE_ITEM => {
+                // Potions belong to the character, daily runs go without
+                if self.is_daily() || player.health_potions == 0 {
+                    return Err(GameErrorCode::NoConsumables.into());
+                }
+                player.health_potions -= 1;
+
+                let tile = &mut self.data[player_x][player_y];
+                let heal = cmp::min(
+                    POTION_HEAL,
+                    tile.tile_max_health.saturating_sub(tile.tile_health),
+                );
+                tile.tile_health += heal;
+                self.log_action(
+                    ACTION_TYPE_USE_ITEM,
+                    player_x,
+                    player_y,
+                    player_x,
+                    player_y,
+                    heal as u64,
+                );
+            }
+            COMBAT_ACTION_ABILITY => {
+                if player.encounter.ability_used {
+                    return Err(GameErrorCode::AbilityAlreadyUsed.into());
+                }
+                player.encounter.ability_used = true;
+
+                let damage = player_class::ability_damage(
+                    &self.data[player_x][player_y],
+                    &self.data[enemy_x][enemy_y],
+                )?;
+                apply_damage(&mut self.data[enemy_x][enemy_y], damage);
+                self.log_action(
+                    ACTION_TYPE_ABILITY,
+                    player_x,
+                    player_y,
+                    enemy_x,
+                    enemy_y,
+                    damage as u64,
+                );
+            }
+            _ => return Err(GameErrorCode::InvalidCombatAction.into()),
+        }
+
+        if self.data[enemy_x][enemy_y].tile_health == 0 {
+            end_encounter(player, &mut self.data[player_x][player_y]);
+            win_fight(player, self, player_x, player_y, enemy_x, enemy_y)?;
+            self.save_player_tile(player);
+            return Ok(());
+        }
+
+        let roll = self.resolve_attack((enemy_x, enemy_y), (player_x, player_y), &mut rng);
+        self.log_action(
+            roll.action_type(ACTION_TYPE_ENEMY_ATTACK),
+            enemy_x,
+            enemy_y,
+            player_x,
+            player_y,
+            roll.damage as u64,
+        );
+        drop_defend_bonus(&mut self.data[player_x][player_y]);
+
+        if self.data[player_x][player_y].tile_health == 0 {
+            kill_player(player, self, player_x, player_y);
+        }
+
+        self.save_player_tile(player);
+        Ok(())
+    }
+
+    // Rolls a single hit and applies it to the defender. An evaded hit does no
+    // damage at all, not even the usual minimum of 1. A stunned attacker loses
+    // the turn without rolling.
+    fn resolve_attack(
+        &mut self,
+        attacker: (usize, usize),
+        defender: (usize, usize),
+        rng: &mut XorShift64,
+    ) -> AttackRoll {
+        if status::take_stun(&mut self.data[attacker.0][attacker.1]) {
+            return AttackRoll {
+                damage: 0,
+                evaded: false,
+                critical: false,
+                stunned: true,
+            };
+        }
+
+        let roll = roll_attack(
+            &self.data[attacker.0][attacker.1],
+            &self.data[defender.0][defender.1],
+            rng,
+        );
+        if !roll.evaded && !roll.stunned {
+            apply_damage(&mut self.data[defender.0][defender.1], roll.damage);
+            let attacker_tile = self.data[attacker.0][attacker.1];
+            status::inflict(&attacker_tile, &mut self.data[defender.0][defender.1]);
+        }
+
+        roll
+    }
+
+    // Stat points spent while on a floor go onto the board tile right away.
+    pub fn apply_stat_allocation(&mut self, player: &mut PlayerData, allocation: &StatAllocation) {
+        if let Some((player_x, player_y)) = self.find_player_position(player.authority) {
+            let tile = &mut self.data[player_x][player_y];
+            let health = allocation.health as u32 * HEALTH_PER_STAT_POINT;
+            tile.tile_max_health += health;
+            tile.tile_health += health;
+            tile.tile_damage += allocation.damage as u32;
+            tile.tile_defence += allocation.defence as u32;
+            tile.tile_max_armor += allocation.armor as u32;
+
+            self.save_player_tile(player);
+        }
+    }
+
+    // Level ups from xp earned off the board, boss rewards and party shares,
+    // grow the board tile the same way PlayerData::add_xp grows the carried one.
+    pub fn apply_level_up(&mut self, player: &mut PlayerData, levels: u32) {
+        if levels == 0 || self.is_daily() {
+            return;
+        }
+        if let Some((player_x, player_y)) = self.find_player_position(player.authority) {
+            let growth = player_class::growth(player.class);
+            let tile = &mut self.data[player_x][player_y];
+            tile.tile_level = player.level;
+            tile.tile_xp = player.xp;
+            tile.tile_max_health += growth.max_health * levels;
+            tile.tile_health = tile.tile_max_health;
+            tile.tile_damage += growth.damage * levels;
+
+            self.save_player_tile(player);
+        }
+    }
+
+    // Housekeeping like update_energy, takes the bonus of buffs that ran out
+    // off the board tile before the player acts.
+    pub fn update_buffs(&mut self, player: &mut PlayerData) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        match self.find_player_position(player.authority) {
+            Some((player_x, player_y)) => {
+                for buff in player.take_expired_buffs(now) {
+                    buffs::apply_buff(&mut self.data[player_x][player_y], &buff, false);
+                }
+                self.save_player_tile(player);
+            }
+            None => player.update_buffs()?,
+        }
+        Ok(())
+    }
+
+    // Housekeeping like update_energy, health comes back over time. Not while
+    // fighting, the time still counts once the fight is over.
+    pub fn update_health(
+        &mut self,
+        player: &mut PlayerData,
+        seconds_per_health: i64,
+    ) -> Result<()> {
+        if player.encounter.active {
+            return Ok(());
+        }
+        match self.find_player_position(player.authority) {
+            Some((player_x, player_y)) => {
+                let tile = &mut self.data[player_x][player_y];
+                tile.tile_health += player.take_health_regen(
+                    tile.tile_health,
+                    tile.tile_max_health,
+                    seconds_per_health,
+                )?;
+                self.save_player_tile(player);
+            }
+            None => player.update_health(seconds_per_health)?,
+        }
+        Ok(())
+    }
+
+    // Resting heals without a potion but costs energy, only on tiles without
+    // an enemy next to them.
+    pub fn rest(&mut self, player: &mut PlayerData) -> Result<()> {
+        if player.encounter.active {
+            return Err(GameErrorCode::InCombat.into());
+        }
+        let (player_x, player_y) = self
+            .find_player_position(player.authority)
+            .ok_or(GameErrorCode::PlayerNotOnBoard)?;
+        if self.is_next_to(player_x, player_y, STATE_ENEMY)
+            || self.is_next_to(player_x, player_y, STATE_BOSS)
+        {
+            return Err(GameErrorCode::NotSafeToRest.into());
+        }
+
+        player.energy = player
+            .energy
+            .checked_sub(REST_ENERGY)
+            .ok_or(GameErrorCode::NotEnoughEnergy)?;
+        let tile = &mut self.data[player_x][player_y];
+        tile.tile_health = cmp::min(tile.tile_health + REST_HEAL, tile.tile_max_health);
+        self.log_action(
+            ACTION_TYPE_REST,
+            player_x,
+            player_y,
+            player_x,
+            player_y,
+            REST_HEAL as u64,
+        );
+
+        self.save_player_tile(player);
+        Ok(())
+    }
+
+    // Potions heal or refill armor right away, the elixir and the armor potion
+    // also give a buff. Only outside of fights, in fights there is
+    // COMBAT_ACTION_USE_ITEM.
+    pub fn use_consumable(&mut self, player: &mut PlayerData, item_id: u16) -> Result<()> {
+        if player.encounter.active {
+            return Err(GameErrorCode::InCombat.into());
+        }
+        let (player_x, player_y) = self
+            .find_player_position(player.authority)
+            .ok_or(GameErrorCode::PlayerNotOnBoard)?;
+
+        let buff = match item_id {
+            ITEM_HEALTH_POTION => {
+                let tile = &mut self.data[player_x][player_y];
+                tile.tile_health = cmp::min(tile.tile_health + POTION_HEAL, tile.tile_max_health);
+                None
+            }
+            ITEM_ARMOR_POTION => {
+                let tile = &mut self.data[player_x][player_y];
+                tile.tile_armor = tile.tile_max_armor;
+                Some(Buff {
+                    kind: BUFF_IRON_SKIN,
+                    amount: ARMOR_POTION_DEFENCE,
+                    expires_at: 0,
+                    moves_left: ARMOR_POTION_MOVES,
+                })
+            }
+            ITEM_STRENGTH_ELIXIR => Some(Buff {
+                kind: BUFF_STRENGTH,
+                amount: STRENGTH_ELIXIR_DAMAGE,
+                expires_at: Clock::get()?.unix_timestamp + STRENGTH_ELIXIR_SECONDS,
+                moves_left: 0,
+            }),
+            ITEM_ENERGY_TONIC => {
+                player.energy = cmp::min(player.energy + ENERGY_TONIC_ENERGY, MAX_ENERGY);
+                None
+            }
+            _ => return Err(GameErrorCode::NotConsumable.into()),
+        };
+        player.remove_item(item_id, 1)?;
+
+        if let Some(buff) = buff {
+            player.add_buff(buff)?;
+            buffs::apply_buff(&mut self.data[player_x][player_y], &buff, true);
+        }
+        self.log_action(
+            ACTION_TYPE_USE_ITEM,
+            player_x,
+            player_y,
+            player_x,
+            player_y,
+            item_id as u64,
+        );
+
+        self.save_player_tile(player);
+        Ok(())
+    }
+
+    pub fn apply_gear(&mut self, player: &mut PlayerData, item: &GearItem) {
+        if let Some((player_x, player_y)) = self.find_player_position(player.authority) {
+            let tile = &mut self.data[player_x][player_y];
+            if item.equipped {
+                tile.tile_damage += item.damage;
+                tile.tile_defence += item.defence;
+                tile.tile_max_armor += item.armor;
+            } else {
+                tile.tile_damage = tile.tile_damage.saturating_sub(item.damage);
+                tile.tile_defence = tile.tile_defence.saturating_sub(item.defence);
+                tile.tile_max_armor = tile.tile_max_armor.saturating_sub(item.armor);
+                tile.tile_armor = tile.tile_armor.min(tile.tile_max_armor);
+            }
+            tile.tile_status = status::with_inflicts(tile.tile_status, player.gear_status());
+
+            self.save_player_tile(player);
+        }
+    }
+
+    pub fn apply_profile(&mut self, player: &mut PlayerData) {
+        if let Some((player_x, player_y)) = self.find_player_position(player.authority) {
+            self.data[player_x][player_y].tile_avatar = player.avatar;
+            self.data[player_x][player_y].tile_name = name_registry::name_tag(&player.name);
+
+            self.save_player_tile(player);
+        }
+    }
+
+    fn tick_status(&mut self, x: usize, y: usize) {
+        let damage = status::tick(&mut self.data[x][y]);
+        if damage > 0 {
+            self.log_action(ACTION_TYPE_STATUS_DAMAGE, x, y, x, y, damage as u64);
+        }
+    }
+
+    fn log_action(
+        &mut self,
+        action_type: u8,
+        from_x: usize,
+        from_y: usize,
+        to_x: usize,
+        to_y: usize,
+        amount: u64,
+    ) {
+        let new_game_action = GameAction {
+            action_id: self.id_counter,
+            action_type,
+            from_x: from_x as u8,
+            from_y: from_y as u8,
+            to_x: to_x as u8,
+            to_y: to_y as u8,
+            tile: self.data[to_x][to_y],
+            amount,
+        };
+
+        self.add_new_game_action(new_game_action);
+    }
+
+    // Floors created by init_if_needed start zeroed and take the floor id of
+    // their address. After that the address and floor_id have to match.
+    pub fn check_floor(&mut self, owner: Pubkey, floor: &FloorAddress) -> Result<()> {
+        if self.owner == Pubkey::default() {
+            self.owner = owner;
+            self.floor_id = floor.floor_id;
+        }
+        if self.floor_id != floor.floor_id {
+            return Err(GameErrorCode::WrongFloor.into());
+        }
+        Ok(())
+    }
+
     pub fn reset_game(&mut self) -> Result<()> {
-        // TOOD: Make it so not everyone can just call this
         for i in 0..BOARD_SIZE_X {
             for j in 0..BOARD_SIZE_Y {
-                self.data[i][j].tile_type = STATE_EMPTY;
+                vacate(&mut self.data[i][j]);
             }
         }
 
-        self.game_actions = [GameAction::default(); 20];
+        self.game_actions.fill(GameAction::default());
         self.action_index = 0;
 
         Ok(())
@@ -450,8 +1002,7 @@ impl GameData {
             for j in 0..BOARD_SIZE_Y {
                 if self.data[i][j].tile_owner == player && self.data[i][j].tile_type == STATE_PLAYER
                 {
-                    self.data[i][j].tile_type = STATE_EMPTY;
-                    self.data[i][j].tile_owner = Pubkey::default();
+                    vacate(&mut self.data[i][j]);
                     msg!("Player removed");
                 }
             }
@@ -469,11 +1020,161 @@ impl GameData {
                 }
             }
         }
-
-        return TileData::default();
+
+        TileData::default()
+    }
+
+    pub fn is_daily(&self) -> bool {
+        self.mode == GAME_MODE_DAILY
+    }
+
+    pub fn count_players(&self) -> usize {
+        self.data
+            .iter()
+            .flatten()
+            .filter(|tile| tile.tile_type == STATE_PLAYER)
+            .count()
+    }
+
+    // Joins an instance that is already running. Unlike spawn_player this
+    // does not add enemies, chests or stairs.
+    pub fn place_player(&mut self, player: Pubkey, player_tile: TileData) -> Result<()> {
+        if self.find_player_position(player).is_some() {
+            return Err(GameErrorCode::PlayerAlreadyExists.into());
+        }
+        if self.count_players() >= MAX_PLAYERS_PER_INSTANCE {
+            return Err(GameErrorCode::InstanceFull.into());
+        }
+
+        let mut empty_slots: Vec<(usize, usize)> = Vec::new();
+        for i in 0..BOARD_SIZE_X {
+            for j in 0..BOARD_SIZE_Y {
+                if self.data[i][j].tile_type == STATE_EMPTY {
+                    empty_slots.push((i, j));
+                }
+            }
+        }
+        if empty_slots.is_empty() {
+            return Err(GameErrorCode::BoardIsFull.into());
+        }
+
+        let mut rng = XorShift64::from_slot(empty_slots.len() as u64)?;
+        self.spawn_tile(&mut empty_slots, &mut rng, player_tile);
+        Ok(())
+    }
+
+    pub fn find_player_position(&self, player: Pubkey) -> Option<(usize, usize)> {
+        for i in 0..BOARD_SIZE_X {
+            for j in 0..BOARD_SIZE_Y {
+                if self.data[i][j].tile_owner == player && self.data[i][j].tile_type == STATE_PLAYER
+                {
+                    return Some((i, j));
+                }
+            }
+        }
+
+        None
+    }
+
+    pub fn spawn_player(
+        &mut self,
+        player: Pubkey,
+        last_tile: TileData,
+        boss_floor: bool,
+    ) -> Result<()> {
+        self.spawn_player_seeded(player, last_tile, boss_floor, None)
+    }
+
+    // Builds a fresh daily floor. The layout only depends on the seed and the
+    // player starts with the base stats of their class, so every player gets
+    // the same challenge.
+    pub fn generate_daily_floor(
+        &mut self,
+        player: &PlayerData,
+        depth: u32,
+        seed: u64,
+    ) -> Result<()> {
+        self.reset_game()?;
+        self.mode = GAME_MODE_DAILY;
+        self.floor_id = depth;
+        self.owner = player.authority;
+
+        let stats = player_class::base_stats(player.class);
+        let player_tile = TileData {
+            tile_owner: player.authority,
+            tile_type: STATE_PLAYER,
+            tile_health: stats.max_health,
+            tile_max_health: stats.max_health,
+            tile_damage: stats.damage,
+            tile_defence: stats.defence,
+            tile_armor: stats.armor,
+            tile_max_armor: stats.armor,
+            tile_level: 1,
+            tile_crit_chance: stats.crit_chance,
+            tile_crit_multiplier: stats.crit_multiplier,
+            tile_evasion: stats.evasion,
+            tile_damage_range: stats.damage_range,
+            tile_class: player.class,
+            tile_avatar: player.avatar,
+            tile_name: name_registry::name_tag(&player.name),
+            ..Default::default()
+        };
+
+        self.spawn_player_seeded(player.authority, player_tile, false, Some(seed))
+    }
+
+    // Daily floors carry the player tile over to the next depth
+    pub fn next_daily_floor(&mut self, player: &PlayerData, depth: u32, seed: u64) -> Result<()> {
+        let (player_x, player_y) = self
+            .find_player_position(player.authority)
+            .ok_or(GameErrorCode::PlayerNotOnBoard)?;
+        if !self.is_next_to(player_x, player_y, STATE_STAIRS) {
+            return Err(GameErrorCode::NotNextToStairs.into());
+        }
+        let player_tile = self.data[player_x][player_y];
+
+        self.reset_game()?;
+        self.floor_id = depth;
+        self.spawn_player_seeded(player.authority, player_tile, false, Some(seed))
+    }
+
+    // Leaving the floor only works from a tile next to the stairs
+    pub fn take_stairs(&mut self, player: Pubkey) -> Result<()> {
+        let (player_x, player_y) = self
+            .find_player_position(player)
+            .ok_or(GameErrorCode::PlayerNotOnBoard)?;
+        if !self.is_next_to(player_x, player_y, STATE_STAIRS) {
+            return Err(GameErrorCode::NotNextToStairs.into());
+        }
+
+        self.remove_player(player)
+    }
+
+    pub fn is_next_to_merchant(&self, player: Pubkey) -> bool {
+        match self.find_player_position(player) {
+            Some((x, y)) => self.is_next_to(x, y, STATE_MERCHANT),
+            None => false,
+        }
+    }
+
+    fn is_next_to(&self, x: usize, y: usize, tile_type: u8) -> bool {
+        for i in x.saturating_sub(1)..=cmp::min(x + 1, BOARD_SIZE_X - 1) {
+            for j in y.saturating_sub(1)..=cmp::min(y + 1, BOARD_SIZE_Y - 1) {
+                if self.data[i][j].tile_type == tile_type {
+                    return true;
+                }
+            }
+        }
+        false
     }
 
-    pub fn spawn_player(&mut self, player: Pubkey, lastTile: TileData) -> Result<()> {
+    fn spawn_player_seeded(
+        &mut self,
+        player: Pubkey,
+        last_tile: TileData,
+        boss_floor: bool,
+        seed: Option<u64>,
+    ) -> Result<()> {
         let mut empty_slots: Vec<(usize, usize)> = Vec::new();
 
         for i in 0..BOARD_SIZE_X {
@@ -492,9 +1193,11 @@ impl GameData {
             return Err(GameErrorCode::BoardIsFull.into());
         }
 
-        let slot = Clock::get()?.slot;
-        let mut rng = XorShift64 {
-            a: empty_slots.len() as u64 + slot,
+        let mut rng = match seed {
+            Some(seed) => XorShift64 { a: seed },
+            None => XorShift64 {
+                a: empty_slots.len() as u64 + Clock::get()?.slot,
+            },
         };
 
         let random_index = (rng.next() % (empty_slots.len() as u64)) as usize;
@@ -505,14 +1208,13 @@ impl GameData {
             random_empty_slot.1
         );
 
-        self.data[random_empty_slot.0][random_empty_slot.1] = lastTile;
+        self.data[random_empty_slot.0][random_empty_slot.1] = last_tile;
 
         empty_slots.remove(random_index);
 
-        for i in 1..=3 {
-            println!("Spawn enem: {}", i);
+        for _ in 1..=3 {
             // Spawn enemy
-            if empty_slots.len() > 0 {
+            if !empty_slots.is_empty() {
                 let random_index = (rng.next() % (empty_slots.len() as u64)) as usize;
                 let random_empty_slot = empty_slots[random_index];
                 msg!(
@@ -524,7 +1226,7 @@ impl GameData {
                 empty_slots.remove(random_index);
 
                 self.data[random_empty_slot.0][random_empty_slot.1] = TileData {
-                    tile_owner: player.clone(),
+                    tile_owner: player,
                     tile_type: STATE_ENEMY,
                     tile_health: self.floor_id + 5,
                     tile_max_health: self.floor_id + 5,
@@ -534,12 +1236,17 @@ impl GameData {
                     tile_max_armor: self.floor_id + 1,
                     tile_level: self.floor_id + 1,
                     tile_xp: self.floor_id + 1,
+                    tile_crit_chance: ENEMY_CRIT_CHANCE,
+                    tile_crit_multiplier: ENEMY_CRIT_MULTIPLIER,
+                    tile_evasion: ENEMY_EVASION,
+                    tile_damage_range: ENEMY_DAMAGE_RANGE,
+                    tile_status: status::roll_archetype(&mut rng, self.floor_id),
                     ..Default::default()
                 };
             }
         }
 
-        if empty_slots.len() > 0 {
+        if !empty_slots.is_empty() {
             let random_index = (rng.next() % (empty_slots.len() as u64)) as usize;
             let random_empty_slot = empty_slots[random_index];
             msg!(
@@ -551,7 +1258,7 @@ impl GameData {
             empty_slots.remove(random_index);
 
             self.data[random_empty_slot.0][random_empty_slot.1] = TileData {
-                tile_owner: player.clone(),
+                tile_owner: player,
                 tile_type: STATE_STAIRS,
                 tile_health: self.floor_id + 5,
                 tile_max_health: self.floor_id + 5,
@@ -566,7 +1273,7 @@ impl GameData {
         }
 
         // Spawn chest
-        if empty_slots.len() > 0 {
+        if !empty_slots.is_empty() {
             let random_index = (rng.next() % (empty_slots.len() as u64)) as usize;
             let random_empty_slot = empty_slots[random_index];
             msg!(
@@ -578,7 +1285,7 @@ impl GameData {
             empty_slots.remove(random_index);
 
             self.data[random_empty_slot.0][random_empty_slot.1] = TileData {
-                tile_owner: player.clone(),
+                tile_owner: player,
                 tile_type: STATE_CHEST_GOLD,
                 tile_health: 0,
                 tile_damage: 0,
@@ -592,7 +1299,7 @@ impl GameData {
         let random_index = (rng.next() % (100)) as usize;
         msg!("Random index chest: {}", random_index);
         // Spawn Super chest
-        if empty_slots.len() > 0 && random_index > 50 && self.floor_id > 0 {
+        if !empty_slots.is_empty() && random_index > 50 && self.floor_id > 0 {
             let random_index = (rng.next() % (empty_slots.len() as u64)) as usize;
             let random_empty_slot = empty_slots[random_index];
             msg!(
@@ -604,7 +1311,7 @@ impl GameData {
             empty_slots.remove(random_index);
 
             self.data[random_empty_slot.0][random_empty_slot.1] = TileData {
-                tile_owner: player.clone(),
+                tile_owner: player,
                 tile_type: STATE_CHEST_BLUE,
                 tile_health: 0,
                 tile_damage: 0,
@@ -615,9 +1322,227 @@ impl GameData {
             };
         }
 
+        let floor_id = self.floor_id;
+        self.spawn_interactive_tiles(&mut empty_slots, &mut rng, player, floor_id);
+
+        // Only one boss per floor, no matter how many players join it
+        let has_boss = self
+            .data
+            .iter()
+            .flatten()
+            .any(|tile| tile.tile_type == STATE_BOSS);
+        if boss_floor && !has_boss {
+            self.spawn_tile(
+                &mut empty_slots,
+                &mut rng,
+                TileData {
+                    tile_owner: player,
+                    tile_type: STATE_BOSS,
+                    tile_health: BossData::health_for_floor(floor_id),
+                    tile_max_health: BossData::health_for_floor(floor_id),
+                    tile_damage: BossData::damage_for_floor(floor_id),
+                    tile_defence: BossData::defence_for_floor(floor_id),
+                    tile_level: floor_id + 1,
+                    ..Default::default()
+                },
+            );
+        }
+
+        Ok(())
+    }
+
+    // One round of a boss fight. The player hits first and the boss strikes
+    // back if it survives. Players have to stand next to the boss tile.
+    pub fn attack_boss(&mut self, player: &mut PlayerData, boss: &mut BossData) -> Result<()> {
+        let (player_x, player_y) = self
+            .find_player_position(player.authority)
+            .ok_or(GameErrorCode::PlayerNotOnBoard)?;
+        let (boss_x, boss_y) = self
+            .find_boss_position()
+            .ok_or(GameErrorCode::NotABossFloor)?;
+
+        if player_x.abs_diff(boss_x) > 1 || player_y.abs_diff(boss_y) > 1 {
+            return Err(GameErrorCode::NotNextToBoss.into());
+        }
+
+        // The boss tile carries the stats of the boss account, so its fight
+        // rolls crits, evasion and status effects like any other
+        let boss_tile = &mut self.data[boss_x][boss_y];
+        boss_tile.tile_health = boss.health;
+        boss_tile.tile_max_health = boss.max_health;
+        boss_tile.tile_damage = boss.damage;
+        boss_tile.tile_defence = boss.defence;
+        let mut rng = XorShift64::from_slot(boss.total_damage)?;
+
+        // Status effects of both sides tick on every attack
+        self.tick_status(player_x, player_y);
+        self.tick_status(boss_x, boss_y);
+        if self.data[player_x][player_y].tile_health > 0 {
+            let roll = self.resolve_attack((player_x, player_y), (boss_x, boss_y), &mut rng);
+            self.log_action(
+                roll.action_type(ACTION_TYPE_BOSS_ATTACK),
+                player_x,
+                player_y,
+                boss_x,
+                boss_y,
+                roll.damage as u64,
+            );
+        }
+
+        let damage_dealt = boss.health - self.data[boss_x][boss_y].tile_health;
+        boss.health -= damage_dealt;
+        boss.add_damage(player.authority, damage_dealt);
+        msg!("Boss hit for {} health left {}", damage_dealt, boss.health);
+
+        if boss.health == 0 {
+            vacate(&mut self.data[boss_x][boss_y]);
+            let new_game_action = GameAction {
+                action_id: self.id_counter,
+                action_type: ACTION_TYPE_BOSS_DEFEATED,
+                from_x: player_x as u8,
+                from_y: player_y as u8,
+                to_x: boss_x as u8,
+                to_y: boss_y as u8,
+                tile: self.data[boss_x][boss_y],
+                amount: boss.reward_xp as u64,
+            };
+            self.add_new_game_action(new_game_action);
+            msg!("Boss defeated");
+        } else if self.data[player_x][player_y].tile_health > 0 {
+            let roll = self.resolve_attack((boss_x, boss_y), (player_x, player_y), &mut rng);
+            self.log_action(
+                roll.action_type(ACTION_TYPE_ENEMY_ATTACK),
+                boss_x,
+                boss_y,
+                player_x,
+                player_y,
+                roll.damage as u64,
+            );
+        }
+
+        player.tile_data.tile_health = self.data[player_x][player_y].tile_health;
+        player.tile_data.tile_armor = self.data[player_x][player_y].tile_armor;
+
+        if self.data[player_x][player_y].tile_health == 0 {
+            kill_player(player, self, player_x, player_y);
+        }
+
         Ok(())
     }
 
+    fn find_boss_position(&self) -> Option<(usize, usize)> {
+        for i in 0..BOARD_SIZE_X {
+            for j in 0..BOARD_SIZE_Y {
+                if self.data[i][j].tile_type == STATE_BOSS {
+                    return Some((i, j));
+                }
+            }
+        }
+
+        None
+    }
+
+    // Traps only show up from the second floor on so new players don't die on
+    // their first steps. Merchants stand on the last floor of every
+    // MERCHANT_FLOOR_INTERVAL floors. Everything else is a coin flip per floor.
+    fn spawn_interactive_tiles(
+        &mut self,
+        empty_slots: &mut Vec<(usize, usize)>,
+        rng: &mut XorShift64,
+        owner: Pubkey,
+        floor: u32,
+    ) {
+        if floor > 0 && rng.next() % 100 > 50 {
+            self.spawn_tile(
+                empty_slots,
+                rng,
+                TileData {
+                    tile_owner: owner,
+                    tile_type: STATE_TRAP,
+                    tile_damage: floor + 1,
+                    tile_level: floor + 1,
+                    ..Default::default()
+                },
+            );
+        }
+
+        if rng.next() % 100 > 70 {
+            self.spawn_tile(
+                empty_slots,
+                rng,
+                TileData {
+                    tile_owner: owner,
+                    tile_type: STATE_FOUNTAIN,
+                    tile_level: floor + 1,
+                    ..Default::default()
+                },
+            );
+        }
+
+        if rng.next() % 100 > 80 {
+            self.spawn_tile(
+                empty_slots,
+                rng,
+                TileData {
+                    tile_owner: owner,
+                    tile_type: STATE_SHRINE,
+                    tile_damage: SHRINE_DAMAGE_BONUS,
+                    tile_level: floor + 1,
+                    ..Default::default()
+                },
+            );
+        }
+
+        if rng.next() % 100 > 60 {
+            self.spawn_tile(
+                empty_slots,
+                rng,
+                TileData {
+                    tile_owner: owner,
+                    tile_type: STATE_DOOR,
+                    tile_level: floor + 1,
+                    ..Default::default()
+                },
+            );
+        }
+
+        let has_merchant = self
+            .data
+            .iter()
+            .flatten()
+            .any(|tile| tile.tile_type == STATE_MERCHANT);
+        if floor % MERCHANT_FLOOR_INTERVAL == MERCHANT_FLOOR_INTERVAL - 1 && !has_merchant {
+            self.spawn_tile(
+                empty_slots,
+                rng,
+                TileData {
+                    tile_owner: owner,
+                    tile_type: STATE_MERCHANT,
+                    tile_level: floor + 1,
+                    ..Default::default()
+                },
+            );
+        }
+    }
+
+    fn spawn_tile(
+        &mut self,
+        empty_slots: &mut Vec<(usize, usize)>,
+        rng: &mut XorShift64,
+        tile: TileData,
+    ) {
+        if empty_slots.is_empty() {
+            return;
+        }
+
+        let random_index = (rng.next() % (empty_slots.len() as u64)) as usize;
+        let (x, y) = empty_slots.remove(random_index);
+        let tile_type = tile.tile_type;
+        msg!("Tile {} spawn at {} {}", tile_type, x, y);
+
+        self.data[x][y] = tile;
+    }
+
     pub fn add_new_game_action(&mut self, game_action: GameAction) {
         {
             let option_add = self.id_counter.checked_add(1);
@@ -630,39 +1555,92 @@ impl GameData {
                 }
             }
         }
-        self.action_index = (self.action_index + 1) % 20;
+        self.action_index = (self.action_index + 1) % GAME_ACTIONS as u64;
         self.game_actions[self.action_index as usize] = game_action;
     }
 }
 
 fn open_chest(
-    playerData: &mut PlayerData,
+    player_data: &mut PlayerData,
+    daily: bool,
     tile_datas: &mut [[TileData; BOARD_SIZE_X]; BOARD_SIZE_Y],
     player_pos_x: usize,
     player_pos_y: usize,
     chest_pos_x: usize,
     chest_pos_y: usize,
 ) -> Result<()> {
+    // Lucky players get the stats of the chest twice
+    let luck_chance = skills::chest_luck_chance(player_data.skills);
+    let rewards = if !daily
+        && luck_chance > 0
+        && XorShift64::for_player(player_data)?.next() % 100 < luck_chance
+    {
+        msg!("Lucky chest");
+        2
+    } else {
+        1
+    };
+
     // TODO: Add chest balancing
     if tile_datas[chest_pos_x][chest_pos_y].tile_type == STATE_CHEST_GOLD {
-        tile_datas[player_pos_x][player_pos_y].tile_damage += 1;
-        tile_datas[player_pos_x][player_pos_y].tile_defence += 1;
-        tile_datas[player_pos_x][player_pos_y].tile_health += 1;
-        tile_datas[player_pos_x][player_pos_y].tile_max_health += 1;
+        // Gold chests patch up the armor
+        tile_datas[player_pos_x][player_pos_y].tile_armor = cmp::min(
+            tile_datas[player_pos_x][player_pos_y].tile_armor + CHEST_ARMOR * rewards,
+            tile_datas[player_pos_x][player_pos_y].tile_max_armor,
+        );
+        tile_datas[player_pos_x][player_pos_y].tile_damage += rewards;
+        tile_datas[player_pos_x][player_pos_y].tile_defence += rewards;
+        tile_datas[player_pos_x][player_pos_y].tile_health += rewards;
+        tile_datas[player_pos_x][player_pos_y].tile_max_health += rewards;
+    }
+
+    if tile_datas[chest_pos_x][chest_pos_y].tile_type == STATE_CHEST_BLUE {
+        tile_datas[player_pos_x][player_pos_y].tile_damage += rewards;
+        tile_datas[player_pos_x][player_pos_y].tile_defence += rewards;
+        tile_datas[player_pos_x][player_pos_y].tile_health += rewards;
+        tile_datas[player_pos_x][player_pos_y].tile_max_health += rewards;
+
+        tile_datas[player_pos_x][player_pos_y].tile_max_armor += rewards;
+        tile_datas[player_pos_x][player_pos_y].tile_armor += rewards;
+    }
+
+    // Only the tile gets the stats on a daily floor
+    if daily {
+        return move_player(
+            tile_datas,
+            player_pos_x,
+            player_pos_y,
+            chest_pos_x,
+            chest_pos_y,
+        );
     }
+    player_data.record_chest();
 
     if tile_datas[chest_pos_x][chest_pos_y].tile_type == STATE_CHEST_BLUE {
-        tile_datas[player_pos_x][player_pos_y].tile_damage += 1;
-        tile_datas[player_pos_x][player_pos_y].tile_defence += 1;
-        tile_datas[player_pos_x][player_pos_y].tile_health += 1;
-        tile_datas[player_pos_x][player_pos_y].tile_max_health += 1;
+        player_data.damage += rewards;
+        player_data.defence += rewards;
+        player_data.max_health += rewards;
+        player_data.max_armor += rewards;
+
+        // Some blue chests hold gear that can be withdrawn as an NFT
+        let mut rng = XorShift64::for_player(player_data)?;
+        if rng.next() % 100 < GEAR_DROP_CHANCE {
+            let gear = item::roll_gear(&mut rng, player_data.current_floor);
+            match player_data.add_gear(gear) {
+                Ok(()) => msg!("Found gear of rarity {}", gear.rarity),
+                Err(_) => msg!("Found gear but the inventory is full"),
+            }
+        }
+    }
 
-        playerData.damage += 1;
-        playerData.defence += 1;
-        playerData.health += 1;
-        playerData.max_health += 1;
+    if tile_datas[chest_pos_x][chest_pos_y].tile_type == STATE_CHEST_GOLD {
+        player_data.health_potions += 1;
     }
 
+    // Every chest holds a key for the locked doors on the floors
+    player_data.keys += 1;
+    player_data.drop_material(ITEM_CRYSTAL, CHEST_MATERIAL_CHANCE)?;
+
     move_player(
         tile_datas,
         player_pos_x,
@@ -681,140 +1659,406 @@ fn move_player(
     new_pos_x: usize,
     new_pos_y: usize,
 ) -> Result<()> {
-    tile_datas[new_pos_x][new_pos_y].tile_owner = tile_datas[player_pos_x][player_pos_y].tile_owner;
-    tile_datas[new_pos_x][new_pos_y].tile_health =
-        tile_datas[player_pos_x][player_pos_y].tile_health;
-    tile_datas[new_pos_x][new_pos_y].tile_max_health =
-        tile_datas[player_pos_x][player_pos_y].tile_max_health;
-    tile_datas[new_pos_x][new_pos_y].tile_damage =
-        tile_datas[player_pos_x][player_pos_y].tile_damage;
-    tile_datas[new_pos_x][new_pos_y].tile_defence =
-        tile_datas[player_pos_x][player_pos_y].tile_defence;
-    tile_datas[new_pos_x][new_pos_y].tile_armor = tile_datas[player_pos_x][player_pos_y].tile_armor;
-    tile_datas[new_pos_x][new_pos_y].tile_max_armor =
-        tile_datas[player_pos_x][player_pos_y].tile_max_armor;
-    tile_datas[new_pos_x][new_pos_y].tile_defence =
-        tile_datas[player_pos_x][player_pos_y].tile_defence;
-    tile_datas[new_pos_x][new_pos_y].tile_level = tile_datas[player_pos_x][player_pos_y].tile_level;
-    tile_datas[new_pos_x][new_pos_y].tile_xp = tile_datas[player_pos_x][player_pos_y].tile_xp;
+    // The whole tile moves, the old one stays behind as an empty tile
+    tile_datas[new_pos_x][new_pos_y] = tile_datas[player_pos_x][player_pos_y];
 
     msg!("Player moved to: {} {}", new_pos_x, new_pos_y);
 
     tile_datas[new_pos_x][new_pos_y].tile_type = STATE_PLAYER;
-    tile_datas[player_pos_x][player_pos_y].tile_type = STATE_EMPTY;
+    vacate(&mut tile_datas[player_pos_x][player_pos_y]);
 
     Ok(())
 }
 
 fn fight_enemy(
-    playerData: &mut PlayerData,
-    gameData: &mut GameData,
+    player_data: &mut PlayerData,
+    game_data: &mut GameData,
     player_pos_x: usize,
     player_pos_y: usize,
     enemy_x: usize,
     enemy_y: usize,
 ) -> Result<()> {
-    gameData.data[player_pos_x][player_pos_y].tile_armor =
-        gameData.data[player_pos_x][player_pos_y].tile_max_armor;
-    gameData.data[enemy_x][enemy_y].tile_armor = gameData.data[enemy_x][enemy_y].tile_max_armor;
+    game_data.data[enemy_x][enemy_y].tile_armor = game_data.data[enemy_x][enemy_y].tile_max_armor;
+
+    let mut rng = XorShift64::from_slot(game_data.id_counter)?;
 
-    while gameData.data[player_pos_x][player_pos_y].tile_health > 0
-        && gameData.data[enemy_x][enemy_y].tile_health > 0
+    // Two tanky players can not hurt each other, the fight ends undecided
+    // after a few rounds instead of running out of compute
+    let mut rounds = 0;
+    while game_data.data[player_pos_x][player_pos_y].tile_health > 0
+        && game_data.data[enemy_x][enemy_y].tile_health > 0
+        && rounds < MAX_PVP_ROUNDS
     {
-        let player_damage = gameData.data[enemy_x][enemy_y]
-            .tile_damage
-            .saturating_sub(gameData.data[player_pos_x][player_pos_y].tile_defence);
-        let enemy_damage = gameData.data[player_pos_x][player_pos_y]
-            .tile_damage
-            .saturating_sub(gameData.data[enemy_x][enemy_y].tile_defence);
-
-        if gameData.data[player_pos_x][player_pos_y].tile_armor > 0 {
-            gameData.data[player_pos_x][player_pos_y].tile_armor = gameData.data[player_pos_x]
-                [player_pos_y]
-                .tile_armor
-                .saturating_sub(cmp::max(
-                    cmp::min(
-                        player_damage,
-                        gameData.data[player_pos_x][player_pos_y].tile_armor,
-                    ),
-                    1,
-                ));
-        } else {
-            gameData.data[player_pos_x][player_pos_y].tile_health = gameData.data[player_pos_x]
-                [player_pos_y]
-                .tile_health
-                .saturating_sub(cmp::max(
-                    cmp::min(
-                        player_damage,
-                        gameData.data[player_pos_x][player_pos_y].tile_health,
-                    ),
-                    1,
-                ));
-        }
-
-        if gameData.data[enemy_x][enemy_y].tile_armor > 0 {
-            gameData.data[enemy_x][enemy_y].tile_armor = gameData.data[enemy_x][enemy_y]
-                .tile_armor
-                .saturating_sub(cmp::max(
-                    cmp::min(enemy_damage, gameData.data[enemy_x][enemy_y].tile_armor),
-                    1,
-                ));
+        game_data.resolve_attack((enemy_x, enemy_y), (player_pos_x, player_pos_y), &mut rng);
+        game_data.resolve_attack((player_pos_x, player_pos_y), (enemy_x, enemy_y), &mut rng);
+        game_data.tick_status(player_pos_x, player_pos_y);
+        game_data.tick_status(enemy_x, enemy_y);
+        rounds += 1;
+    }
+
+    if game_data.data[player_pos_x][player_pos_y].tile_health == 0 {
+        kill_player(player_data, game_data, player_pos_x, player_pos_y);
+    } else if game_data.data[enemy_x][enemy_y].tile_health > 0 {
+        msg!("Fight undecided after {} rounds", rounds);
+    } else {
+        win_fight(
+            player_data,
+            game_data,
+            player_pos_x,
+            player_pos_y,
+            enemy_x,
+            enemy_y,
+        )?;
+    }
+
+    Ok(())
+}
+
+fn win_fight(
+    player_data: &mut PlayerData,
+    game_data: &mut GameData,
+    player_pos_x: usize,
+    player_pos_y: usize,
+    enemy_x: usize,
+    enemy_y: usize,
+) -> Result<()> {
+    msg!("Enemy killed");
+    // Daily kills only level up the daily tile
+    if !game_data.is_daily() {
+        player_data.record_kill();
+        player_data.drop_material(ITEM_SCRAP, ENEMY_MATERIAL_CHANCE)?;
+        player_data.add_xp(game_data.data[enemy_x][enemy_y].tile_level + 1);
+    }
+
+    game_data.data[player_pos_x][player_pos_y].tile_xp +=
+        game_data.data[enemy_x][enemy_y].tile_level + 1;
+
+    let growth = player_class::growth(game_data.data[player_pos_x][player_pos_y].tile_class);
+    while game_data.data[player_pos_x][player_pos_y].tile_xp
+        >= 5 * game_data.data[player_pos_x][player_pos_y].tile_level
+    {
+        game_data.data[player_pos_x][player_pos_y].tile_xp -=
+            5 * game_data.data[player_pos_x][player_pos_y].tile_level;
+        game_data.data[player_pos_x][player_pos_y].tile_level += 1;
+        game_data.data[player_pos_x][player_pos_y].tile_max_health += growth.max_health;
+        game_data.data[player_pos_x][player_pos_y].tile_health =
+            game_data.data[player_pos_x][player_pos_y].tile_max_health;
+        game_data.data[player_pos_x][player_pos_y].tile_damage += growth.damage;
+    }
+
+    move_player(
+        &mut game_data.data,
+        player_pos_x,
+        player_pos_y,
+        enemy_x,
+        enemy_y,
+    )
+}
+
+// Base damage of a single hit. Armor and the minimum of 1 damage are handled
+// in apply_damage.
+fn attack_damage(attacker: &TileData, defender: &TileData) -> u32 {
+    attacker
+        .tile_damage
+        .saturating_sub(status::defence(defender))
+}
+
+struct AttackRoll {
+    damage: u32,
+    evaded: bool,
+    critical: bool,
+    stunned: bool,
+}
+
+impl AttackRoll {
+    // Stuns, evaded and critical hits get their own action so clients can
+    // show them.
+    fn action_type(&self, hit_action_type: u8) -> u8 {
+        if self.stunned {
+            ACTION_TYPE_STUNNED
+        } else if self.evaded {
+            ACTION_TYPE_EVADED
+        } else if self.critical {
+            ACTION_TYPE_CRITICAL_HIT
         } else {
-            gameData.data[enemy_x][enemy_y].tile_health = gameData.data[enemy_x][enemy_y]
-                .tile_health
-                .saturating_sub(cmp::max(
-                    cmp::min(enemy_damage, gameData.data[enemy_x][enemy_y].tile_health),
-                    1,
-                ));
+            hit_action_type
         }
     }
+}
 
-    if gameData.data[player_pos_x][player_pos_y].tile_health == 0 {
-        gameData.data[player_pos_x][player_pos_y].tile_type = STATE_EMPTY;
-        let new_game_action = GameAction {
-            action_id: gameData.id_counter,
-            action_type: ACTION_TYPE_PLAYER_DIED,
-            from_x: player_pos_x as u8,
-            from_y: player_pos_y as u8,
-            to_x: player_pos_x as u8,
-            to_y: player_pos_y as u8,
-            tile: gameData.data[player_pos_x as usize][player_pos_y as usize],
-            amount: 0,
+// Evasion is rolled first. A hit then varies around the base damage by the
+// damage range of the attacker and can turn into a critical hit. A crit
+// multiplier below 100 percent is treated as 100 so old tiles never crit for
+// less.
+fn roll_attack(attacker: &TileData, defender: &TileData, rng: &mut XorShift64) -> AttackRoll {
+    let evasion = defender.tile_evasion as u64;
+    let evade_roll = rng.next() % 100;
+    if evade_roll < evasion {
+        msg!("Evade roll {} below {}", evade_roll, evasion);
+        return AttackRoll {
+            damage: 0,
+            evaded: true,
+            critical: false,
+            stunned: false,
         };
-        playerData.current_floor = 0;
-        playerData.xp = 0;
-        playerData.level = 0;
-        gameData.add_new_game_action(new_game_action);
-        msg!("Player died");
+    }
+
+    let range = attacker.tile_damage_range as u64;
+    let variance_roll = rng.next() % (2 * range + 1);
+    let mut damage =
+        (attack_damage(attacker, defender) as u64 + variance_roll).saturating_sub(range) as u32;
+
+    let crit_chance = attacker.tile_crit_chance as u64;
+    let crit_roll = rng.next() % 100;
+    let critical = crit_roll < crit_chance;
+    if critical {
+        damage = damage * cmp::max(attacker.tile_crit_multiplier as u32, 100) / 100;
+    }
+
+    msg!(
+        "Rolls evade {} variance {} crit {} damage {}",
+        evade_roll,
+        variance_roll,
+        crit_roll,
+        damage
+    );
+
+    AttackRoll {
+        damage,
+        evaded: false,
+        critical,
+        stunned: false,
+    }
+}
+
+// Getting away is easier from weaker enemies and with more health left. It is
+// never a sure thing either way.
+fn flee_chance(player: &TileData, enemy: &TileData) -> u64 {
+    let level_difference = player.tile_level as i64 - enemy.tile_level as i64;
+    let health_bonus = player.tile_health as i64 * 20 / cmp::max(player.tile_max_health, 1) as i64;
+    (FLEE_BASE_CHANCE as i64 + level_difference * 10 + health_bonus).clamp(10, 90) as u64
+}
+
+// Whatever armor was raised by defending is gone once the fight is over.
+// Raises the armor for the strike of the enemy this round. Defending again
+// does not stack, the armor goes up to the maximum plus the bonus at most.
+fn defend(player_tile: &mut TileData) -> u32 {
+    let bonus = player_tile.tile_defence + DEFEND_ARMOR_BONUS;
+    player_tile.tile_armor = cmp::min(
+        player_tile.tile_armor + bonus,
+        player_tile.tile_max_armor + bonus,
+    );
+    bonus
+}
+
+// Armor above the maximum from DEFEND only lasts for one round
+fn drop_defend_bonus(player_tile: &mut TileData) {
+    player_tile.tile_armor = cmp::min(player_tile.tile_armor, player_tile.tile_max_armor);
+}
+
+fn end_encounter(player_data: &mut PlayerData, player_tile: &mut TileData) {
+    player_tile.tile_armor = cmp::min(player_tile.tile_armor, player_tile.tile_max_armor);
+    player_data.encounter = Encounter::default();
+}
+
+// An empty tile has no owner. Owners take a slot in the player table of the
+// floor account as long as a tile points to them.
+fn vacate(tile: &mut TileData) {
+    tile.tile_type = STATE_EMPTY;
+    tile.tile_owner = Pubkey::default();
+}
+
+// Armor soaks up hits before health does. Every hit does at least 1 damage.
+fn apply_damage(tile: &mut TileData, damage: u32) {
+    if tile.tile_armor > 0 {
+        tile.tile_armor = tile
+            .tile_armor
+            .saturating_sub(cmp::max(cmp::min(damage, tile.tile_armor), 1));
     } else {
-        msg!("Enemy killed");
-        playerData.add_xp(gameData.data[enemy_x][enemy_y].tile_level + 1);
+        tile.tile_health = tile
+            .tile_health
+            .saturating_sub(cmp::max(cmp::min(damage, tile.tile_health), 1));
+    }
+}
+
+fn kill_player(
+    player_data: &mut PlayerData,
+    game_data: &mut GameData,
+    player_pos_x: usize,
+    player_pos_y: usize,
+) {
+    game_data.data[player_pos_x][player_pos_y].tile_type = STATE_EMPTY;
+    // The action keeps the owner so clients know who died, the board does not
+    let new_game_action = GameAction {
+        action_id: game_data.id_counter,
+        action_type: ACTION_TYPE_PLAYER_DIED,
+        from_x: player_pos_x as u8,
+        from_y: player_pos_y as u8,
+        to_x: player_pos_x as u8,
+        to_y: player_pos_y as u8,
+        tile: game_data.data[player_pos_x][player_pos_y],
+        amount: 0,
+    };
+    vacate(&mut game_data.data[player_pos_x][player_pos_y]);
+    player_data.encounter = Encounter::default();
+    if !game_data.is_daily() {
+        player_data.clear_buffs();
+        // The blessing ends with the run, reset_tile drops its damage bonus
+        player_data.shrine_buff_moves = 0;
+        player_data.shrine_damage_bonus = 0;
+        player_data.current_floor = 0;
+        player_data.xp = 0;
+        player_data.level = 0;
+        player_data.record_death();
+        // Back in the lobby the carried tile is built again from the stats
+        player_data.reset_tile();
+    }
+    game_data.add_new_game_action(new_game_action);
+    msg!("Player died");
+}
+
+// Spikes ignore armor and go straight to health. The trap is used up either way.
+fn trigger_trap(
+    player_data: &mut PlayerData,
+    game_data: &mut GameData,
+    player_pos_x: usize,
+    player_pos_y: usize,
+    trap_x: usize,
+    trap_y: usize,
+) -> Result<()> {
+    let trap_damage = game_data.data[trap_x][trap_y].tile_damage;
+    game_data.data[player_pos_x][player_pos_y].tile_health = game_data.data[player_pos_x]
+        [player_pos_y]
+        .tile_health
+        .saturating_sub(trap_damage);
+    msg!("Trap hit for {}", trap_damage);
+
+    if game_data.data[player_pos_x][player_pos_y].tile_health == 0 {
+        vacate(&mut game_data.data[trap_x][trap_y]);
+        kill_player(player_data, game_data, player_pos_x, player_pos_y);
+        return Ok(());
+    }
 
-        gameData.data[player_pos_x as usize][player_pos_y as usize].tile_xp +=
-            gameData.data[enemy_x][enemy_y].tile_level + 1;
+    move_player(
+        &mut game_data.data,
+        player_pos_x,
+        player_pos_y,
+        trap_x,
+        trap_y,
+    )
+}
 
-        while gameData.data[player_pos_x as usize][player_pos_y as usize].tile_xp
-            >= 5 * gameData.data[player_pos_x as usize][player_pos_y as usize].tile_level
-        {
-            gameData.data[player_pos_x][player_pos_y].tile_xp -=
-                5 * gameData.data[player_pos_x][player_pos_y].tile_level;
-            gameData.data[player_pos_x][player_pos_y].tile_level += 1;
-            gameData.data[player_pos_x][player_pos_y].tile_max_health += 1;
-            gameData.data[player_pos_x][player_pos_y].tile_health =
-                gameData.data[player_pos_x][player_pos_y].tile_max_health;
-            gameData.data[player_pos_x][player_pos_y].tile_damage += 1;
+fn drink_from_fountain(
+    tile_datas: &mut [[TileData; BOARD_SIZE_X]; BOARD_SIZE_Y],
+    player_pos_x: usize,
+    player_pos_y: usize,
+    fountain_x: usize,
+    fountain_y: usize,
+) -> Result<()> {
+    tile_datas[player_pos_x][player_pos_y].tile_health =
+        tile_datas[player_pos_x][player_pos_y].tile_max_health;
+    msg!("Health restored");
+
+    move_player(
+        tile_datas,
+        player_pos_x,
+        player_pos_y,
+        fountain_x,
+        fountain_y,
+    )
+}
+
+// Praying again while blessed only refreshes the duration, the bonus does not
+// stack. On daily floors the bonus stays on the tile for the rest of the run.
+fn pray_at_shrine(
+    player_data: &mut PlayerData,
+    daily: bool,
+    tile_datas: &mut [[TileData; BOARD_SIZE_X]; BOARD_SIZE_Y],
+    player_pos_x: usize,
+    player_pos_y: usize,
+    shrine_x: usize,
+    shrine_y: usize,
+) -> Result<()> {
+    if daily {
+        tile_datas[player_pos_x][player_pos_y].tile_damage +=
+            tile_datas[shrine_x][shrine_y].tile_damage;
+        msg!("Shrine blessing for the daily run");
+    } else {
+        if player_data.shrine_buff_moves == 0 {
+            let bonus = tile_datas[shrine_x][shrine_y].tile_damage;
+            tile_datas[player_pos_x][player_pos_y].tile_damage += bonus;
+            player_data.shrine_damage_bonus = bonus;
         }
+        player_data.shrine_buff_moves = SHRINE_BUFF_MOVES;
+        msg!("Shrine blessing for {} moves", SHRINE_BUFF_MOVES);
+    }
 
-        move_player(
-            &mut gameData.data,
-            player_pos_x,
-            player_pos_y,
-            enemy_x,
-            enemy_y,
-        )?;
+    // Praying also mends the armor
+    tile_datas[player_pos_x][player_pos_y].tile_armor =
+        tile_datas[player_pos_x][player_pos_y].tile_max_armor;
+
+    move_player(tile_datas, player_pos_x, player_pos_y, shrine_x, shrine_y)
+}
+
+// Moves regenerate the armor_regen_per_move of the config, skills add to it
+// on moves and in combat rounds.
+fn regenerate_armor(player_data: &PlayerData, player_tile: &mut TileData, base_regen: u32) {
+    let regen = base_regen + skills::armor_regen(player_data.skills);
+    if regen > 0 {
+        player_tile.tile_armor =
+            cmp::min(player_tile.tile_armor + regen, player_tile.tile_max_armor);
     }
+}
 
-    Ok(())
+fn fade_shrine_blessing(player_data: &mut PlayerData, player_tile: &mut TileData) {
+    if player_data.shrine_buff_moves == 0 {
+        return;
+    }
+
+    player_data.shrine_buff_moves -= 1;
+    if player_data.shrine_buff_moves == 0 {
+        player_tile.tile_damage = player_tile
+            .tile_damage
+            .saturating_sub(player_data.shrine_damage_bonus);
+        player_data.shrine_damage_bonus = 0;
+        msg!("Shrine blessing faded");
+    }
+}
+
+// Behind every locked door waits the same reward as in a blue chest. Keys
+// belong to the character, daily doors open without one and only reward the
+// tile.
+fn open_door(
+    player_data: &mut PlayerData,
+    daily: bool,
+    tile_datas: &mut [[TileData; BOARD_SIZE_X]; BOARD_SIZE_Y],
+    player_pos_x: usize,
+    player_pos_y: usize,
+    door_x: usize,
+    door_y: usize,
+) -> Result<()> {
+    if !daily {
+        if player_data.keys == 0 {
+            return Err(GameErrorCode::DoorIsLocked.into());
+        }
+        player_data.keys -= 1;
+    }
+
+    tile_datas[player_pos_x][player_pos_y].tile_damage += 1;
+    tile_datas[player_pos_x][player_pos_y].tile_defence += 1;
+    tile_datas[player_pos_x][player_pos_y].tile_health += 1;
+    tile_datas[player_pos_x][player_pos_y].tile_max_health += 1;
+    tile_datas[player_pos_x][player_pos_y].tile_max_armor += 1;
+    tile_datas[player_pos_x][player_pos_y].tile_armor += 1;
+
+    if !daily {
+        player_data.damage += 1;
+        player_data.defence += 1;
+        player_data.max_health += 1;
+        player_data.max_armor += 1;
+    }
+
+    move_player(tile_datas, player_pos_x, player_pos_y, door_x, door_y)
 }
 
 pub struct XorShift64 {
@@ -822,6 +2066,29 @@ pub struct XorShift64 {
 }
 
 impl XorShift64 {
+    pub fn from_slot(salt: u64) -> Result<Self> {
+        Ok(XorShift64 {
+            a: Clock::get()?.slot + salt,
+        })
+    }
+
+    // For rolls that decide rewards. The client can not pick the seed, it
+    // mixes the slot with the player key and a nonce that counts up with
+    // every roll.
+    pub fn for_player(player: &mut PlayerData) -> Result<Self> {
+        player.rng_nonce = player.rng_nonce.wrapping_add(1);
+        let key = player.authority.to_bytes();
+        let key = u64::from_le_bytes([
+            key[0], key[1], key[2], key[3], key[4], key[5], key[6], key[7],
+        ]);
+        let mut rng = XorShift64 {
+            a: (Clock::get()?.slot ^ key).wrapping_add(player.rng_nonce) | 1,
+        };
+        rng.next();
+        Ok(rng)
+    }
+
+    #[allow(clippy::should_implement_trait)]
     pub fn next(&mut self) -> u64 {
         let mut x = self.a;
         x ^= x << 13;
@@ -831,3 +2098,255 @@ impl XorShift64 {
         x
     }
 }
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::state::floor_data::{FloorData, PLAYER_TABLE_SIZE};
+
+    // Every field gets its own value so a swapped or missing field shows up.
+    // Also used by the floor_data tests.
+    pub(crate) fn sample_tile() -> TileData {
+        TileData {
+            tile_type: STATE_PLAYER,
+            tile_level: 2,
+            tile_owner: Pubkey::new_unique(),
+            tile_xp: 3,
+            tile_damage: 4,
+            tile_defence: 5,
+            tile_armor: 6,
+            tile_max_armor: 7,
+            tile_health: 8,
+            tile_max_health: 9,
+            tile_crit_chance: 10,
+            tile_crit_multiplier: 150,
+            tile_evasion: 11,
+            tile_damage_range: 12,
+            tile_class: 13,
+            tile_avatar: 14,
+            tile_name: [15; TILE_NAME_LENGTH],
+            tile_status: 16,
+            tile_status_turns: 17,
+        }
+    }
+
+    #[test]
+    fn tile_data_round_trips() {
+        let tile = sample_tile();
+        assert_eq!(TileData::from(TileData2::from(tile)), tile);
+
+        let tile2 = TileData2::from(tile);
+        assert_eq!(TileData2::from(TileData::from(&tile2)), tile2);
+    }
+
+    #[test]
+    fn defend_does_not_stack() {
+        let mut tile = TileData {
+            tile_defence: 3,
+            tile_armor: 4,
+            tile_max_armor: 6,
+            ..sample_tile()
+        };
+        let bonus = 3 + DEFEND_ARMOR_BONUS;
+
+        assert_eq!(defend(&mut tile), bonus);
+        assert_eq!({ tile.tile_armor }, 4 + bonus);
+        defend(&mut tile);
+        defend(&mut tile);
+        assert_eq!({ tile.tile_armor }, 6 + bonus);
+
+        drop_defend_bonus(&mut tile);
+        assert_eq!({ tile.tile_armor }, 6);
+    }
+
+    #[test]
+    fn take_stairs_needs_the_stairs_next_to_the_player() {
+        let mut game_data = GameData::default();
+        let tile = sample_tile();
+        game_data.data[1][1] = tile;
+
+        assert!(game_data.take_stairs(tile.tile_owner).is_err());
+        assert_eq!({ game_data.data[1][1].tile_type }, STATE_PLAYER);
+
+        game_data.data[2][2].tile_type = STATE_STAIRS;
+        game_data.take_stairs(tile.tile_owner).unwrap();
+        assert_eq!({ game_data.data[1][1].tile_type }, STATE_EMPTY);
+
+        // Gone from the board, there is nothing left to move down
+        assert!(game_data.take_stairs(tile.tile_owner).is_err());
+    }
+
+    // A level up from xp earned off the board grows the carried tile and the
+    // board tile alike and heals both to the new max health
+    #[test]
+    fn level_up_grows_the_board_tile() {
+        let mut game_data = GameData::default();
+        let tile = sample_tile();
+        game_data.data[1][1] = tile;
+        let mut player = PlayerData {
+            authority: tile.tile_owner,
+            class: player_class::CLASS_WARRIOR,
+            level: 2,
+            max_health: 9,
+            tile_data: tile.into(),
+            ..Default::default()
+        };
+        let growth = player_class::growth(player_class::CLASS_WARRIOR);
+
+        let levels = player.add_xp(player.xp_threshold());
+        assert_eq!(levels, 1);
+        assert_eq!({ player.tile_data.tile_max_health }, 9 + growth.max_health);
+        assert_eq!({ player.tile_data.tile_health }, 9 + growth.max_health);
+
+        game_data.apply_level_up(&mut player, levels);
+        let board_tile = game_data.data[1][1];
+        assert_eq!({ board_tile.tile_level }, 3);
+        assert_eq!({ board_tile.tile_max_health }, 9 + growth.max_health);
+        assert_eq!({ board_tile.tile_health }, 9 + growth.max_health);
+        assert_eq!({ board_tile.tile_damage }, 4 + growth.damage);
+        assert_eq!(player.health, 9 + growth.max_health);
+    }
+
+    // Armor comes back by the regen of the config and the skills, never above
+    // the max
+    #[test]
+    fn armor_regenerates_up_to_the_max() {
+        let mut player = PlayerData::default();
+        let mut tile = sample_tile();
+        tile.tile_armor = 2;
+
+        regenerate_armor(&player, &mut tile, 0);
+        assert_eq!({ tile.tile_armor }, 2);
+        regenerate_armor(&player, &mut tile, 1);
+        assert_eq!({ tile.tile_armor }, 3);
+
+        player.skills = 1 << skills::SKILL_ARMOR_REGEN;
+        regenerate_armor(&player, &mut tile, 0);
+        assert_eq!({ tile.tile_armor }, 4);
+        regenerate_armor(&player, &mut tile, 10);
+        assert_eq!({ tile.tile_armor }, { tile.tile_max_armor });
+    }
+
+    // A new fight only refreshes the enemy, the damaged player armor is kept
+    // and carried with the player
+    #[test]
+    fn damaged_armor_persists_into_the_next_fight() {
+        let mut game_data = GameData::default();
+        let mut tile = sample_tile();
+        tile.tile_armor = 1;
+        game_data.data[1][1] = tile;
+        game_data.data[1][2] = TileData {
+            tile_type: STATE_ENEMY,
+            tile_armor: 0,
+            tile_max_armor: 3,
+            tile_health: 5,
+            ..Default::default()
+        };
+        let mut player = PlayerData {
+            authority: tile.tile_owner,
+            ..Default::default()
+        };
+
+        game_data.start_encounter(&mut player, Pubkey::new_unique(), 1, 1, 1, 2);
+        assert!(player.encounter.active);
+        assert_eq!({ game_data.data[1][1].tile_armor }, 1);
+        assert_eq!({ game_data.data[1][2].tile_armor }, 3);
+
+        game_data.save_player_tile(&mut player);
+        assert_eq!({ player.tile_data.tile_armor }, 1);
+    }
+
+    // The armor potion fills the armor up again
+    #[test]
+    fn armor_potion_refills_the_armor() {
+        let mut game_data = GameData::default();
+        let mut tile = sample_tile();
+        tile.tile_armor = 0;
+        game_data.data[1][1] = tile;
+        let mut player = PlayerData {
+            authority: tile.tile_owner,
+            ..Default::default()
+        };
+        player.add_item(ITEM_ARMOR_POTION, 1).unwrap();
+
+        game_data
+            .use_consumable(&mut player, ITEM_ARMOR_POTION)
+            .unwrap();
+        assert_eq!({ game_data.data[1][1].tile_armor }, { tile.tile_max_armor });
+        assert_eq!({ player.tile_data.tile_armor }, { tile.tile_max_armor });
+        assert_eq!(player.item_count(ITEM_ARMOR_POTION), 0);
+    }
+
+    // Resting costs energy and only works away from enemies
+    #[test]
+    fn rest_heals_away_from_enemies() {
+        let mut game_data = GameData::default();
+        let mut tile = sample_tile();
+        tile.tile_health = 1;
+        game_data.data[1][1] = tile;
+        let mut player = PlayerData {
+            authority: tile.tile_owner,
+            energy: REST_ENERGY,
+            ..Default::default()
+        };
+
+        game_data.data[2][2].tile_type = STATE_ENEMY;
+        assert!(game_data.rest(&mut player).is_err());
+
+        game_data.data[2][2].tile_type = STATE_EMPTY;
+        game_data.rest(&mut player).unwrap();
+        assert_eq!(player.energy, 0);
+        assert_eq!({ game_data.data[1][1].tile_health }, 1 + REST_HEAL);
+        assert_eq!(player.health, 1 + REST_HEAL);
+        assert!(game_data.rest(&mut player).is_err());
+    }
+
+    #[test]
+    fn move_player_moves_the_whole_tile() {
+        let mut game_data = GameData::default();
+        let tile = sample_tile();
+        game_data.data[1][1] = tile;
+
+        move_player(&mut game_data.data, 1, 1, 1, 2).unwrap();
+
+        assert_eq!(game_data.data[1][2], tile);
+        assert_eq!({ game_data.data[1][1].tile_type }, STATE_EMPTY);
+    }
+
+    // Every player who walks over the floor leaves a trail of vacated tiles.
+    // They must not keep the owner, or the player table of the floor account
+    // fills up and the instance can not be saved anymore.
+    #[test]
+    fn players_passing_through_free_their_slot() {
+        let mut floor_data = FloorData::default();
+        floor_data.save(&GameData::default()).unwrap();
+
+        for i in 0..PLAYER_TABLE_SIZE * 2 {
+            let mut game_data = floor_data.board();
+            let mut player = PlayerData {
+                authority: Pubkey::new_unique(),
+                ..Default::default()
+            };
+            // Everyone walks over other tiles, the trails do not overlap
+            let (x, y) = (i % BOARD_SIZE_X, i / BOARD_SIZE_X % 5 * 2);
+            game_data.data[x][y] = TileData {
+                tile_owner: player.authority,
+                ..sample_tile()
+            };
+            move_player(&mut game_data.data, x, y, x, y + 1).unwrap();
+            if i % 2 == 0 {
+                kill_player(&mut player, &mut game_data, x, y + 1);
+            } else {
+                game_data.remove_player(player.authority).unwrap();
+            }
+            floor_data.save(&game_data).unwrap();
+        }
+
+        let game_data = floor_data.board();
+        assert!(game_data
+            .data
+            .iter()
+            .flatten()
+            .all(|tile| tile.tile_owner == Pubkey::default()));
+    }
+}
@@ -0,0 +1,360 @@
+use std::cmp::Ordering;
+
+use crate::{constants::*, GameErrorCode};
+use anchor_lang::prelude::*;
+
+use super::player_data::PlayerData;
+
+pub const GUILD_ROLE_MEMBER: u8 = 0;
+pub const GUILD_ROLE_OFFICER: u8 = 1; // can invite
+pub const GUILD_ROLE_LEADER: u8 = 2;
+
+// The stats are copied from the season stats of the player by
+// update_guild_ranking.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct GuildMember {
+    pub player: Pubkey,
+    pub role: u8,
+    pub season: u16,
+    pub max_floor: u16,
+    pub kills: u32,
+    pub contributed: u64,
+}
+
+// Guild names are unique like player names, the seed is the lowercased name.
+// The treasury is the gold token account of the guild PDA.
+#[account]
+pub struct Guild {
+    pub name: String,
+    pub leader: Pubkey,
+    pub members: Vec<GuildMember>,
+    pub invites: Vec<Pubkey>,
+    pub treasury: Pubkey,
+    pub contributed: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct GuildEntry {
+    pub guild: Pubkey,
+    pub deepest_floor: u16,
+    pub kills: u32,
+    pub reached_at: u64,
+}
+
+// Guilds are ranked by the deepest floor any member reached this season, then
+// by the kills of all members together. Ties go to the guild that got there
+// first.
+#[account]
+pub struct GuildLeaderboard {
+    pub season: u16,
+    pub entries: Vec<GuildEntry>,
+}
+
+pub fn compare_entries(a: &GuildEntry, b: &GuildEntry) -> Ordering {
+    b.deepest_floor
+        .cmp(&a.deepest_floor)
+        .then(b.kills.cmp(&a.kills))
+        .then(a.reached_at.cmp(&b.reached_at))
+        .then(a.guild.to_bytes().cmp(&b.guild.to_bytes()))
+}
+
+impl Guild {
+    fn position(&self, player: Pubkey) -> Option<usize> {
+        self.members
+            .iter()
+            .position(|member| member.player == player)
+    }
+
+    pub fn member(&mut self, player: Pubkey) -> Result<&mut GuildMember> {
+        let index = self.position(player).ok_or(GameErrorCode::NotInGuild)?;
+        Ok(&mut self.members[index])
+    }
+
+    pub fn role(&self, player: Pubkey) -> Result<u8> {
+        let index = self.position(player).ok_or(GameErrorCode::NotInGuild)?;
+        Ok(self.members[index].role)
+    }
+
+    pub fn invite(&mut self, by: Pubkey, player: Pubkey) -> Result<()> {
+        if self.role(by)? < GUILD_ROLE_OFFICER {
+            return Err(GameErrorCode::NotGuildOfficer.into());
+        }
+        if self.members.len() + self.invites.len() >= MAX_GUILD_MEMBERS
+            || self.invites.len() >= MAX_GUILD_INVITES
+        {
+            return Err(GameErrorCode::GuildFull.into());
+        }
+        if self.position(player).is_some() || self.invites.contains(&player) {
+            return Err(GameErrorCode::AlreadyInGuild.into());
+        }
+        self.invites.push(player);
+        Ok(())
+    }
+
+    pub fn join(&mut self, player: Pubkey) -> Result<()> {
+        let invite = self
+            .invites
+            .iter()
+            .position(|invited| *invited == player)
+            .ok_or(GameErrorCode::NotInvited)?;
+        self.invites.remove(invite);
+        self.members.push(GuildMember {
+            player,
+            role: GUILD_ROLE_MEMBER,
+            ..Default::default()
+        });
+        Ok(())
+    }
+
+    // The leader hands the guild to the highest ranked member when leaving.
+    // Returns true if the guild is empty now and has to be closed.
+    pub fn leave(&mut self, player: Pubkey) -> Result<bool> {
+        let index = self.position(player).ok_or(GameErrorCode::NotInGuild)?;
+        self.members.remove(index);
+
+        if self.leader == player {
+            self.leader = Pubkey::default();
+            if let Some(successor) = self.members.iter_mut().max_by_key(|member| member.role) {
+                successor.role = GUILD_ROLE_LEADER;
+                self.leader = successor.player;
+                msg!("New guild leader {}", self.leader);
+            }
+        }
+        Ok(self.members.is_empty())
+    }
+
+    // Gold sent to the treasury is counted per member and for the guild
+    pub fn contribute(&mut self, player: Pubkey, amount: u64) -> Result<()> {
+        self.member(player)?.contributed += amount;
+        self.contributed += amount;
+        Ok(())
+    }
+
+    pub fn check_leader(&self, player: Pubkey) -> Result<()> {
+        if self.leader != player {
+            return Err(GameErrorCode::NotGuildLeader.into());
+        }
+        Ok(())
+    }
+
+    // Giving someone the leader role makes the old leader an officer
+    pub fn set_role(&mut self, by: Pubkey, player: Pubkey, role: u8) -> Result<()> {
+        self.check_leader(by)?;
+        if role > GUILD_ROLE_LEADER {
+            return Err(GameErrorCode::InvalidGuildRole.into());
+        }
+        if player == by {
+            return Err(GameErrorCode::InvalidGuildRole.into());
+        }
+
+        self.member(player)?.role = role;
+        if role == GUILD_ROLE_LEADER {
+            self.member(by)?.role = GUILD_ROLE_OFFICER;
+            self.leader = player;
+        }
+        Ok(())
+    }
+
+    pub fn update_member(&mut self, player: &PlayerData) -> Result<()> {
+        let member = self.member(player.authority)?;
+        member.season = player.season;
+        member.max_floor = player.season_stats.max_floor;
+        member.kills = player.season_stats.kills;
+        Ok(())
+    }
+
+    // Only members who played in the given season count
+    pub fn ranking(&self, season: u16) -> (u16, u32) {
+        self.members
+            .iter()
+            .filter(|member| member.season == season)
+            .fold((0, 0), |(deepest, kills), member| {
+                (deepest.max(member.max_floor), kills + member.kills)
+            })
+    }
+}
+
+impl GuildLeaderboard {
+    pub fn submit(&mut self, guild: Pubkey, deepest_floor: u16, kills: u32) -> Result<()> {
+        let slot = Clock::get()?.slot;
+
+        if let Some(entry) = self.entries.iter_mut().find(|entry| entry.guild == guild) {
+            if deepest_floor != entry.deepest_floor || kills != entry.kills {
+                entry.reached_at = slot;
+            }
+            entry.deepest_floor = deepest_floor;
+            entry.kills = kills;
+        } else {
+            let entry = GuildEntry {
+                guild,
+                deepest_floor,
+                kills,
+                reached_at: slot,
+            };
+            if self.entries.len() < GUILD_LEADERBOARD_SIZE {
+                self.entries.push(entry);
+            } else {
+                let last = self.entries.len() - 1;
+                if compare_entries(&entry, &self.entries[last]) != Ordering::Less {
+                    return Ok(());
+                }
+                self.entries[last] = entry;
+            }
+        }
+
+        self.entries.sort_by(compare_entries);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn guild(leader: Pubkey) -> Guild {
+        Guild {
+            name: "Tufia".to_string(),
+            leader,
+            members: vec![GuildMember {
+                player: leader,
+                role: GUILD_ROLE_LEADER,
+                ..Default::default()
+            }],
+            invites: Vec::new(),
+            treasury: Pubkey::new_unique(),
+            contributed: 0,
+        }
+    }
+
+    fn add_member(guild: &mut Guild, player: Pubkey) {
+        let leader = guild.leader;
+        guild.invite(leader, player).unwrap();
+        guild.join(player).unwrap();
+    }
+
+    // Plain members can not invite, officers can
+    #[test]
+    fn only_officers_invite() {
+        let leader = Pubkey::new_unique();
+        let member = Pubkey::new_unique();
+        let mut guild = guild(leader);
+        add_member(&mut guild, member);
+
+        assert!(guild.invite(member, Pubkey::new_unique()).is_err());
+        assert!(guild.set_role(member, member, GUILD_ROLE_OFFICER).is_err());
+        guild.set_role(leader, member, GUILD_ROLE_OFFICER).unwrap();
+        guild.invite(member, Pubkey::new_unique()).unwrap();
+
+        assert!(guild.join(Pubkey::new_unique()).is_err());
+        assert!(guild.invite(leader, member).is_err());
+        assert!(guild
+            .set_role(leader, member, GUILD_ROLE_LEADER + 1)
+            .is_err());
+    }
+
+    // Handing over the lead makes the old leader an officer
+    #[test]
+    fn new_leader_demotes_the_old_one() {
+        let leader = Pubkey::new_unique();
+        let member = Pubkey::new_unique();
+        let mut guild = guild(leader);
+        add_member(&mut guild, member);
+
+        guild.set_role(leader, member, GUILD_ROLE_LEADER).unwrap();
+        assert_eq!(guild.leader, member);
+        assert_eq!(guild.role(leader).unwrap(), GUILD_ROLE_OFFICER);
+        assert!(guild.check_leader(leader).is_err());
+    }
+
+    // The highest role takes over when the leader leaves, the last member
+    // closes the guild
+    #[test]
+    fn leader_leaving_promotes_an_officer() {
+        let leader = Pubkey::new_unique();
+        let member = Pubkey::new_unique();
+        let officer = Pubkey::new_unique();
+        let mut guild = guild(leader);
+        add_member(&mut guild, member);
+        add_member(&mut guild, officer);
+        guild.set_role(leader, officer, GUILD_ROLE_OFFICER).unwrap();
+
+        assert!(!guild.leave(leader).unwrap());
+        assert_eq!(guild.leader, officer);
+        assert_eq!(guild.role(officer).unwrap(), GUILD_ROLE_LEADER);
+
+        assert!(!guild.leave(officer).unwrap());
+        assert!(guild.leave(member).unwrap());
+    }
+
+    // Contributions are kept per member and for the whole treasury
+    #[test]
+    fn contributions_add_up() {
+        let leader = Pubkey::new_unique();
+        let member = Pubkey::new_unique();
+        let mut guild = guild(leader);
+        add_member(&mut guild, member);
+
+        guild.contribute(leader, 30).unwrap();
+        guild.contribute(member, 20).unwrap();
+        guild.contribute(member, 5).unwrap();
+
+        assert_eq!(guild.member(leader).unwrap().contributed, 30);
+        assert_eq!(guild.member(member).unwrap().contributed, 25);
+        assert_eq!(guild.contributed, 55);
+        assert!(guild.contribute(Pubkey::new_unique(), 1).is_err());
+        assert_eq!(guild.contributed, 55);
+    }
+
+    // The deepest floor of anyone and the kills of everyone, this season only
+    #[test]
+    fn ranking_counts_the_current_season() {
+        let leader = Pubkey::new_unique();
+        let member = Pubkey::new_unique();
+        let mut guild = guild(leader);
+        add_member(&mut guild, member);
+
+        let mut player = PlayerData {
+            authority: leader,
+            season: 2,
+            ..Default::default()
+        };
+        player.season_stats.max_floor = 7;
+        player.season_stats.kills = 10;
+        guild.update_member(&player).unwrap();
+
+        player.authority = member;
+        player.season_stats.max_floor = 4;
+        player.season_stats.kills = 5;
+        guild.update_member(&player).unwrap();
+        assert_eq!(guild.ranking(2), (7, 15));
+
+        player.season = 3;
+        guild.update_member(&player).unwrap();
+        assert_eq!(guild.ranking(2), (7, 10));
+        assert_eq!(guild.ranking(3), (4, 5));
+    }
+
+    #[test]
+    fn deeper_then_more_kills() {
+        let entry = GuildEntry {
+            deepest_floor: 5,
+            kills: 10,
+            reached_at: 2,
+            ..Default::default()
+        };
+        let deeper = GuildEntry {
+            deepest_floor: 6,
+            kills: 0,
+            ..entry
+        };
+        let more_kills = GuildEntry { kills: 11, ..entry };
+        let earlier = GuildEntry {
+            reached_at: 1,
+            ..entry
+        };
+
+        assert_eq!(compare_entries(&deeper, &entry), Ordering::Less);
+        assert_eq!(compare_entries(&more_kills, &entry), Ordering::Less);
+        assert_eq!(compare_entries(&earlier, &entry), Ordering::Less);
+    }
+}
@@ -0,0 +1,80 @@
+use crate::{constants::*, GameErrorCode};
+use anchor_lang::prelude::*;
+
+use super::player_data::PlayerData;
+
+// Item ids used by shops. Health potions and keys already have their own
+// counters on the player, everything else is kept in the inventory list.
+pub const ITEM_HEALTH_POTION: u16 = 0;
+pub const ITEM_KEY: u16 = 1;
+// Crafting materials, see crafting.rs
+pub const ITEM_SCRAP: u16 = 2;
+pub const ITEM_CRYSTAL: u16 = 3;
+// Consumables, see GameData::use_consumable
+pub const ITEM_ARMOR_POTION: u16 = 4;
+pub const ITEM_STRENGTH_ELIXIR: u16 = 5;
+pub const ITEM_ENERGY_TONIC: u16 = 6;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct InventoryItem {
+    pub item_id: u16,
+    pub amount: u32,
+}
+
+impl PlayerData {
+    pub fn item_count(&self, item_id: u16) -> u32 {
+        match item_id {
+            ITEM_HEALTH_POTION => self.health_potions,
+            ITEM_KEY => self.keys,
+            _ => self
+                .inventory
+                .iter()
+                .find(|item| item.item_id == item_id)
+                .map_or(0, |item| item.amount),
+        }
+    }
+
+    pub fn add_item(&mut self, item_id: u16, amount: u32) -> Result<()> {
+        match item_id {
+            ITEM_HEALTH_POTION => self.health_potions += amount,
+            ITEM_KEY => self.keys += amount,
+            _ => match self
+                .inventory
+                .iter_mut()
+                .find(|item| item.item_id == item_id)
+            {
+                Some(item) => item.amount += amount,
+                None => {
+                    if self.inventory.len() >= MAX_INVENTORY_ITEMS {
+                        return Err(GameErrorCode::InventoryFull.into());
+                    }
+                    self.inventory.push(InventoryItem { item_id, amount });
+                }
+            },
+        }
+        Ok(())
+    }
+
+    pub fn remove_item(&mut self, item_id: u16, amount: u32) -> Result<()> {
+        if self.item_count(item_id) < amount {
+            return Err(GameErrorCode::NotEnoughItems.into());
+        }
+
+        match item_id {
+            ITEM_HEALTH_POTION => self.health_potions -= amount,
+            ITEM_KEY => self.keys -= amount,
+            _ => {
+                let index = self
+                    .inventory
+                    .iter()
+                    .position(|item| item.item_id == item_id)
+                    .ok_or(GameErrorCode::NotEnoughItems)?;
+                self.inventory[index].amount -= amount;
+                if self.inventory[index].amount == 0 {
+                    self.inventory.remove(index);
+                }
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,182 @@
+use crate::{constants::*, GameErrorCode};
+use anchor_lang::prelude::*;
+use anchor_spl::metadata::mpl_token_metadata::types::DataV2;
+
+use super::{game_data::XorShift64, player_data::PlayerData, status};
+
+pub const RARITY_COMMON: u8 = 0;
+pub const RARITY_RARE: u8 = 1;
+pub const RARITY_EPIC: u8 = 2;
+pub const RARITY_LEGENDARY: u8 = 3;
+
+// Gear found in blue chests. It stays in the PlayerData inventory until the
+// player withdraws it as an NFT.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct GearItem {
+    pub rarity: u8,
+    pub damage: u32,
+    pub defence: u32,
+    pub armor: u32,
+    pub floor: u16, // where it was found
+    pub equipped: bool,
+    pub tier: u8,   // raised by upgrade_item
+    pub status: u8, // STATUS_* effects the hits of the wearer inflict
+}
+
+impl GearItem {
+    // Same item, no matter if it is equipped
+    pub fn same_item(&self, other: &GearItem) -> bool {
+        self.rarity == other.rarity
+            && self.damage == other.damage
+            && self.defence == other.defence
+            && self.armor == other.armor
+            && self.floor == other.floor
+            && self.tier == other.tier
+            && self.status == other.status
+    }
+}
+
+// The stats of a withdrawn item. The NFT metadata only points to it, so the
+// stats can not be changed by editing the off-chain json.
+#[account]
+pub struct ItemRecord {
+    pub mint: Pubkey,
+    pub item: GearItem,
+}
+
+// Higher rarities and deeper floors give more stat points, spread randomly
+// over damage, defence and armor.
+pub fn roll_gear(rng: &mut XorShift64, floor: u16) -> GearItem {
+    let roll = rng.next() % 100;
+    let rarity = if roll < 60 {
+        RARITY_COMMON
+    } else if roll < 85 {
+        RARITY_RARE
+    } else if roll < 97 {
+        RARITY_EPIC
+    } else {
+        RARITY_LEGENDARY
+    };
+
+    let power = (floor as u64 / 2 + 1) * (rarity as u64 + 1);
+    // Only epic and legendary gear can poison, burn, stun or shred
+    let status = if rarity >= RARITY_EPIC && rng.next() % 100 < GEAR_STATUS_CHANCE {
+        1 << (rng.next() % 4)
+    } else {
+        0
+    };
+    let damage = rng.next() % (power + 1);
+    let defence = rng.next() % (power - damage + 1);
+
+    GearItem {
+        rarity,
+        damage: damage as u32,
+        defence: defence as u32,
+        armor: (power - damage - defence) as u32,
+        floor,
+        equipped: false,
+        tier: 0,
+        status,
+    }
+}
+
+fn rarity_name(rarity: u8) -> &'static str {
+    match rarity {
+        RARITY_RARE => "Rare",
+        RARITY_EPIC => "Epic",
+        RARITY_LEGENDARY => "Legendary",
+        _ => "Common",
+    }
+}
+
+// The json behind the uri is served off-chain from the ItemRecord of the mint
+pub fn metadata(item: &GearItem, item_uri: &str, mint: Pubkey) -> DataV2 {
+    DataV2 {
+        name: format!("{} Gear of Floor {}", rarity_name(item.rarity), item.floor),
+        symbol: ITEM_SYMBOL.to_string(),
+        uri: format!("{}/{}", item_uri, mint),
+        seller_fee_basis_points: 0,
+        creators: None,
+        collection: None,
+        uses: None,
+    }
+}
+
+pub fn validate_uri(uri: &str) -> Result<()> {
+    if uri.len() > MAX_ITEM_URI_LENGTH {
+        return Err(GameErrorCode::InvalidItemUri.into());
+    }
+    Ok(())
+}
+
+impl PlayerData {
+    pub fn add_gear(&mut self, item: GearItem) -> Result<()> {
+        if self.gear.len() >= MAX_GEAR_ITEMS {
+            return Err(GameErrorCode::GearFull.into());
+        }
+        self.gear.push(GearItem {
+            equipped: false,
+            ..item
+        });
+        Ok(())
+    }
+
+    // Equipped gear has to be taken off before it can leave the inventory
+    pub fn remove_gear(&mut self, index: u8) -> Result<GearItem> {
+        let item = self
+            .gear
+            .get(index as usize)
+            .ok_or(GameErrorCode::InvalidItem)?;
+        if item.equipped {
+            return Err(GameErrorCode::ItemEquipped.into());
+        }
+        Ok(self.gear.remove(index as usize))
+    }
+
+    // Adds or removes the stats of the gear on the player and on the tile that
+    // is carried to the next floor. The board tile is updated by GameData.
+    pub fn equip_gear(&mut self, index: u8, equip: bool) -> Result<GearItem> {
+        let equipped = self.gear.iter().filter(|item| item.equipped).count();
+        let item = self
+            .gear
+            .get_mut(index as usize)
+            .ok_or(GameErrorCode::InvalidItem)?;
+        if item.equipped == equip {
+            return Err(GameErrorCode::InvalidItem.into());
+        }
+        if equip && equipped >= MAX_EQUIPPED_GEAR {
+            return Err(GameErrorCode::TooManyEquipped.into());
+        }
+        item.equipped = equip;
+        let item = *item;
+
+        if equip {
+            self.damage += item.damage;
+            self.defence += item.defence;
+            self.max_armor += item.armor;
+            self.tile_data.tile_damage += item.damage;
+            self.tile_data.tile_defence += item.defence;
+            self.tile_data.tile_max_armor += item.armor;
+        } else {
+            self.damage = self.damage.saturating_sub(item.damage);
+            self.defence = self.defence.saturating_sub(item.defence);
+            self.max_armor = self.max_armor.saturating_sub(item.armor);
+            self.tile_data.tile_damage = self.tile_data.tile_damage.saturating_sub(item.damage);
+            self.tile_data.tile_defence = self.tile_data.tile_defence.saturating_sub(item.defence);
+            self.tile_data.tile_max_armor =
+                self.tile_data.tile_max_armor.saturating_sub(item.armor);
+            self.tile_data.tile_armor =
+                self.tile_data.tile_armor.min(self.tile_data.tile_max_armor);
+        }
+        self.tile_data.tile_status =
+            status::with_inflicts(self.tile_data.tile_status, self.gear_status());
+        Ok(item)
+    }
+
+    pub fn gear_status(&self) -> u8 {
+        self.gear
+            .iter()
+            .filter(|item| item.equipped)
+            .fold(0, |effects, item| effects | item.status)
+    }
+}
@@ -0,0 +1,116 @@
+use std::cmp::Ordering;
+
+use crate::{constants::*, GameErrorCode};
+use anchor_lang::prelude::*;
+
+use super::player_data::PlayerData;
+
+pub const LEADERBOARD_METRIC_MAX_FLOOR: u8 = 0;
+pub const LEADERBOARD_METRIC_KILLS: u8 = 1;
+pub const LEADERBOARD_METRIC_CHESTS_OPENED: u8 = 2;
+
+// Stats that survive death, unlike current_floor, level and xp
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct LifetimeStats {
+    pub max_floor: u16,
+    pub kills: u32,
+    pub deaths: u32,
+    pub chests_opened: u32,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct LeaderboardEntry {
+    pub player: Pubkey,
+    pub stats: LifetimeStats,
+    pub reached_at: u64, // slot in which the ranked value was reached
+}
+
+// There is one leaderboard per metric and season. It keeps the best LEADERBOARD_SIZE
+// players sorted by that metric. Ties go to the player who reached the value
+// first and after that to the lower public key, so the order never depends on
+// the order of transactions in a block.
+#[account]
+pub struct Leaderboard {
+    pub season: u16,
+    pub metric: u8,
+    pub entries: Vec<LeaderboardEntry>,
+}
+
+pub fn validate_metric(metric: u8) -> Result<()> {
+    if metric > LEADERBOARD_METRIC_CHESTS_OPENED {
+        return Err(GameErrorCode::InvalidLeaderboardMetric.into());
+    }
+    Ok(())
+}
+
+pub fn metric_value(stats: &LifetimeStats, metric: u8) -> u32 {
+    match metric {
+        LEADERBOARD_METRIC_MAX_FLOOR => stats.max_floor as u32,
+        LEADERBOARD_METRIC_KILLS => stats.kills,
+        LEADERBOARD_METRIC_CHESTS_OPENED => stats.chests_opened,
+        _ => 0,
+    }
+}
+
+// Better entries sort first
+pub fn compare_entries(a: &LeaderboardEntry, b: &LeaderboardEntry, metric: u8) -> Ordering {
+    metric_value(&b.stats, metric)
+        .cmp(&metric_value(&a.stats, metric))
+        .then(a.reached_at.cmp(&b.reached_at))
+        .then(a.player.to_bytes().cmp(&b.player.to_bytes()))
+}
+
+impl Leaderboard {
+    pub fn submit(&mut self, player: &PlayerData) -> Result<()> {
+        if player.season != self.season {
+            return Err(GameErrorCode::WrongSeason.into());
+        }
+
+        let value = metric_value(&player.season_stats, self.metric);
+        if value == 0 {
+            return Ok(());
+        }
+        let slot = Clock::get()?.slot;
+        let metric = self.metric;
+
+        if let Some(entry) = self
+            .entries
+            .iter_mut()
+            .find(|entry| entry.player == player.authority)
+        {
+            if value > metric_value(&entry.stats, metric) {
+                entry.reached_at = slot;
+            }
+            entry.stats = player.season_stats;
+        } else {
+            let entry = LeaderboardEntry {
+                player: player.authority,
+                stats: player.season_stats,
+                reached_at: slot,
+            };
+            if self.entries.len() < LEADERBOARD_SIZE {
+                self.entries.push(entry);
+            } else {
+                let last = self.entries.len() - 1;
+                if compare_entries(&entry, &self.entries[last], metric) != Ordering::Less {
+                    return Ok(());
+                }
+                self.entries[last] = entry;
+            }
+        }
+
+        self.entries.sort_by(|a, b| compare_entries(a, b, metric));
+        Ok(())
+    }
+}
+
+// Floor changes and deaths pass the leaderboard as an optional account
+pub fn submit_if_present(
+    leaderboard: &mut Option<Account<Leaderboard>>,
+    player: &PlayerData,
+) -> Result<()> {
+    match leaderboard {
+        Some(leaderboard) => leaderboard.submit(player),
+        None => Ok(()),
+    }
+}
@@ -1,2 +1,21 @@
+pub mod boss_data;
+pub mod buffs;
+pub mod crafting;
+pub mod daily;
+pub mod floor;
+pub mod floor_data;
+pub mod game_config;
 pub mod game_data;
+pub mod guild;
+pub mod inventory;
+pub mod item;
+pub mod leaderboard;
+pub mod name_registry;
+pub mod party;
+pub mod player_class;
 pub mod player_data;
+pub mod season;
+pub mod shop;
+pub mod skills;
+pub mod status;
+pub mod trade;
@@ -0,0 +1,55 @@
+use crate::{constants::*, GameErrorCode};
+use anchor_lang::prelude::*;
+
+// One account per taken name. The seed is the lowercased name so "Bob" and
+// "bob" can not both exist.
+#[account]
+pub struct NameRegistry {
+    pub authority: Pubkey,
+    pub name: String,
+}
+
+pub fn registry_seed(name: &str) -> String {
+    name.to_lowercase()
+}
+
+// Names are 3 to 12 letters, digits, '_' or '-'
+pub fn validate_name(name: &str) -> Result<()> {
+    if name.len() < MIN_NAME_LENGTH
+        || name.len() > MAX_NAME_LENGTH
+        || !name
+            .bytes()
+            .all(|c| c.is_ascii_alphanumeric() || c == b'_' || c == b'-')
+    {
+        return Err(GameErrorCode::InvalidName.into());
+    }
+    Ok(())
+}
+
+// Titles can be empty and may contain spaces
+pub fn validate_title(title: &str) -> Result<()> {
+    if title.len() > MAX_TITLE_LENGTH
+        || !title
+            .bytes()
+            .all(|c| c.is_ascii_alphanumeric() || c == b' ' || c == b'_' || c == b'-')
+    {
+        return Err(GameErrorCode::InvalidTitle.into());
+    }
+    Ok(())
+}
+
+pub fn validate_avatar(avatar: u8) -> Result<()> {
+    if avatar >= AVATAR_COUNT {
+        return Err(GameErrorCode::InvalidAvatar.into());
+    }
+    Ok(())
+}
+
+// The name zero padded to a fixed size, stored on the board tile for the
+// clients. It keeps the whole name so two players never show the same tag.
+pub fn name_tag(name: &str) -> [u8; TILE_NAME_LENGTH] {
+    let mut tag = [0u8; TILE_NAME_LENGTH];
+    let length = name.len().min(TILE_NAME_LENGTH);
+    tag[..length].copy_from_slice(&name.as_bytes()[..length]);
+    tag
+}
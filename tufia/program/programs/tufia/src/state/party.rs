@@ -0,0 +1,335 @@
+use crate::{constants::*, GameErrorCode};
+use anchor_lang::prelude::*;
+
+use super::player_data::PlayerData;
+
+// How xp from kills is shared
+pub const PARTY_XP_SOLO: u8 = 0; // the killer keeps everything
+pub const PARTY_XP_SHARED: u8 = 1; // every other member gets PARTY_XP_SHARE_PERCENT on top
+
+// How keys and potions from chests are shared
+pub const PARTY_LOOT_FINDER: u8 = 0; // the finder keeps everything
+pub const PARTY_LOOT_ROUND_ROBIN: u8 = 1; // members take turns
+
+// Shares for other members are parked here until they claim them, since
+// their PlayerData accounts are not part of the transaction.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct PartyMember {
+    pub player: Pubkey,
+    pub pending_xp: u32,
+    pub pending_keys: u32,
+    pub pending_potions: u32,
+}
+
+// Seeded by the founder, so the address stays the same when the leader
+// changes. The floor and instance are where the party plays, the first member
+// going to a new floor decides the instance for everyone.
+#[account]
+pub struct Party {
+    pub founder: Pubkey,
+    pub leader: Pubkey,
+    pub members: Vec<PartyMember>,
+    pub invites: Vec<Pubkey>,
+    pub xp_rule: u8,
+    pub loot_rule: u8,
+    pub loot_turn: u8,
+    pub season: u16,
+    pub floor_id: u32,
+    pub instance: u16,
+}
+
+// What a player had before an action, to find out what they gained
+#[derive(Clone, Copy)]
+pub struct LootSnapshot {
+    total_xp: u64,
+    keys: u32,
+    health_potions: u32,
+}
+
+impl LootSnapshot {
+    pub fn of(player: &PlayerData) -> Self {
+        LootSnapshot {
+            total_xp: player.total_xp(),
+            keys: player.keys,
+            health_potions: player.health_potions,
+        }
+    }
+}
+
+pub fn validate_rules(xp_rule: u8, loot_rule: u8) -> Result<()> {
+    if xp_rule > PARTY_XP_SHARED || loot_rule > PARTY_LOOT_ROUND_ROBIN {
+        return Err(GameErrorCode::InvalidPartyRule.into());
+    }
+    Ok(())
+}
+
+// Party members of the player that must not be attacked. Players in a party
+// have to pass their party account, otherwise friendly fire could be skipped.
+pub fn allies(party: &Option<Account<Party>>, player: &PlayerData) -> Result<Vec<Pubkey>> {
+    check_party(party, player)?;
+    Ok(match party {
+        Some(party) => party.members.iter().map(|member| member.player).collect(),
+        None => Vec::new(),
+    })
+}
+
+// The same for instructions that share loot, leaving the party out would
+// keep it from the other members
+pub fn check_party(party: &Option<Account<Party>>, player: &PlayerData) -> Result<()> {
+    match party {
+        Some(party) => check_member(party, player),
+        None if player.party == Pubkey::default() => Ok(()),
+        None => Err(GameErrorCode::WrongParty.into()),
+    }
+}
+
+pub fn check_member(party: &Account<Party>, player: &PlayerData) -> Result<()> {
+    if player.party != party.key() || party.position(player.authority).is_none() {
+        return Err(GameErrorCode::WrongParty.into());
+    }
+    Ok(())
+}
+
+impl Party {
+    fn position(&self, player: Pubkey) -> Option<usize> {
+        self.members
+            .iter()
+            .position(|member| member.player == player)
+    }
+
+    pub fn invite(&mut self, player: Pubkey) -> Result<()> {
+        if self.members.len() + self.invites.len() >= MAX_PARTY_SIZE {
+            return Err(GameErrorCode::PartyFull.into());
+        }
+        if self.position(player).is_some() || self.invites.contains(&player) {
+            return Err(GameErrorCode::AlreadyInParty.into());
+        }
+        self.invites.push(player);
+        Ok(())
+    }
+
+    pub fn join(&mut self, player: Pubkey) -> Result<()> {
+        let invite = self
+            .invites
+            .iter()
+            .position(|invited| *invited == player)
+            .ok_or(GameErrorCode::NotInvited)?;
+        self.invites.remove(invite);
+        self.members.push(PartyMember {
+            player,
+            ..Default::default()
+        });
+        Ok(())
+    }
+
+    // Returns true when the party is empty afterwards
+    pub fn leave(&mut self, player: Pubkey) -> Result<bool> {
+        let index = self.position(player).ok_or(GameErrorCode::NotInParty)?;
+        self.members.remove(index);
+        if self.members.is_empty() {
+            return Ok(true);
+        }
+
+        if self.leader == player {
+            self.leader = self.members[0].player;
+            msg!("New party leader {}", self.leader);
+        }
+        self.loot_turn %= self.members.len() as u8;
+        Ok(false)
+    }
+
+    // The instance the party plays on for this floor, if a member is already
+    // there.
+    pub fn instance_for(&self, season: u16, floor_id: u32) -> Option<u16> {
+        if self.season == season && self.floor_id == floor_id {
+            Some(self.instance)
+        } else {
+            None
+        }
+    }
+
+    pub fn set_instance(&mut self, season: u16, floor_id: u32, instance: u16) {
+        self.season = season;
+        self.floor_id = floor_id;
+        self.instance = instance;
+    }
+
+    // Shares what the player gained since the snapshot by the rules of the
+    // party.
+    pub fn share(&mut self, player: &mut PlayerData, before: LootSnapshot) {
+        let finder = match self.position(player.authority) {
+            Some(finder) => finder,
+            None => return,
+        };
+
+        let xp = player.total_xp().saturating_sub(before.total_xp) as u32;
+        if self.xp_rule == PARTY_XP_SHARED && xp > 0 {
+            let share = xp * PARTY_XP_SHARE_PERCENT / 100;
+            for (index, member) in self.members.iter_mut().enumerate() {
+                if index != finder {
+                    member.pending_xp += share;
+                }
+            }
+        }
+
+        let keys = player.keys.saturating_sub(before.keys);
+        let potions = player.health_potions.saturating_sub(before.health_potions);
+        if self.loot_rule == PARTY_LOOT_ROUND_ROBIN && (keys > 0 || potions > 0) {
+            let receiver = self.loot_turn as usize % self.members.len();
+            self.loot_turn = ((receiver + 1) % self.members.len()) as u8;
+            if receiver != finder {
+                player.keys -= keys;
+                player.health_potions -= potions;
+                self.members[receiver].pending_keys += keys;
+                self.members[receiver].pending_potions += potions;
+            }
+        }
+    }
+
+    // Returns the levels gained from the xp share, see GameData::apply_level_up.
+    pub fn claim_share(&mut self, player: &mut PlayerData) -> Result<u32> {
+        let index = self
+            .position(player.authority)
+            .ok_or(GameErrorCode::NotInParty)?;
+        let member = &mut self.members[index];
+
+        let levels = player.add_xp(member.pending_xp);
+        player.keys += member.pending_keys;
+        player.health_potions += member.pending_potions;
+        msg!(
+            "Party share {} xp {} keys {} potions",
+            member.pending_xp,
+            member.pending_keys,
+            member.pending_potions
+        );
+
+        member.pending_xp = 0;
+        member.pending_keys = 0;
+        member.pending_potions = 0;
+        Ok(levels)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn party(founder: Pubkey, xp_rule: u8, loot_rule: u8) -> Party {
+        Party {
+            founder,
+            leader: founder,
+            members: vec![PartyMember {
+                player: founder,
+                ..Default::default()
+            }],
+            invites: Vec::new(),
+            xp_rule,
+            loot_rule,
+            loot_turn: 0,
+            season: 0,
+            floor_id: 0,
+            instance: 0,
+        }
+    }
+
+    fn player(authority: Pubkey) -> PlayerData {
+        PlayerData {
+            authority,
+            level: 1,
+            ..Default::default()
+        }
+    }
+
+    // Only invited players join, and invites count against the size
+    #[test]
+    fn invites_fill_the_party() {
+        let founder = Pubkey::new_unique();
+        let mut party = party(founder, PARTY_XP_SOLO, PARTY_LOOT_FINDER);
+
+        assert!(party.join(Pubkey::new_unique()).is_err());
+        assert!(party.invite(founder).is_err());
+        for _ in 1..MAX_PARTY_SIZE {
+            party.invite(Pubkey::new_unique()).unwrap();
+        }
+        assert!(party.invite(Pubkey::new_unique()).is_err());
+
+        let invited = party.invites[0];
+        party.join(invited).unwrap();
+        assert_eq!(party.members.len(), 2);
+        assert!(party.join(invited).is_err());
+    }
+
+    // The next member leads when the leader leaves, the last one closes it
+    #[test]
+    fn leaving_hands_over_the_lead() {
+        let founder = Pubkey::new_unique();
+        let member = Pubkey::new_unique();
+        let mut party = party(founder, PARTY_XP_SOLO, PARTY_LOOT_FINDER);
+        party.invite(member).unwrap();
+        party.join(member).unwrap();
+
+        assert!(!party.leave(founder).unwrap());
+        assert_eq!(party.leader, member);
+        assert!(party.leave(founder).is_err());
+        assert!(party.leave(member).unwrap());
+    }
+
+    // Other members get a part of the xp on top, the finder keeps all of it
+    #[test]
+    fn shared_xp_goes_to_the_others() {
+        let founder = Pubkey::new_unique();
+        let member = Pubkey::new_unique();
+        let mut party = party(founder, PARTY_XP_SHARED, PARTY_LOOT_FINDER);
+        party.invite(member).unwrap();
+        party.join(member).unwrap();
+
+        let mut finder = player(founder);
+        let before = LootSnapshot::of(&finder);
+        finder.xp += 10;
+        party.share(&mut finder, before);
+
+        assert_eq!(finder.xp, 10);
+        assert_eq!(party.members[0].pending_xp, 0);
+        assert_eq!(
+            party.members[1].pending_xp,
+            10 * PARTY_XP_SHARE_PERCENT / 100
+        );
+
+        let mut other = player(member);
+        party.claim_share(&mut other).unwrap();
+        assert_eq!(other.total_xp(), (10 * PARTY_XP_SHARE_PERCENT / 100) as u64);
+        assert_eq!(party.members[1].pending_xp, 0);
+    }
+
+    // Chest loot goes around the party, turn by turn
+    #[test]
+    fn round_robin_loot_takes_turns() {
+        let founder = Pubkey::new_unique();
+        let member = Pubkey::new_unique();
+        let mut party = party(founder, PARTY_XP_SOLO, PARTY_LOOT_ROUND_ROBIN);
+        party.invite(member).unwrap();
+        party.join(member).unwrap();
+
+        let mut finder = player(founder);
+        for _ in 0..2 {
+            let before = LootSnapshot::of(&finder);
+            finder.keys += 1;
+            finder.health_potions += 1;
+            party.share(&mut finder, before);
+        }
+
+        // The founder kept the first chest, the second went to the member
+        assert_eq!(finder.keys, 1);
+        assert_eq!(finder.health_potions, 1);
+        assert_eq!(party.members[1].pending_keys, 1);
+        assert_eq!(party.members[1].pending_potions, 1);
+
+        let mut other = player(member);
+        party.claim_share(&mut other).unwrap();
+        assert_eq!(other.keys, 1);
+        assert_eq!(other.health_potions, 1);
+        assert!(party
+            .claim_share(&mut player(Pubkey::new_unique()))
+            .is_err());
+    }
+}
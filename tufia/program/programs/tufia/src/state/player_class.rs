@@ -0,0 +1,129 @@
+use crate::GameErrorCode;
+use anchor_lang::prelude::*;
+use std::cmp;
+
+use super::game_data::TileData;
+
+// Class 0 is what players created before classes existed (and enemies) get.
+// It keeps the original stats and growth.
+pub const CLASS_NONE: u8 = 0;
+pub const CLASS_WARRIOR: u8 = 1;
+pub const CLASS_ROGUE: u8 = 2;
+pub const CLASS_MAGE: u8 = 3;
+
+pub struct ClassStats {
+    pub max_health: u32,
+    pub damage: u32,
+    pub defence: u32,
+    pub armor: u32,
+    pub crit_chance: u8,
+    pub crit_multiplier: u8,
+    pub evasion: u8,
+    pub damage_range: u8,
+}
+
+// What a class gains on every level up
+pub struct ClassGrowth {
+    pub max_health: u32,
+    pub damage: u32,
+}
+
+const BASE_STATS: [ClassStats; 4] = [
+    ClassStats {
+        max_health: 10,
+        damage: 1,
+        defence: 0,
+        armor: 0,
+        crit_chance: 5,
+        crit_multiplier: 150,
+        evasion: 5,
+        damage_range: 1,
+    },
+    ClassStats {
+        max_health: 14,
+        damage: 1,
+        defence: 1,
+        armor: 3,
+        crit_chance: 5,
+        crit_multiplier: 150,
+        evasion: 0,
+        damage_range: 1,
+    },
+    ClassStats {
+        max_health: 9,
+        damage: 2,
+        defence: 0,
+        armor: 1,
+        crit_chance: 20,
+        crit_multiplier: 200,
+        evasion: 15,
+        damage_range: 1,
+    },
+    ClassStats {
+        max_health: 8,
+        damage: 2,
+        defence: 0,
+        armor: 1,
+        crit_chance: 10,
+        crit_multiplier: 150,
+        evasion: 5,
+        damage_range: 3,
+    },
+];
+
+const GROWTH: [ClassGrowth; 4] = [
+    ClassGrowth {
+        max_health: 1,
+        damage: 1,
+    },
+    ClassGrowth {
+        max_health: 3,
+        damage: 1,
+    },
+    ClassGrowth {
+        max_health: 1,
+        damage: 1,
+    },
+    ClassGrowth {
+        max_health: 1,
+        damage: 2,
+    },
+];
+
+pub fn validate_class(class: u8) -> Result<()> {
+    if class == CLASS_NONE || class as usize >= BASE_STATS.len() {
+        return Err(GameErrorCode::InvalidClass.into());
+    }
+
+    Ok(())
+}
+
+pub fn base_stats(class: u8) -> &'static ClassStats {
+    BASE_STATS
+        .get(class as usize)
+        .unwrap_or(&BASE_STATS[CLASS_NONE as usize])
+}
+
+pub fn growth(class: u8) -> &'static ClassGrowth {
+    GROWTH
+        .get(class as usize)
+        .unwrap_or(&GROWTH[CLASS_NONE as usize])
+}
+
+// Damage of the class ability, used once per encounter. It can not be evaded.
+// Warrior: Cleave, a hit that ignores the defence of the enemy.
+// Rogue: Backstab, a hit with double the crit multiplier.
+// Mage: Fireball, a hit with two extra damage per level.
+pub fn ability_damage(attacker: &TileData, defender: &TileData) -> Result<u32> {
+    let damage = attacker.tile_damage;
+    let defence = defender.tile_defence;
+    match attacker.tile_class {
+        CLASS_WARRIOR => Ok(damage),
+        CLASS_ROGUE => {
+            let crit_multiplier = cmp::max(attacker.tile_crit_multiplier as u32, 100);
+            Ok(damage.saturating_sub(defence) * 2 * crit_multiplier / 100)
+        }
+        CLASS_MAGE => Ok((damage + 2 * attacker.tile_level).saturating_sub(defence)),
+        _ => Err(GameErrorCode::NoClassAbility.into()),
+    }
+}
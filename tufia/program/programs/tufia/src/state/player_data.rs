@@ -1,9 +1,19 @@
-use crate::constants::*;
+use crate::{constants::*, GameErrorCode};
 use anchor_lang::prelude::*;
 
-use super::game_data::{TileData, TileData2};
+use super::{
+    buffs::{self, Buff},
+    game_data::{TileData, TileData2, XorShift64, STATE_PLAYER},
+    inventory::InventoryItem,
+    item::GearItem,
+    leaderboard::LifetimeStats,
+    name_registry, player_class,
+    season::SeasonBadge,
+    skills, status,
+};
 
 #[account]
+#[derive(Default)]
 pub struct PlayerData {
     pub authority: Pubkey,
     pub name: String,
@@ -20,7 +30,62 @@ pub struct PlayerData {
     pub last_id: u16,
     pub current_floor: u16,
     pub tile_data: TileData2,
-    //pub inventory: Vec<Item>,
+    pub keys: u32,
+    pub shrine_damage_bonus: u32,
+    pub shrine_buff_moves: u32,
+    pub health_potions: u32,
+    pub encounter: Encounter,
+    pub crit_chance: u8,
+    pub crit_multiplier: u8,
+    pub evasion: u8,
+    pub damage_range: u8,
+    pub class: u8,
+    pub max_armor: u32,
+    pub stat_points: u16,
+    pub skill_points: u16,
+    pub skills: u32,
+    pub avatar: u8,
+    pub title: String,
+    pub stats: LifetimeStats,
+    pub season: u16,
+    pub season_stats: LifetimeStats,
+    pub badges: Vec<SeasonBadge>,
+    pub party: Pubkey,
+    pub guild: Pubkey,
+    pub inventory: Vec<InventoryItem>,
+    pub gear: Vec<GearItem>,
+    pub buffs: Vec<Buff>,
+    pub last_heal: i64,
+    pub current_instance: u16,
+    pub rng_nonce: u64,
+}
+
+// The enemy the player is currently fighting. While it is active the player
+// can not move and has to use combat_action on the floor account the
+// encounter started on.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct Encounter {
+    pub active: bool,
+    pub enemy_x: u8,
+    pub enemy_y: u8,
+    pub round: u16,
+    pub ability_used: bool,
+    pub floor: Pubkey,
+}
+
+// How many unspent stat points go into each stat
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct StatAllocation {
+    pub health: u16,
+    pub damage: u16,
+    pub defence: u16,
+    pub armor: u16,
+}
+
+impl StatAllocation {
+    pub fn total(&self) -> u32 {
+        self.health as u32 + self.damage as u32 + self.defence as u32 + self.armor as u32
+    }
 }
 
 /*pub struct Item {
@@ -41,24 +106,45 @@ impl PlayerData {
         Ok(())
     }
 
-    pub fn add_xp(&mut self, amount: u32) {
+    // Grows the base stats and the carried tile, returns the number of levels
+    // gained. On a floor the board tile is grown by GameData::apply_level_up.
+    pub fn add_xp(&mut self, amount: u32) -> u32 {
         self.xp += amount;
 
+        let growth = player_class::growth(self.class);
+        let mut levels = 0;
         while self.xp >= self.xp_threshold() {
             self.xp -= self.xp_threshold();
             self.level += 1;
-            self.max_health += 1;
-            self.health = self.max_health;
-            self.damage += 1;
+            self.max_health += growth.max_health;
+            self.damage += growth.damage;
+            self.stat_points += STAT_POINTS_PER_LEVEL;
+            self.skill_points += SKILL_POINTS_PER_LEVEL;
 
-            println!("Leveled up! Current level: {}", self.level);
+            self.tile_data.tile_max_health += growth.max_health;
+            self.tile_data.tile_damage += growth.damage;
+            self.set_health(self.tile_data.tile_max_health);
+            levels += 1;
         }
+        if levels > 0 {
+            self.tile_data.tile_level = self.level;
+            self.tile_data.tile_xp = self.xp;
+            msg!("Leveled up! Current level: {}", self.level);
+        }
+        levels
     }
 
     pub fn xp_threshold(&self) -> u32 {
         5 * self.level // Example: Each level requires 100 * level XP
     }
 
+    // All xp ever earned in this life, the thresholds of the levels below
+    // plus the current xp.
+    pub fn total_xp(&self) -> u64 {
+        let level = self.level as u64;
+        5 * level * level.saturating_sub(1) / 2 + self.xp as u64
+    }
+
     pub fn update_energy(&mut self) -> Result<()> {
         // Get the current timestamp
         let current_timestamp = Clock::get()?.unix_timestamp;
@@ -84,7 +170,236 @@ impl PlayerData {
         Ok(())
     }
 
-    pub fn move_to_tile(&mut self, x: u64, y: u64, player: Pubkey) -> Result<()> {
+    // Health refills over time like energy. Returns how much of the missing
+    // health came back, the caller puts it on the tile.
+    pub fn take_health_regen(
+        &mut self,
+        health: u32,
+        max_health: u32,
+        seconds_per_health: i64,
+    ) -> Result<u32> {
+        let current_timestamp = Clock::get()?.unix_timestamp;
+        Ok(self.health_regen_at(current_timestamp, health, max_health, seconds_per_health))
+    }
+
+    fn health_regen_at(
+        &mut self,
+        current_timestamp: i64,
+        health: u32,
+        max_health: u32,
+        seconds_per_health: i64,
+    ) -> u32 {
+        if seconds_per_health <= 0 {
+            self.last_heal = current_timestamp;
+            return 0;
+        }
+
+        let mut time_passed: i64 = current_timestamp - self.last_heal;
+        let mut time_spent = 0;
+        let mut healed = 0;
+
+        while time_passed >= seconds_per_health && health + healed < max_health {
+            healed += 1;
+            time_passed -= seconds_per_health;
+            time_spent += seconds_per_health;
+        }
+
+        if health + healed >= max_health {
+            self.last_heal = current_timestamp;
+        } else {
+            self.last_heal += time_spent;
+        }
+
+        healed
+    }
+
+    // Housekeeping for players that are not on a board, on a floor use
+    // GameData::update_health.
+    pub fn update_health(&mut self, seconds_per_health: i64) -> Result<()> {
+        let healed = self.take_health_regen(
+            self.tile_data.tile_health,
+            self.tile_data.tile_max_health,
+            seconds_per_health,
+        )?;
+        self.set_health(self.tile_data.tile_health + healed);
         Ok(())
     }
+
+    // The tile is the source of truth for health, the board tile while on a
+    // floor and the carried tile in between. The health field mirrors it.
+    pub fn set_health(&mut self, health: u32) {
+        self.tile_data.tile_health = health;
+        self.health = health;
+    }
+
+    // Builds the carried tile from the stats of the player, used when the
+    // player starts out and when they go back to the lobby after dying. The
+    // next floor is joined with this tile. Active buffs are added on top, so
+    // taking them away again when they run out leaves the base stats.
+    pub fn reset_tile(&mut self) {
+        let mut tile = TileData {
+            tile_type: STATE_PLAYER,
+            tile_owner: self.authority,
+            tile_level: self.level,
+            tile_xp: self.xp,
+            tile_damage: self.damage,
+            tile_defence: self.defence,
+            tile_armor: self.max_armor,
+            tile_max_armor: self.max_armor,
+            tile_max_health: self.max_health,
+            tile_crit_chance: self.crit_chance,
+            tile_crit_multiplier: self.crit_multiplier,
+            tile_evasion: self.evasion,
+            tile_damage_range: self.damage_range,
+            tile_class: self.class,
+            tile_avatar: self.avatar,
+            tile_name: name_registry::name_tag(&self.name),
+            tile_status: status::with_inflicts(0, self.gear_status()),
+            ..Default::default()
+        };
+        for buff in self.buffs.iter() {
+            buffs::apply_buff(&mut tile, buff, true);
+        }
+        self.tile_data = tile.into();
+        self.set_health(self.max_health);
+    }
+
+    pub fn move_to_tile(&mut self) -> Result<()> {
+        let discount_chance = skills::energy_discount_chance(self.skills);
+        if discount_chance > 0 && XorShift64::for_player(self)?.next() % 100 < discount_chance {
+            msg!("Move without energy cost");
+            return Ok(());
+        }
+
+        self.energy = self
+            .energy
+            .checked_sub(ENERGY_PER_MOVE)
+            .ok_or(GameErrorCode::NotEnoughEnergy)?;
+        Ok(())
+    }
+
+    // Spends stat points on the base stats and on the tile that is carried to
+    // the next floor. The board tile is updated by GameData.
+    pub fn spend_stat_points(&mut self, allocation: &StatAllocation) -> Result<()> {
+        let total = allocation.total();
+        if total == 0 || total > self.stat_points as u32 {
+            return Err(GameErrorCode::NotEnoughStatPoints.into());
+        }
+        self.stat_points -= total as u16;
+
+        let health = allocation.health as u32 * HEALTH_PER_STAT_POINT;
+        self.max_health += health;
+        self.health += health;
+        self.damage += allocation.damage as u32;
+        self.defence += allocation.defence as u32;
+        self.max_armor += allocation.armor as u32;
+
+        self.tile_data.tile_max_health += health;
+        self.tile_data.tile_health += health;
+        self.tile_data.tile_damage += allocation.damage as u32;
+        self.tile_data.tile_defence += allocation.defence as u32;
+        self.tile_data.tile_max_armor += allocation.armor as u32;
+
+        Ok(())
+    }
+
+    // Progress belongs to a season. The first action in a new season starts
+    // the player from the lobby again, level and items are kept.
+    pub fn sync_season(&mut self, season: u16) {
+        if self.season == season {
+            return;
+        }
+        msg!("Season {} started", season);
+        self.season = season;
+        self.current_floor = 0;
+        self.current_instance = 0;
+        self.season_stats = LifetimeStats::default();
+        self.encounter = Encounter::default();
+    }
+
+    pub fn record_floor(&mut self) {
+        self.stats.max_floor = self.stats.max_floor.max(self.current_floor);
+        self.season_stats.max_floor = self.season_stats.max_floor.max(self.current_floor);
+    }
+
+    pub fn record_kill(&mut self) {
+        self.stats.kills += 1;
+        self.season_stats.kills += 1;
+    }
+
+    pub fn record_death(&mut self) {
+        self.stats.deaths += 1;
+        self.season_stats.deaths += 1;
+    }
+
+    pub fn record_chest(&mut self) {
+        self.stats.chests_opened += 1;
+        self.season_stats.chests_opened += 1;
+    }
+
+    pub fn add_badge(&mut self, badge: SeasonBadge) {
+        if self.badges.len() >= MAX_SEASON_BADGES {
+            self.badges.remove(0);
+        }
+        self.badges.push(badge);
+    }
+
+    pub fn learn_skill(&mut self, skill: u8) -> Result<()> {
+        let (skills, skill_points) = skills::learn(self.skills, self.skill_points, skill)?;
+        self.skills = skills;
+        self.skill_points = skill_points;
+        msg!("Learned skill {}", skill);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Health comes back one point per period, unfinished periods are kept
+    // for the next update
+    #[test]
+    fn health_regen_keeps_the_unused_time() {
+        let mut player = PlayerData {
+            last_heal: 1000,
+            ..Default::default()
+        };
+
+        assert_eq!(player.health_regen_at(1025, 5, 10, 10), 2);
+        assert_eq!(player.last_heal, 1020);
+        assert_eq!(player.health_regen_at(1029, 7, 10, 10), 0);
+        assert_eq!(player.last_heal, 1020);
+    }
+
+    // Time spent at full health does not pile up, and a rate of 0 turns the
+    // regen off
+    #[test]
+    fn health_regen_stops_at_the_max() {
+        let mut player = PlayerData {
+            last_heal: 1000,
+            ..Default::default()
+        };
+
+        assert_eq!(player.health_regen_at(2000, 8, 10, 10), 2);
+        assert_eq!(player.last_heal, 2000);
+        assert_eq!(player.health_regen_at(3000, 5, 10, 0), 0);
+        assert_eq!(player.last_heal, 3000);
+    }
+
+    // The tile is the source of truth, the health field follows it
+    #[test]
+    fn health_follows_the_tile() {
+        let mut player = PlayerData {
+            max_health: 12,
+            ..Default::default()
+        };
+        player.reset_tile();
+        assert_eq!({ player.tile_data.tile_health }, 12);
+        assert_eq!(player.health, 12);
+
+        player.set_health(4);
+        assert_eq!({ player.tile_data.tile_health }, 4);
+        assert_eq!(player.health, 4);
+    }
 }
@@ -0,0 +1,115 @@
+use crate::{constants::*, GameErrorCode};
+use anchor_lang::prelude::*;
+
+use super::leaderboard::{Leaderboard, LeaderboardEntry};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct ArchivedEntry {
+    pub entry: LeaderboardEntry,
+    pub claimed: bool,
+}
+
+// A frozen copy of the leaderboard of a season, written by end_season. The
+// first SEASON_REWARD_RANKS players can claim a reward from it once. The
+// floor ranking pays out gold, so floors must only be gained by reaching the
+// stairs (GameData::take_stairs in next_floor).
+#[account]
+pub struct SeasonArchive {
+    pub season: u16,
+    pub metric: u8,
+    pub ended_at: i64,
+    pub entries: Vec<ArchivedEntry>,
+}
+
+// Badges are kept on the player, the oldest one is dropped when there are
+// more than MAX_SEASON_BADGES.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct SeasonBadge {
+    pub season: u16,
+    pub rank: u8,
+}
+
+impl SeasonArchive {
+    pub fn archive(&mut self, leaderboard: &Leaderboard) -> Result<()> {
+        self.season = leaderboard.season;
+        self.metric = leaderboard.metric;
+        self.ended_at = Clock::get()?.unix_timestamp;
+        self.entries = leaderboard
+            .entries
+            .iter()
+            .map(|entry| ArchivedEntry {
+                entry: *entry,
+                claimed: false,
+            })
+            .collect();
+        Ok(())
+    }
+
+    // Returns the rank of the player and the gold they get
+    pub fn claim_reward(&mut self, player: Pubkey) -> Result<(u8, u64)> {
+        let rank = self
+            .entries
+            .iter()
+            .position(|archived| archived.entry.player == player)
+            .filter(|rank| *rank < SEASON_REWARD_RANKS)
+            .ok_or(GameErrorCode::NoSeasonReward)?;
+
+        let archived = &mut self.entries[rank];
+        if archived.claimed {
+            return Err(GameErrorCode::SeasonRewardAlreadyClaimed.into());
+        }
+        archived.claimed = true;
+
+        Ok((rank as u8, reward_for_rank(rank)))
+    }
+}
+
+// First place gets SEASON_REWARD_GOLD, every place below a bit less
+pub fn reward_for_rank(rank: usize) -> u64 {
+    SEASON_REWARD_GOLD * (SEASON_REWARD_RANKS - rank) as u64 / SEASON_REWARD_RANKS as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn archive(players: usize) -> SeasonArchive {
+        SeasonArchive {
+            season: 1,
+            metric: 0,
+            ended_at: 0,
+            entries: (0..players)
+                .map(|_| ArchivedEntry {
+                    entry: LeaderboardEntry {
+                        player: Pubkey::new_unique(),
+                        ..Default::default()
+                    },
+                    claimed: false,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn reward_is_claimed_once() {
+        let mut archive = archive(3);
+        let player = archive.entries[1].entry.player;
+
+        assert_eq!(
+            archive.claim_reward(player).unwrap(),
+            (1, reward_for_rank(1))
+        );
+        assert!(archive.claim_reward(player).is_err());
+    }
+
+    #[test]
+    fn only_the_top_ranks_get_a_reward() {
+        let mut archive = archive(SEASON_REWARD_RANKS + 1);
+        let last = archive.entries[SEASON_REWARD_RANKS].entry.player;
+
+        assert!(archive.claim_reward(last).is_err());
+        assert!(archive.claim_reward(Pubkey::new_unique()).is_err());
+        assert_eq!(reward_for_rank(0), SEASON_REWARD_GOLD);
+        assert!(reward_for_rank(SEASON_REWARD_RANKS - 1) > 0);
+    }
+}
@@ -0,0 +1,104 @@
+use crate::{constants::*, GameErrorCode};
+use anchor_lang::prelude::*;
+
+use super::{game_data::GameData, player_data::PlayerData};
+
+// The lobby shop can be used from floor 0, the merchant shop only next to a
+// merchant tile. Merchants spawn on every MERCHANT_FLOOR_INTERVAL-th floor.
+pub const SHOP_LOBBY: u8 = 0;
+pub const SHOP_MERCHANT: u8 = 1;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct ShopItem {
+    pub item_id: u16,
+    pub price: u64,      // gold paid by the player
+    pub sell_price: u64, // gold paid to the player, 0 means the shop does not buy it
+    pub stock: u32,
+}
+
+// Configured by the admin. Gold of sales goes to the treasury of the shop
+// PDA and items bought back from players are paid from it.
+#[account]
+pub struct Shop {
+    pub kind: u8,
+    pub items: Vec<ShopItem>,
+}
+
+pub fn validate_kind(kind: u8) -> Result<()> {
+    if kind > SHOP_MERCHANT {
+        return Err(GameErrorCode::InvalidShop.into());
+    }
+    Ok(())
+}
+
+impl Shop {
+    pub fn set_item(&mut self, item: ShopItem) -> Result<()> {
+        match self
+            .items
+            .iter_mut()
+            .find(|listed| listed.item_id == item.item_id)
+        {
+            Some(listed) => *listed = item,
+            None => {
+                if self.items.len() >= MAX_SHOP_ITEMS {
+                    return Err(GameErrorCode::ShopFull.into());
+                }
+                self.items.push(item);
+            }
+        }
+        Ok(())
+    }
+
+    fn item(&mut self, item_id: u16) -> Result<&mut ShopItem> {
+        self.items
+            .iter_mut()
+            .find(|item| item.item_id == item_id)
+            .ok_or(GameErrorCode::ItemNotForSale.into())
+    }
+
+    // Returns the price of the purchase
+    pub fn buy(&mut self, item_id: u16, amount: u32) -> Result<u64> {
+        let item = self.item(item_id)?;
+        if amount == 0 || item.stock < amount {
+            return Err(GameErrorCode::OutOfStock.into());
+        }
+        let price = item
+            .price
+            .checked_mul(amount as u64)
+            .ok_or(GameErrorCode::ShopOverflow)?;
+        item.stock -= amount;
+        Ok(price)
+    }
+
+    // Returns the gold paid for the items
+    pub fn sell(&mut self, item_id: u16, amount: u32) -> Result<u64> {
+        let item = self.item(item_id)?;
+        if amount == 0 || item.sell_price == 0 {
+            return Err(GameErrorCode::ItemNotForSale.into());
+        }
+        let price = item
+            .sell_price
+            .checked_mul(amount as u64)
+            .ok_or(GameErrorCode::ShopOverflow)?;
+        item.stock = item
+            .stock
+            .checked_add(amount)
+            .ok_or(GameErrorCode::ShopOverflow)?;
+        Ok(price)
+    }
+
+    pub fn check_access(&self, player: &PlayerData, game_data: Option<&GameData>) -> Result<()> {
+        if player.encounter.active {
+            return Err(GameErrorCode::InCombat.into());
+        }
+
+        let at_shop = match self.kind {
+            SHOP_LOBBY => player.current_floor == 0,
+            _ => game_data.is_some_and(|game_data| game_data.is_next_to_merchant(player.authority)),
+        };
+        if !at_shop {
+            return Err(GameErrorCode::NotAtShop.into());
+        }
+        Ok(())
+    }
+}
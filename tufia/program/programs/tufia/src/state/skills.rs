@@ -0,0 +1,97 @@
+use crate::GameErrorCode;
+use anchor_lang::prelude::*;
+
+// The skill tree. Every skill is a bit in PlayerData.skills. The second tier
+// of each branch needs the first one.
+pub const SKILL_ARMOR_REGEN: u8 = 0;
+pub const SKILL_CHEST_LUCK: u8 = 1;
+pub const SKILL_ENERGY_DISCOUNT: u8 = 2;
+pub const SKILL_IRON_SKIN: u8 = 3;
+pub const SKILL_TREASURE_HUNTER: u8 = 4;
+pub const SKILL_SECOND_WIND: u8 = 5;
+
+struct SkillDefinition {
+    cost: u16,
+    requires: Option<u8>,
+}
+
+const SKILLS: [SkillDefinition; 6] = [
+    SkillDefinition {
+        cost: 1,
+        requires: None,
+    },
+    SkillDefinition {
+        cost: 1,
+        requires: None,
+    },
+    SkillDefinition {
+        cost: 1,
+        requires: None,
+    },
+    SkillDefinition {
+        cost: 2,
+        requires: Some(SKILL_ARMOR_REGEN),
+    },
+    SkillDefinition {
+        cost: 2,
+        requires: Some(SKILL_CHEST_LUCK),
+    },
+    SkillDefinition {
+        cost: 2,
+        requires: Some(SKILL_ENERGY_DISCOUNT),
+    },
+];
+
+pub fn has_skill(skills: u32, skill: u8) -> bool {
+    skills & (1 << skill) != 0
+}
+
+// Returns the new skill bits and the skill points left after learning.
+pub fn learn(skills: u32, skill_points: u16, skill: u8) -> Result<(u32, u16)> {
+    let definition = SKILLS
+        .get(skill as usize)
+        .ok_or(GameErrorCode::InvalidSkill)?;
+
+    if has_skill(skills, skill) {
+        return Err(GameErrorCode::SkillAlreadyLearned.into());
+    }
+
+    if let Some(required) = definition.requires {
+        if !has_skill(skills, required) {
+            return Err(GameErrorCode::SkillRequirementMissing.into());
+        }
+    }
+
+    let skill_points = skill_points
+        .checked_sub(definition.cost)
+        .ok_or(GameErrorCode::NotEnoughSkillPoints)?;
+
+    Ok((skills | (1 << skill), skill_points))
+}
+
+// Armor regained on every move
+pub fn armor_regen(skills: u32) -> u32 {
+    has_skill(skills, SKILL_ARMOR_REGEN) as u32 + has_skill(skills, SKILL_IRON_SKIN) as u32
+}
+
+// Chance in percent that a chest pays out twice
+pub fn chest_luck_chance(skills: u32) -> u64 {
+    if has_skill(skills, SKILL_TREASURE_HUNTER) {
+        60
+    } else if has_skill(skills, SKILL_CHEST_LUCK) {
+        30
+    } else {
+        0
+    }
+}
+
+// Chance in percent that a move costs no energy
+pub fn energy_discount_chance(skills: u32) -> u64 {
+    if has_skill(skills, SKILL_SECOND_WIND) {
+        50
+    } else if has_skill(skills, SKILL_ENERGY_DISCOUNT) {
+        25
+    } else {
+        0
+    }
+}
@@ -0,0 +1,196 @@
+use std::cmp;
+
+use crate::constants::*;
+use anchor_lang::prelude::*;
+
+use super::game_data::{TileData, XorShift64};
+
+// The low bits of tile_status are the effects on the tile, the high bits the
+// effects its hits put on others. All effects on a tile share
+// tile_status_turns and tick on every action of the tile, see tick.
+pub const STATUS_POISON: u8 = 1; // damage over time, ignores armor
+pub const STATUS_BURN: u8 = 2; // damage over time, armor first
+pub const STATUS_STUN: u8 = 4; // skips the next attack
+pub const STATUS_SHRED: u8 = 8; // halves the defence
+
+const ACTIVE_MASK: u8 = 0x0f;
+const INFLICT_SHIFT: u8 = 4;
+
+pub fn active(tile: &TileData) -> u8 {
+    tile.tile_status & ACTIVE_MASK
+}
+
+pub fn inflicts(tile: &TileData) -> u8 {
+    tile.tile_status >> INFLICT_SHIFT
+}
+
+pub fn with_inflicts(status: u8, effects: u8) -> u8 {
+    (status & ACTIVE_MASK) | (effects << INFLICT_SHIFT)
+}
+
+// Enemies from the third floor on can be venomous, fiery, stunning or
+// corrosive.
+pub fn roll_archetype(rng: &mut XorShift64, floor: u32) -> u8 {
+    if floor < 2 || rng.next() % 100 >= ENEMY_STATUS_CHANCE {
+        return 0;
+    }
+    let effect = 1 << (rng.next() % 4);
+    with_inflicts(0, effect)
+}
+
+// After a hit that was not evaded
+pub fn inflict(attacker: &TileData, defender: &mut TileData) {
+    let effects = inflicts(attacker);
+    if effects == 0 {
+        return;
+    }
+    defender.tile_status |= effects;
+    defender.tile_status_turns = cmp::max(defender.tile_status_turns, STATUS_TURNS);
+    msg!("Status effects {} for {} turns", effects, STATUS_TURNS);
+}
+
+// A stunned tile loses its next attack, the stun is used up by that
+pub fn take_stun(tile: &mut TileData) -> bool {
+    if active(tile) & STATUS_STUN == 0 {
+        return false;
+    }
+    tile.tile_status &= !STATUS_STUN;
+    msg!("Stunned, attack skipped");
+    true
+}
+
+pub fn defence(tile: &TileData) -> u32 {
+    if active(tile) & STATUS_SHRED != 0 {
+        tile.tile_defence / 2
+    } else {
+        tile.tile_defence
+    }
+}
+
+// Runs once per action of the tile. Returns the damage over time it took.
+pub fn tick(tile: &mut TileData) -> u32 {
+    if tile.tile_status_turns == 0 {
+        return 0;
+    }
+    let effects = active(tile);
+    let mut damage = 0;
+
+    if effects & STATUS_POISON != 0 {
+        let poison = cmp::min(POISON_DAMAGE, tile.tile_health);
+        tile.tile_health -= poison;
+        damage += poison;
+    }
+    if effects & STATUS_BURN != 0 {
+        let burn = cmp::min(BURN_DAMAGE, tile.tile_armor);
+        tile.tile_armor -= burn;
+        let rest = cmp::min(BURN_DAMAGE - burn, tile.tile_health);
+        tile.tile_health -= rest;
+        damage += burn + rest;
+    }
+
+    tile.tile_status_turns -= 1;
+    if tile.tile_status_turns == 0 {
+        tile.tile_status &= !ACTIVE_MASK;
+        msg!("Status effects wore off");
+    }
+    damage
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::game_data::tests::sample_tile;
+
+    fn clean_tile() -> TileData {
+        TileData {
+            tile_status: 0,
+            tile_status_turns: 0,
+            ..sample_tile()
+        }
+    }
+
+    // Hits pass on what the attacker inflicts, the turns are refreshed
+    #[test]
+    fn hits_inflict_the_effects_of_the_attacker() {
+        let attacker = TileData {
+            tile_status: with_inflicts(0, STATUS_POISON | STATUS_STUN),
+            ..clean_tile()
+        };
+        let mut defender = clean_tile();
+        inflict(&clean_tile(), &mut defender);
+        assert_eq!(active(&defender), 0);
+
+        inflict(&attacker, &mut defender);
+        assert_eq!(active(&defender), STATUS_POISON | STATUS_STUN);
+        assert_eq!(inflicts(&defender), 0);
+        assert_eq!({ defender.tile_status_turns }, STATUS_TURNS);
+    }
+
+    // A stun skips exactly one attack
+    #[test]
+    fn stun_is_used_up() {
+        let mut tile = TileData {
+            tile_status: STATUS_STUN,
+            tile_status_turns: STATUS_TURNS,
+            ..clean_tile()
+        };
+        assert!(take_stun(&mut tile));
+        assert!(!take_stun(&mut tile));
+    }
+
+    #[test]
+    fn shred_halves_the_defence() {
+        let mut tile = clean_tile();
+        tile.tile_defence = 9;
+        assert_eq!(defence(&tile), 9);
+        tile.tile_status = STATUS_SHRED;
+        assert_eq!(defence(&tile), 4);
+    }
+
+    // Poison goes straight to health, burn eats the armor first
+    #[test]
+    fn damage_over_time() {
+        let mut poisoned = TileData {
+            tile_status: STATUS_POISON,
+            tile_status_turns: STATUS_TURNS,
+            tile_armor: 5,
+            tile_health: 8,
+            ..clean_tile()
+        };
+        assert_eq!(tick(&mut poisoned), POISON_DAMAGE);
+        assert_eq!({ poisoned.tile_armor }, 5);
+        assert_eq!({ poisoned.tile_health }, 8 - POISON_DAMAGE);
+
+        let mut burning = TileData {
+            tile_status: STATUS_BURN,
+            tile_status_turns: STATUS_TURNS,
+            tile_armor: 1,
+            tile_health: 8,
+            ..clean_tile()
+        };
+        assert_eq!(tick(&mut burning), BURN_DAMAGE);
+        assert_eq!({ burning.tile_armor }, 0);
+        assert_eq!({ burning.tile_health }, 8 - (BURN_DAMAGE - 1));
+    }
+
+    // The effects wear off after their turns, what the tile inflicts stays
+    #[test]
+    fn effects_wear_off() {
+        let mut tile = TileData {
+            tile_status: with_inflicts(STATUS_POISON, STATUS_BURN),
+            tile_status_turns: STATUS_TURNS,
+            tile_health: 8,
+            ..clean_tile()
+        };
+        for _ in 0..STATUS_TURNS {
+            tick(&mut tile);
+        }
+        assert_eq!(active(&tile), 0);
+        assert_eq!(inflicts(&tile), STATUS_BURN);
+        assert_eq!(tick(&mut tile), 0);
+        assert_eq!(
+            { tile.tile_health },
+            8 - STATUS_TURNS as u32 * POISON_DAMAGE
+        );
+    }
+}
@@ -0,0 +1,231 @@
+use crate::{constants::*, GameErrorCode};
+use anchor_lang::prelude::*;
+
+use super::{inventory::InventoryItem, item::GearItem, player_data::PlayerData};
+
+// One side of a trade. Gold moves as tokens, items and gear move between the
+// PlayerData inventories.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct TradeItems {
+    pub items: Vec<InventoryItem>,
+    pub gear: Vec<GearItem>,
+    pub gold: u64,
+}
+
+// What the maker puts into the escrow. The gear is picked by its index in
+// the inventory of the maker.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct OfferArgs {
+    pub items: Vec<InventoryItem>,
+    pub gear: Vec<u8>,
+    pub gold: u64,
+    pub requested: TradeItems,
+    pub taker: Pubkey, // default means anyone can accept
+    pub expires_at: i64,
+}
+
+// The escrow of an offer. The offered items are taken out of the inventory of
+// the maker when the offer is created and the offered gold sits in the token
+// account of this PDA until the offer is accepted or cancelled.
+#[account]
+pub struct Offer {
+    pub maker: Pubkey,
+    pub id: u32,
+    pub taker: Pubkey,
+    pub offered: TradeItems,
+    pub requested: TradeItems,
+    pub expires_at: i64,
+}
+
+impl TradeItems {
+    pub fn validate(&self) -> Result<()> {
+        if self.items.len() > MAX_TRADE_ITEMS || self.gear.len() > MAX_TRADE_ITEMS {
+            return Err(GameErrorCode::TradeTooLarge.into());
+        }
+        if self.items.iter().any(|item| item.amount == 0) {
+            return Err(GameErrorCode::InvalidItem.into());
+        }
+        Ok(())
+    }
+}
+
+impl Offer {
+    pub fn check_taker(&self, taker: Pubkey) -> Result<()> {
+        if taker == self.maker || (self.taker != Pubkey::default() && self.taker != taker) {
+            return Err(GameErrorCode::NotOfferTaker.into());
+        }
+        if Clock::get()?.unix_timestamp >= self.expires_at {
+            return Err(GameErrorCode::OfferExpired.into());
+        }
+        Ok(())
+    }
+}
+
+impl PlayerData {
+    // Moves the offered items of the maker into the escrow
+    pub fn escrow_items(&mut self, args: &OfferArgs) -> Result<TradeItems> {
+        let mut indices = args.gear.clone();
+        indices.sort_unstable_by(|a, b| b.cmp(a));
+        if indices.windows(2).any(|pair| pair[0] == pair[1]) {
+            return Err(GameErrorCode::InvalidItem.into());
+        }
+
+        let mut gear = Vec::with_capacity(indices.len());
+        for index in indices {
+            gear.push(self.remove_gear(index)?);
+        }
+        for item in &args.items {
+            self.remove_item(item.item_id, item.amount)?;
+        }
+
+        Ok(TradeItems {
+            items: args.items.clone(),
+            gear,
+            gold: args.gold,
+        })
+    }
+
+    // Takes the requested items from the taker. Gear is matched by its stats
+    // and has to be unequipped.
+    pub fn take_items(&mut self, trade: &TradeItems) -> Result<()> {
+        for wanted in &trade.gear {
+            let index = self
+                .gear
+                .iter()
+                .position(|item| item.same_item(wanted) && !item.equipped)
+                .ok_or(if self.gear.iter().any(|item| item.same_item(wanted)) {
+                    GameErrorCode::ItemEquipped
+                } else {
+                    GameErrorCode::NotEnoughItems
+                })?;
+            self.gear.remove(index);
+        }
+        for item in &trade.items {
+            self.remove_item(item.item_id, item.amount)?;
+        }
+        Ok(())
+    }
+
+    pub fn give_items(&mut self, trade: &TradeItems) -> Result<()> {
+        for item in &trade.gear {
+            self.add_gear(*item)?;
+        }
+        for item in &trade.items {
+            self.add_item(item.item_id, item.amount)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::inventory::{ITEM_CRYSTAL, ITEM_SCRAP};
+
+    fn gear(damage: u32) -> GearItem {
+        GearItem {
+            damage,
+            ..Default::default()
+        }
+    }
+
+    fn scrap(amount: u32) -> InventoryItem {
+        InventoryItem {
+            item_id: ITEM_SCRAP,
+            amount,
+        }
+    }
+
+    fn maker() -> PlayerData {
+        let mut player = PlayerData::default();
+        player.add_item(ITEM_SCRAP, 5).unwrap();
+        for damage in 0..3 {
+            player.add_gear(gear(damage)).unwrap();
+        }
+        player
+    }
+
+    // The offered gear and items leave the inventory of the maker
+    #[test]
+    fn escrow_takes_the_offer_out() {
+        let mut player = maker();
+        let args = OfferArgs {
+            items: vec![scrap(3)],
+            gear: vec![0, 2],
+            gold: 7,
+            ..Default::default()
+        };
+
+        let escrow = player.escrow_items(&args).unwrap();
+        assert_eq!(escrow.gold, 7);
+        assert_eq!(escrow.items.len(), 1);
+        assert_eq!(escrow.gear.len(), 2);
+        assert_eq!(player.gear.len(), 1);
+        assert!(player.gear[0].same_item(&gear(1)));
+        assert_eq!(player.item_count(ITEM_SCRAP), 2);
+    }
+
+    // The same gear can not be offered twice, and neither can equipped gear
+    // or items the maker does not have
+    #[test]
+    fn escrow_rejects_what_the_maker_can_not_give() {
+        let twice = OfferArgs {
+            gear: vec![1, 1],
+            ..Default::default()
+        };
+        assert!(maker().escrow_items(&twice).is_err());
+
+        let missing = OfferArgs {
+            items: vec![scrap(6)],
+            ..Default::default()
+        };
+        assert!(maker().escrow_items(&missing).is_err());
+
+        let mut player = maker();
+        player.gear[0].equipped = true;
+        let equipped = OfferArgs {
+            gear: vec![0],
+            ..Default::default()
+        };
+        assert!(player.escrow_items(&equipped).is_err());
+    }
+
+    // Requested gear is matched by its stats and must not be equipped
+    #[test]
+    fn take_items_skips_equipped_gear() {
+        let mut player = maker();
+        player.gear[1].equipped = true;
+        let wanted = TradeItems {
+            gear: vec![gear(1)],
+            ..Default::default()
+        };
+        assert!(player.take_items(&wanted).is_err());
+
+        player.gear[1].equipped = false;
+        player.take_items(&wanted).unwrap();
+        assert_eq!(player.gear.len(), 2);
+        assert!(!player.gear.iter().any(|item| item.same_item(&gear(1))));
+    }
+
+    // Whatever comes out of the escrow arrives unequipped
+    #[test]
+    fn give_items_fills_the_inventory() {
+        let mut player = PlayerData::default();
+        let trade = TradeItems {
+            items: vec![InventoryItem {
+                item_id: ITEM_CRYSTAL,
+                amount: 2,
+            }],
+            gear: vec![GearItem {
+                equipped: true,
+                ..gear(4)
+            }],
+            gold: 0,
+        };
+
+        player.give_items(&trade).unwrap();
+        assert_eq!(player.item_count(ITEM_CRYSTAL), 2);
+        assert_eq!(player.gear.len(), 1);
+        assert!(!player.gear[0].equipped);
+    }
+}
@@ -10,8 +10,10 @@
 using Tufia;
 using Tufia.Accounts;
 using Tufia.Program;
+using Tufia.Types;
 using Solana.Unity.Programs;
 using Solana.Unity.Programs.Models;
+using Solana.Unity.Programs.Utilities;
 using Solana.Unity.Rpc.Core.Http;
 using Solana.Unity.Rpc.Messages;
 using Solana.Unity.Rpc.Models;
@@ -40,6 +42,12 @@ public class AnchorService : MonoBehaviour
     public const byte BUILDING_TYPE_BLUE_CHEST = 4;
     public const byte BUILDING_TYPE_STAIRS = 5;
 
+    public const byte CLASS_WARRIOR = 1;
+    public const byte CLASS_ROGUE = 2;
+    public const byte CLASS_MAGE = 3;
+
+    public const int MAX_PLAYERS_PER_INSTANCE = 8;
+
     public static AnchorService Instance { get; private set; }
     public static Action<PlayerData> OnPlayerDataChanged;
     public static Action<GameData, bool> OnGameDataChanged;
@@ -57,14 +65,17 @@ public class AnchorService : MonoBehaviour
 
     private SessionWallet sessionWallet;
     private PublicKey PlayerDataPDA;
-    //private PublicKey GameDataPDA;
+    private PublicKey ConfigPDA;
+    private ushort season;
     private bool _isInitialized;
     private TufiaClient anchorClient;
     private int blockingTransactionsInProgress;
     private int nonBlockingTransactionsInProgress;
     private long? sessionValidUntil;
     private string sessionKeyPassword = "inGame"; // Would be better to generate and save in playerprefs
-    private string DefaultFloorSeed = "floorssssssssss";
+    private string FloorSeed = "floor";
+    private string DirectorySeed = "directory";
+    private string ConfigSeed = "config";
     private string PlayerSeed = "player1";
     private ushort transactionCounter = 0;
 
@@ -106,6 +117,7 @@ private async void OnLogin(Account account)
 
         anchorClient = new TufiaClient(Web3.Rpc, Web3.WsRpc, AnchorProgramIdPubKey);
 
+        await LoadConfig();
         await SubscribeToPlayerDataUpdates();
         await SubscribeToGameDataUpdates(true);
 
@@ -118,35 +130,94 @@ private void FindPDAs(Account account)
           {Encoding.UTF8.GetBytes(PlayerSeed), account.PublicKey.KeyBytes},
         AnchorProgramIdPubKey, out PlayerDataPDA, out byte bump);
 
-       /* PublicKey.TryFindProgramAddress(new[]
-            {Encoding.UTF8.GetBytes(FloorSeed)},
-          AnchorProgramIdPubKey, out GameDataPDA, out byte bump2);*/
+      PublicKey.TryFindProgramAddress(new[]
+          {Encoding.UTF8.GetBytes(ConfigSeed)},
+        AnchorProgramIdPubKey, out ConfigPDA, out byte bump2);
+    }
+
+    // Floor accounts are per season, so the season has to be known before
+    // any floor can be found
+    private async Task LoadConfig()
+    {
+      try
+      {
+        var config = await anchorClient.GetGameConfigAsync(ConfigPDA, Commitment.Confirmed);
+        if (config.ParsedResult != null)
+        {
+          season = config.ParsedResult.Season;
+        }
+      }
+      catch (Exception e)
+      {
+        Debug.Log("Probably config not available " + e.Message);
+      }
     }
 
-    private PublicKey GetCurrentFloorSeed(out string seed)
+    // The floor and instance the player joined with buy_next_floor
+    private FloorAddress GetCurrentFloor()
     {
-      PublicKey result = null;
-      int currentFloor = CurrentPlayerData != null ? CurrentPlayerData.CurrentFloor : 0;
-      seed = DefaultFloorSeed + currentFloor;
+      return new FloorAddress
+      {
+        FloorId = CurrentPlayerData != null ? CurrentPlayerData.CurrentFloor : 0u,
+        Instance = CurrentPlayerData != null ? CurrentPlayerData.CurrentInstance : (ushort) 0,
+      };
+    }
+
+    // Same seeds as in the anchor program: season, floor id and instance, all little endian
+    private PublicKey GetFloorPDA(FloorAddress floor)
+    {
+      byte[] seasonBytes = new byte[2];
+      seasonBytes.WriteU16(season, 0);
+      byte[] floorIdBytes = new byte[4];
+      floorIdBytes.WriteU32(floor.FloorId, 0);
+      byte[] instanceBytes = new byte[2];
+      instanceBytes.WriteU16(floor.Instance, 0);
       PublicKey.TryFindProgramAddress(new[]
-          {Encoding.UTF8.GetBytes(seed)  },
-        AnchorProgramIdPubKey, out result, out byte bump2);
+          {Encoding.UTF8.GetBytes(FloorSeed), seasonBytes, floorIdBytes, instanceBytes},
+        AnchorProgramIdPubKey, out PublicKey result, out byte bump);
 
       return result;
     }
 
-    private PublicKey GetNextFloorSeed(out string seed)
+    private PublicKey GetDirectoryPDA(uint floorId)
     {
-      PublicKey result = null;
-      int currentFloor = CurrentPlayerData != null ? CurrentPlayerData.CurrentFloor + 1 : 1;
-      seed = DefaultFloorSeed + currentFloor;
+      byte[] seasonBytes = new byte[2];
+      seasonBytes.WriteU16(season, 0);
+      byte[] floorIdBytes = new byte[4];
+      floorIdBytes.WriteU32(floorId, 0);
       PublicKey.TryFindProgramAddress(new[]
-          {Encoding.UTF8.GetBytes(seed)  },
-        AnchorProgramIdPubKey, out result, out byte bump2);
+          {Encoding.UTF8.GetBytes(DirectorySeed), seasonBytes, floorIdBytes},
+        AnchorProgramIdPubKey, out PublicKey result, out byte bump);
 
       return result;
     }
 
+    // Picks the instance the same way as FloorDirectory::route, the first one
+    // with space left or a new one if all are full
+    private async Task<FloorAddress> RouteToInstance()
+    {
+      FloorAddress floor = GetCurrentFloor();
+      floor.Instance = 0;
+      try
+      {
+        var directory = await anchorClient.GetFloorDirectoryAsync(GetDirectoryPDA(floor.FloorId), Commitment.Confirmed);
+        if (directory.ParsedResult != null)
+        {
+          byte[] instances = directory.ParsedResult.Instances;
+          while (floor.Instance < instances.Length && instances[floor.Instance] + 1 > MAX_PLAYERS_PER_INSTANCE)
+          {
+            floor.Instance++;
+          }
+        }
+      }
+      catch (Exception e)
+      {
+        Debug.Log("Probably floor directory not available " + e.Message);
+      }
+
+      return floor;
+    }
+
     private static async Task RequestAirdropIfSolValueIsLow()
     {
         var solBalance = await Web3.Instance.WalletBase.GetBalance();
@@ -223,15 +294,15 @@ public async Task UnSubscribeToGameDataUpdates()
 
     public async Task SubscribeToGameDataUpdates(bool reset)
     {
-        AccountResultWrapper<GameData> gameData = null;
+        AccountResultWrapper<FloorData> gameData = null;
 
         try
         {
-          gameData = await anchorClient.GetGameDataAsync(GetCurrentFloorSeed(out String seed), Commitment.Confirmed);
+          gameData = await anchorClient.GetFloorDataAsync(GetFloorPDA(GetCurrentFloor()), Commitment.Confirmed);
             if (gameData.ParsedResult != null)
             {
-                CurrentGameData = gameData.ParsedResult;
-                OnGameDataChanged?.Invoke(gameData.ParsedResult, true);
+                CurrentGameData = gameData.ParsedResult.Board();
+                OnGameDataChanged?.Invoke(CurrentGameData, true);
             }
             else
             {
@@ -255,9 +326,9 @@ public async Task SubscribeToGameDataUpdates(bool reset)
             }
             Debug.Log("Subscription: Subscribe to game data");
 
-            gameDataSubscription = await anchorClient.SubscribeGameDataAsync(GetCurrentFloorSeed(out String seed), (state, value, gameData) =>
+            gameDataSubscription = await anchorClient.SubscribeFloorDataAsync(GetFloorPDA(GetCurrentFloor()), (state, value, floorData) =>
             {
-                OnRecievedGameDataUpdate(gameData, false);
+                OnRecievedGameDataUpdate(floorData?.Board(), false);
             }, Commitment.Processed);
         }
     }
@@ -269,7 +340,7 @@ private void OnRecievedGameDataUpdate(GameData gameData, bool reset)
         OnGameDataChanged?.Invoke(gameData, reset);
     }
 
-    public async Task InitAccounts(bool useSession)
+    public async Task InitAccounts(bool useSession, byte playerClass = CLASS_WARRIOR)
     {
         var tx = new Transaction()
         {
@@ -280,11 +351,11 @@ public async Task InitAccounts(bool useSession)
 
         InitPlayerAccounts accounts = new InitPlayerAccounts();
         accounts.Player = PlayerDataPDA;
-        accounts.GameData = GetCurrentFloorSeed(out String seed);
+        accounts.Config = ConfigPDA;
         accounts.Signer = Web3.Account;
         accounts.SystemProgram = SystemProgram.ProgramIdKey;
 
-        var initTx = TufiaProgram.InitPlayer(accounts, seed, AnchorProgramIdPubKey);
+        var initTx = TufiaProgram.InitPlayer(accounts, playerClass, AnchorProgramIdPubKey);
         tx.Add(initTx);
 
         if (true)
@@ -307,7 +378,12 @@ public async Task InitAccounts(bool useSession)
 
         await UpdateSessionValid();
         await SubscribeToPlayerDataUpdates();
-        await SubscribeToGameDataUpdates(true);
+
+        // New players start in the lobby and still have to join the first floor
+        if (success)
+        {
+          BuyNewFloor(() => { });
+        }
     }
 
     private async Task<bool> SendAndConfirmTransaction(WalletBase wallet, Transaction transaction, string label = "",
@@ -478,26 +554,15 @@ public void OnCellClicked(byte x, byte y)
             return;
           }
 
+           // The player is not on any instance of the next floor yet, the
+           // directory decides which one they join
            MoveToStair(true, async () =>
            {
-              await Web3.WsRpc.UnsubscribeAsync(gameDataSubscription);
               await SubscribeToPlayerDataUpdates();
-              await SubscribeToGameDataUpdates(true);
-              if (CurrentGameData == null)
-              {
-                BuyNewFloor(() =>
-                {
-
-                });
-              }
-              else
+              BuyNewFloor(() =>
               {
-                MoveToTile(true, () =>
-                {
-
-                }, x, y);
-              }
 
+              });
            }, x, y);
         }
     }
@@ -523,10 +588,12 @@ public async void MoveToTile(bool useSession, Action onSuccess, ulong x, ulong y
             RecentBlockHash = await Web3.BlockHash(maxSeconds: 15)
         };
 
+        FloorAddress floor = GetCurrentFloor();
         MoveToTileAccounts chopTreeAccounts = new MoveToTileAccounts
         {
             Player = PlayerDataPDA,
-            GameData = GetCurrentFloorSeed(out String seed),
+            Config = ConfigPDA,
+            GameData = GetFloorPDA(floor),
             SystemProgram = SystemProgram.ProgramIdKey,
         };
 
@@ -535,7 +602,7 @@ public async void MoveToTile(bool useSession, Action onSuccess, ulong x, ulong y
             transaction.FeePayer = sessionWallet.Account.PublicKey;
             chopTreeAccounts.Signer = sessionWallet.Account.PublicKey;
             chopTreeAccounts.SessionToken = sessionWallet.SessionTokenPDA;
-            var chopInstruction = TufiaProgram.MoveToTile(chopTreeAccounts, seed, transactionCounter, x, y, AnchorProgramIdPubKey);
+            var chopInstruction = TufiaProgram.MoveToTile(chopTreeAccounts, floor, transactionCounter, x, y, AnchorProgramIdPubKey);
             transaction.Add(chopInstruction);
             Debug.Log("Sign and send chop tree with session");
             await SendAndConfirmTransaction(sessionWallet, transaction, "Chop Tree with session.", isBlocking: false, onSucccess: onSuccess);
@@ -544,7 +611,7 @@ public async void MoveToTile(bool useSession, Action onSuccess, ulong x, ulong y
         {
             transaction.FeePayer = Web3.Account.PublicKey;
             chopTreeAccounts.Signer = Web3.Account.PublicKey;
-            var chopInstruction = TufiaProgram.MoveToTile(chopTreeAccounts, seed, transactionCounter, x, y, AnchorProgramIdPubKey);
+            var chopInstruction = TufiaProgram.MoveToTile(chopTreeAccounts, floor, transactionCounter, x, y, AnchorProgramIdPubKey);
             transaction.Add(chopInstruction);
             Debug.Log("Sign and send init without session");
             await SendAndConfirmTransaction(Web3.Wallet, transaction, "Chop Tree without session.", onSucccess: onSuccess);
@@ -577,24 +644,30 @@ public async void BuyNewFloor(Action onSuccess)
             RecentBlockHash = await Web3.BlockHash(maxSeconds: 15)
         };
 
+        FloorAddress floor = await RouteToInstance();
+        // The instance the player is on now, they are taken off it
+        PublicKey oldGameData = GetFloorPDA(GetCurrentFloor());
+        var oldGameDataInfo = await Web3.Rpc.GetAccountInfoAsync(oldGameData, Commitment.Confirmed);
         BuyNextFloorAccounts accounts = new BuyNextFloorAccounts
         {
             Player = PlayerDataPDA,
-            GameData = GetCurrentFloorSeed(out String seed),
+            Config = ConfigPDA,
+            Directory = GetDirectoryPDA(floor.FloorId),
+            GameData = GetFloorPDA(floor),
+            OldGameData = oldGameDataInfo.Result?.Value != null ? oldGameData : null,
             SystemProgram = SystemProgram.ProgramIdKey,
         };
 
         transaction.FeePayer = Web3.Account.PublicKey;
         accounts.Signer = Web3.Account.PublicKey;
-        var ix = TufiaProgram.BuyNextFloor(accounts, seed, transactionCounter, AnchorProgramIdPubKey);
+        var ix = TufiaProgram.BuyNextFloor(accounts, floor, transactionCounter, AnchorProgramIdPubKey);
         transaction.Add(ix);
         Debug.Log("Sign and send init without session");
         await SendAndConfirmTransaction(Web3.Wallet, transaction, "Chop Tree without session.", onSucccess: onSuccess);
 
-        if (CurrentGameData == null)
-        {
-            await SubscribeToGameDataUpdates(false);
-        }
+        // The instance can be a different one than before
+        await SubscribeToPlayerDataUpdates();
+        await SubscribeToGameDataUpdates(true);
     }
 
     public async void MoveToStair(bool useSession, Action onSuccess, ulong x, ulong y)
@@ -618,16 +691,19 @@ public async void MoveToStair(bool useSession, Action onSuccess, ulong x, ulong
             RecentBlockHash = await Web3.BlockHash(maxSeconds: 15)
         };
 
+        FloorAddress floor = GetCurrentFloor();
         MoveToNextFloorAccounts nextFloorAccounts = new MoveToNextFloorAccounts()
         {
             Player = PlayerDataPDA,
-            GameData = GetCurrentFloorSeed(out String seed),
+            Config = ConfigPDA,
+            GameData = GetFloorPDA(floor),
+            Directory = GetDirectoryPDA(floor.FloorId),
             SystemProgram = SystemProgram.ProgramIdKey,
         };
 
         try
         {
-          var nextData = await anchorClient.GetGameDataAsync(nextFloorAccounts.GameData, Commitment.Confirmed);
+          var nextData = await anchorClient.GetFloorDataAsync(nextFloorAccounts.GameData, Commitment.Confirmed);
           if (nextData.ParsedResult == null)
           {
             useSession = false;
@@ -645,7 +721,7 @@ public async void MoveToStair(bool useSession, Action onSuccess, ulong x, ulong
             transaction.FeePayer = sessionWallet.Account.PublicKey;
             nextFloorAccounts.Signer = sessionWallet.Account.PublicKey;
             nextFloorAccounts.SessionToken = sessionWallet.SessionTokenPDA;
-            var ix = TufiaProgram.MoveToNextFloor(nextFloorAccounts, seed, transactionCounter, AnchorProgramIdPubKey);
+            var ix = TufiaProgram.MoveToNextFloor(nextFloorAccounts, floor, transactionCounter, AnchorProgramIdPubKey);
             transaction.Add(ix);
             Debug.Log("Sign and send chop tree with session");
             await SendAndConfirmTransaction(sessionWallet, transaction, "Chop Tree with session.", isBlocking: false, onSucccess: onSuccess);
@@ -654,7 +730,7 @@ public async void MoveToStair(bool useSession, Action onSuccess, ulong x, ulong
         {
             transaction.FeePayer = Web3.Account.PublicKey;
             nextFloorAccounts.Signer = Web3.Account.PublicKey;
-            var ix = TufiaProgram.MoveToNextFloor(nextFloorAccounts, seed, transactionCounter, AnchorProgramIdPubKey);
+            var ix = TufiaProgram.MoveToNextFloor(nextFloorAccounts, floor, transactionCounter, AnchorProgramIdPubKey);
             transaction.Add(ix);
             Debug.Log("Sign and send init without session");
             await SendAndConfirmTransaction(Web3.Wallet, transaction, "Chop Tree without session.", onSucccess: onSuccess);
@@ -687,16 +763,18 @@ public async void ResetFloor()
             RecentBlockHash = await Web3.BlockHash(maxSeconds: 15)
         };
 
+        FloorAddress floor = GetCurrentFloor();
         ResetFloorAccounts chopTreeAccounts = new ResetFloorAccounts
         {
             Player = PlayerDataPDA,
-            GameData = GetCurrentFloorSeed(out String seed),
+            Config = ConfigPDA,
+            GameData = GetFloorPDA(floor),
             SystemProgram = SystemProgram.ProgramIdKey,
         };
 
         transaction.FeePayer = Web3.Account.PublicKey;
         chopTreeAccounts.Signer = Web3.Account.PublicKey;
-        var chopInstruction = TufiaProgram.ResetFloor(chopTreeAccounts, seed, transactionCounter, AnchorProgramIdPubKey);
+        var chopInstruction = TufiaProgram.ResetFloor(chopTreeAccounts, floor, transactionCounter, AnchorProgramIdPubKey);
         transaction.Add(chopInstruction);
         Debug.Log("Sign and send init without session");
         await SendAndConfirmTransaction(Web3.Wallet, transaction, "Reset Floor without session.", onSucccess: () =>
@@ -22,26 +22,30 @@ namespace Tufia
 {
     namespace Accounts
     {
-        public partial class GameData
+        public partial class FloorData
         {
-            public static ulong ACCOUNT_DISCRIMINATOR => 13758009850765924589UL;
-            public static ReadOnlySpan<byte> ACCOUNT_DISCRIMINATOR_BYTES => new byte[]{237, 88, 58, 243, 16, 69, 238, 190};
-            public static string ACCOUNT_DISCRIMINATOR_B58 => "ghYLwVtPH73";
+            public static ulong ACCOUNT_DISCRIMINATOR => 14038281021916740366UL;
+            public static ReadOnlySpan<byte> ACCOUNT_DISCRIMINATOR_BYTES => new byte[]{14, 123, 116, 32, 54, 254, 209, 194};
+            public static string ACCOUNT_DISCRIMINATOR_B58 => "3RVm8LS3mvH";
             public ulong IdCounter { get; set; }
 
-            public ulong ActionIndex { get; set; }
-
-            public TileData[][] Data { get; set; }
+            public byte ActionIndex { get; set; }
 
             public ulong TotalWoodCollected { get; set; }
 
-            public GameAction[] GameActions { get; set; }
-
             public uint FloorId { get; set; }
 
             public PublicKey Owner { get; set; }
 
-            public static GameData Deserialize(ReadOnlySpan<byte> _data)
+            public byte Mode { get; set; }
+
+            public PlayerSlot[] Players { get; set; }
+
+            public PackedTile[][] Tiles { get; set; }
+
+            public PackedAction[] Actions { get; set; }
+
+            public static FloorData Deserialize(ReadOnlySpan<byte> _data)
             {
                 int offset = 0;
                 ulong accountHashValue = _data.GetU64(offset);
@@ -51,35 +55,138 @@ public static GameData Deserialize(ReadOnlySpan<byte> _data)
                     return null;
                 }
 
-                GameData result = new GameData();
+                FloorData result = new FloorData();
                 result.IdCounter = _data.GetU64(offset);
                 offset += 8;
-                result.ActionIndex = _data.GetU64(offset);
+                result.ActionIndex = _data.GetU8(offset);
+                offset += 1;
+                result.TotalWoodCollected = _data.GetU64(offset);
                 offset += 8;
-                result.Data = new TileData[10][];
-                for (uint resultDataIdx = 0; resultDataIdx < 10; resultDataIdx++)
+                result.FloorId = _data.GetU32(offset);
+                offset += 4;
+                result.Owner = _data.GetPubKey(offset);
+                offset += 32;
+                result.Mode = _data.GetU8(offset);
+                offset += 1;
+                result.Players = new PlayerSlot[29];
+                for (uint resultPlayersIdx = 0; resultPlayersIdx < 29; resultPlayersIdx++)
+                {
+                    offset += PlayerSlot.Deserialize(_data, offset, out var resultPlayersItem);
+                    result.Players[resultPlayersIdx] = resultPlayersItem;
+                }
+
+                result.Tiles = new PackedTile[10][];
+                for (uint resultTilesIdx = 0; resultTilesIdx < 10; resultTilesIdx++)
                 {
-                    result.Data[resultDataIdx] = new TileData[10];
-                    for (uint resultDataresultDataIdxIdx = 0; resultDataresultDataIdxIdx < 10; resultDataresultDataIdxIdx++)
+                    result.Tiles[resultTilesIdx] = new PackedTile[10];
+                    for (uint resultTilesItemIdx = 0; resultTilesItemIdx < 10; resultTilesItemIdx++)
                     {
-                        offset += TileData.Deserialize(_data, offset, out var resultDataresultDataIdxresultDataresultDataIdxIdx);
-                        result.Data[resultDataIdx][resultDataresultDataIdxIdx] = resultDataresultDataIdxresultDataresultDataIdxIdx;
+                        offset += PackedTile.Deserialize(_data, offset, out var resultTilesItemItem);
+                        result.Tiles[resultTilesIdx][resultTilesItemIdx] = resultTilesItemItem;
                     }
                 }
 
-                result.TotalWoodCollected = _data.GetU64(offset);
+                result.Actions = new PackedAction[20];
+                for (uint resultActionsIdx = 0; resultActionsIdx < 20; resultActionsIdx++)
+                {
+                    offset += PackedAction.Deserialize(_data, offset, out var resultActionsItem);
+                    result.Actions[resultActionsIdx] = resultActionsItem;
+                }
+
+                return result;
+            }
+        }
+
+        public partial class FloorDirectory
+        {
+            public static ulong ACCOUNT_DISCRIMINATOR => 4175602479539194574UL;
+            public static ReadOnlySpan<byte> ACCOUNT_DISCRIMINATOR_BYTES => new byte[]{206, 138, 44, 244, 84, 184, 242, 57};
+            public static string ACCOUNT_DISCRIMINATOR_B58 => "bYhGZnMKz3J";
+            public ushort Season { get; set; }
+
+            public uint FloorId { get; set; }
+
+            public byte[] Instances { get; set; }
+
+            public static FloorDirectory Deserialize(ReadOnlySpan<byte> _data)
+            {
+                int offset = 0;
+                ulong accountHashValue = _data.GetU64(offset);
                 offset += 8;
-                result.GameActions = new GameAction[20];
-                for (uint resultGameActionsIdx = 0; resultGameActionsIdx < 20; resultGameActionsIdx++)
+                if (accountHashValue != ACCOUNT_DISCRIMINATOR)
                 {
-                    offset += GameAction.Deserialize(_data, offset, out var resultGameActionsresultGameActionsIdx);
-                    result.GameActions[resultGameActionsIdx] = resultGameActionsresultGameActionsIdx;
+                    return null;
                 }
 
+                FloorDirectory result = new FloorDirectory();
+                result.Season = _data.GetU16(offset);
+                offset += 2;
                 result.FloorId = _data.GetU32(offset);
                 offset += 4;
-                result.Owner = _data.GetPubKey(offset);
+                int resultInstancesLength = (int)_data.GetU32(offset);
+                offset += 4;
+                result.Instances = new byte[resultInstancesLength];
+                for (uint resultInstancesIdx = 0; resultInstancesIdx < resultInstancesLength; resultInstancesIdx++)
+                {
+                    result.Instances[resultInstancesIdx] = _data.GetU8(offset);
+                    offset += 1;
+                }
+
+                return result;
+            }
+        }
+
+        public partial class GameConfig
+        {
+            public static ulong ACCOUNT_DISCRIMINATOR => 9610758201818649133UL;
+            public static ReadOnlySpan<byte> ACCOUNT_DISCRIMINATOR_BYTES => new byte[]{45, 146, 146, 33, 170, 69, 96, 133};
+            public static string ACCOUNT_DISCRIMINATOR_B58 => "8d7RZ1Z6Zhn";
+            public PublicKey Admin { get; set; }
+
+            public ushort BossFloorInterval { get; set; }
+
+            public ushort Season { get; set; }
+
+            public string ItemUri { get; set; }
+
+            public Recipe[] Recipes { get; set; }
+
+            public uint ArmorRegenPerMove { get; set; }
+
+            public long HealthRegenSeconds { get; set; }
+
+            public static GameConfig Deserialize(ReadOnlySpan<byte> _data)
+            {
+                int offset = 0;
+                ulong accountHashValue = _data.GetU64(offset);
+                offset += 8;
+                if (accountHashValue != ACCOUNT_DISCRIMINATOR)
+                {
+                    return null;
+                }
+
+                GameConfig result = new GameConfig();
+                result.Admin = _data.GetPubKey(offset);
                 offset += 32;
+                result.BossFloorInterval = _data.GetU16(offset);
+                offset += 2;
+                result.Season = _data.GetU16(offset);
+                offset += 2;
+                offset += _data.GetBorshString(offset, out var resultItemUri);
+                result.ItemUri = resultItemUri;
+                int resultRecipesLength = (int)_data.GetU32(offset);
+                offset += 4;
+                result.Recipes = new Recipe[resultRecipesLength];
+                for (uint resultRecipesIdx = 0; resultRecipesIdx < resultRecipesLength; resultRecipesIdx++)
+                {
+                    offset += Recipe.Deserialize(_data, offset, out var resultRecipesItem);
+                    result.Recipes[resultRecipesIdx] = resultRecipesItem;
+                }
+
+                result.ArmorRegenPerMove = _data.GetU32(offset);
+                offset += 4;
+                result.HealthRegenSeconds = _data.GetS64(offset);
+                offset += 8;
                 return result;
             }
         }
@@ -119,6 +226,62 @@ public partial class PlayerData
 
             public TileData2 TileData { get; set; }
 
+            public uint Keys { get; set; }
+
+            public uint ShrineDamageBonus { get; set; }
+
+            public uint ShrineBuffMoves { get; set; }
+
+            public uint HealthPotions { get; set; }
+
+            public Encounter Encounter { get; set; }
+
+            public byte CritChance { get; set; }
+
+            public byte CritMultiplier { get; set; }
+
+            public byte Evasion { get; set; }
+
+            public byte DamageRange { get; set; }
+
+            public byte Class { get; set; }
+
+            public uint MaxArmor { get; set; }
+
+            public ushort StatPoints { get; set; }
+
+            public ushort SkillPoints { get; set; }
+
+            public uint Skills { get; set; }
+
+            public byte Avatar { get; set; }
+
+            public string Title { get; set; }
+
+            public LifetimeStats Stats { get; set; }
+
+            public ushort Season { get; set; }
+
+            public LifetimeStats SeasonStats { get; set; }
+
+            public SeasonBadge[] Badges { get; set; }
+
+            public PublicKey Party { get; set; }
+
+            public PublicKey Guild { get; set; }
+
+            public InventoryItem[] Inventory { get; set; }
+
+            public GearItem[] Gear { get; set; }
+
+            public Buff[] Buffs { get; set; }
+
+            public long LastHeal { get; set; }
+
+            public ushort CurrentInstance { get; set; }
+
+            public ulong RngNonce { get; set; }
+
             public static PlayerData Deserialize(ReadOnlySpan<byte> _data)
             {
                 int offset = 0;
@@ -129,117 +292,829 @@ public static PlayerData Deserialize(ReadOnlySpan<byte> _data)
                     return null;
                 }
 
-                PlayerData result = new PlayerData();
-                result.Authority = _data.GetPubKey(offset);
-                offset += 32;
-                offset += _data.GetBorshString(offset, out var resultName);
-                result.Name = resultName;
-                result.Level = _data.GetU32(offset);
+                PlayerData result = new PlayerData();
+                result.Authority = _data.GetPubKey(offset);
+                offset += 32;
+                offset += _data.GetBorshString(offset, out var resultName);
+                result.Name = resultName;
+                result.Level = _data.GetU32(offset);
+                offset += 4;
+                result.Xp = _data.GetU32(offset);
+                offset += 4;
+                result.Health = _data.GetU32(offset);
+                offset += 4;
+                result.MaxHealth = _data.GetU32(offset);
+                offset += 4;
+                result.Damage = _data.GetU32(offset);
+                offset += 4;
+                result.Defence = _data.GetU32(offset);
+                offset += 4;
+                result.Swords = _data.GetU32(offset);
+                offset += 4;
+                result.Shields = _data.GetU32(offset);
+                offset += 4;
+                result.Energy = _data.GetU32(offset);
+                offset += 4;
+                result.LastLogin = _data.GetS64(offset);
+                offset += 8;
+                result.LastId = _data.GetU16(offset);
+                offset += 2;
+                result.CurrentFloor = _data.GetU16(offset);
+                offset += 2;
+                offset += TileData2.Deserialize(_data, offset, out var resultTileData);
+                result.TileData = resultTileData;
+                result.Keys = _data.GetU32(offset);
+                offset += 4;
+                result.ShrineDamageBonus = _data.GetU32(offset);
+                offset += 4;
+                result.ShrineBuffMoves = _data.GetU32(offset);
+                offset += 4;
+                result.HealthPotions = _data.GetU32(offset);
+                offset += 4;
+                offset += Encounter.Deserialize(_data, offset, out var resultEncounter);
+                result.Encounter = resultEncounter;
+                result.CritChance = _data.GetU8(offset);
+                offset += 1;
+                result.CritMultiplier = _data.GetU8(offset);
+                offset += 1;
+                result.Evasion = _data.GetU8(offset);
+                offset += 1;
+                result.DamageRange = _data.GetU8(offset);
+                offset += 1;
+                result.Class = _data.GetU8(offset);
+                offset += 1;
+                result.MaxArmor = _data.GetU32(offset);
+                offset += 4;
+                result.StatPoints = _data.GetU16(offset);
+                offset += 2;
+                result.SkillPoints = _data.GetU16(offset);
+                offset += 2;
+                result.Skills = _data.GetU32(offset);
+                offset += 4;
+                result.Avatar = _data.GetU8(offset);
+                offset += 1;
+                offset += _data.GetBorshString(offset, out var resultTitle);
+                result.Title = resultTitle;
+                offset += LifetimeStats.Deserialize(_data, offset, out var resultStats);
+                result.Stats = resultStats;
+                result.Season = _data.GetU16(offset);
+                offset += 2;
+                offset += LifetimeStats.Deserialize(_data, offset, out var resultSeasonStats);
+                result.SeasonStats = resultSeasonStats;
+                int resultBadgesLength = (int)_data.GetU32(offset);
+                offset += 4;
+                result.Badges = new SeasonBadge[resultBadgesLength];
+                for (uint resultBadgesIdx = 0; resultBadgesIdx < resultBadgesLength; resultBadgesIdx++)
+                {
+                    offset += SeasonBadge.Deserialize(_data, offset, out var resultBadgesItem);
+                    result.Badges[resultBadgesIdx] = resultBadgesItem;
+                }
+
+                result.Party = _data.GetPubKey(offset);
+                offset += 32;
+                result.Guild = _data.GetPubKey(offset);
+                offset += 32;
+                int resultInventoryLength = (int)_data.GetU32(offset);
+                offset += 4;
+                result.Inventory = new InventoryItem[resultInventoryLength];
+                for (uint resultInventoryIdx = 0; resultInventoryIdx < resultInventoryLength; resultInventoryIdx++)
+                {
+                    offset += InventoryItem.Deserialize(_data, offset, out var resultInventoryItem);
+                    result.Inventory[resultInventoryIdx] = resultInventoryItem;
+                }
+
+                int resultGearLength = (int)_data.GetU32(offset);
+                offset += 4;
+                result.Gear = new GearItem[resultGearLength];
+                for (uint resultGearIdx = 0; resultGearIdx < resultGearLength; resultGearIdx++)
+                {
+                    offset += GearItem.Deserialize(_data, offset, out var resultGearItem);
+                    result.Gear[resultGearIdx] = resultGearItem;
+                }
+
+                int resultBuffsLength = (int)_data.GetU32(offset);
+                offset += 4;
+                result.Buffs = new Buff[resultBuffsLength];
+                for (uint resultBuffsIdx = 0; resultBuffsIdx < resultBuffsLength; resultBuffsIdx++)
+                {
+                    offset += Buff.Deserialize(_data, offset, out var resultBuffsItem);
+                    result.Buffs[resultBuffsIdx] = resultBuffsItem;
+                }
+
+                result.LastHeal = _data.GetS64(offset);
+                offset += 8;
+                result.CurrentInstance = _data.GetU16(offset);
+                offset += 2;
+                result.RngNonce = _data.GetU64(offset);
+                offset += 8;
+                return result;
+            }
+        }
+
+        // Not an account, the board the game works on. Unpacked from the
+        // FloorData account the same way as FloorData::board in floor_data.rs.
+        public partial class GameData
+        {
+            public ulong IdCounter { get; set; }
+
+            public ulong ActionIndex { get; set; }
+
+            public TileData[][] Data { get; set; }
+
+            public ulong TotalWoodCollected { get; set; }
+
+            public GameAction[] GameActions { get; set; }
+
+            public uint FloorId { get; set; }
+
+            public PublicKey Owner { get; set; }
+
+            public byte Mode { get; set; }
+        }
+
+        public partial class FloorData
+        {
+            public const int BOARD_SIZE = 10;
+            public const int GAME_ACTIONS = 20;
+            private const byte NO_ENTITY = 0;
+            private const byte STATE_PLAYER = 1;
+
+            public GameData Board()
+            {
+                GameData result = new GameData
+                {
+                    IdCounter = IdCounter,
+                    ActionIndex = ActionIndex,
+                    TotalWoodCollected = TotalWoodCollected,
+                    FloorId = FloorId,
+                    Owner = Owner,
+                    Mode = Mode,
+                    Data = new TileData[BOARD_SIZE][],
+                    GameActions = new GameAction[GAME_ACTIONS],
+                };
+                for (int i = 0; i < BOARD_SIZE; i++)
+                {
+                    result.Data[i] = new TileData[BOARD_SIZE];
+                    for (int j = 0; j < BOARD_SIZE; j++)
+                    {
+                        result.Data[i][j] = UnpackTile(Tiles[i][j]);
+                    }
+                }
+
+                // Positions are packed as x << 4 | y
+                for (int i = 0; i < GAME_ACTIONS; i++)
+                {
+                    PackedAction action = Actions[i];
+                    result.GameActions[i] = new GameAction
+                    {
+                        ActionId = action.ActionId,
+                        ActionType = action.ActionType,
+                        FromX = (byte)(action.From >> 4),
+                        FromY = (byte)(action.From & 0x0f),
+                        ToX = (byte)(action.To >> 4),
+                        ToY = (byte)(action.To & 0x0f),
+                        Tile = UnpackTile(action.Tile),
+                        Amount = action.Amount,
+                    };
+                }
+
+                return result;
+            }
+
+            // Tiles point into the player table with the slot + 1. The name and
+            // the avatar are only shown on player tiles.
+            private TileData UnpackTile(PackedTile tile)
+            {
+                PlayerSlot slot = tile.Entity == NO_ENTITY ? null : Players[tile.Entity - 1];
+                TileData result = new TileData
+                {
+                    TileType = tile.TileType,
+                    TileLevel = tile.Level,
+                    TileOwner = slot != null ? slot.Player : new PublicKey(new byte[32]),
+                    TileXp = tile.Xp,
+                    TileDamage = tile.Damage,
+                    TileDefence = tile.Defence,
+                    TileArmor = tile.Armor,
+                    TileMaxArmor = tile.MaxArmor,
+                    TileHealth = tile.Health,
+                    TileMaxHealth = tile.MaxHealth,
+                    TileCritChance = tile.CritChance,
+                    TileCritMultiplier = tile.CritMultiplier,
+                    TileEvasion = tile.Evasion,
+                    TileDamageRange = tile.DamageRange,
+                    TileClass = tile.Class,
+                    TileName = new byte[12],
+                    TileStatus = tile.Status,
+                    TileStatusTurns = tile.StatusTurns,
+                };
+                if (slot != null && tile.TileType == STATE_PLAYER)
+                {
+                    result.TileAvatar = slot.Avatar;
+                    result.TileName = slot.Name;
+                }
+
+                return result;
+            }
+        }
+    }
+
+    namespace Errors
+    {
+        public enum TufiaErrorKind : uint
+        {
+            NotEnoughEnergy = 6000U,
+            WrongAuthority = 6001U,
+            PlayerNotOnBoard = 6002U,
+            OutOfBounds = 6003U,
+            PlayerAlreadyExists = 6004U,
+            BoardIsFull = 6005U,
+            PlayerIsAlreadyOnThisTile = 6006U
+        }
+    }
+
+    namespace Types
+    {
+        public partial class Encounter
+        {
+            public bool Active { get; set; }
+
+            public byte EnemyX { get; set; }
+
+            public byte EnemyY { get; set; }
+
+            public ushort Round { get; set; }
+
+            public bool AbilityUsed { get; set; }
+
+            public PublicKey Floor { get; set; }
+
+            public int Serialize(byte[] _data, int initialOffset)
+            {
+                int offset = initialOffset;
+                _data.WriteBool(Active, offset);
+                offset += 1;
+                _data.WriteU8(EnemyX, offset);
+                offset += 1;
+                _data.WriteU8(EnemyY, offset);
+                offset += 1;
+                _data.WriteU16(Round, offset);
+                offset += 2;
+                _data.WriteBool(AbilityUsed, offset);
+                offset += 1;
+                _data.WritePubKey(Floor, offset);
+                offset += 32;
+                return offset - initialOffset;
+            }
+
+            public static int Deserialize(ReadOnlySpan<byte> _data, int initialOffset, out Encounter result)
+            {
+                int offset = initialOffset;
+                result = new Encounter();
+                result.Active = _data.GetBool(offset);
+                offset += 1;
+                result.EnemyX = _data.GetU8(offset);
+                offset += 1;
+                result.EnemyY = _data.GetU8(offset);
+                offset += 1;
+                result.Round = _data.GetU16(offset);
+                offset += 2;
+                result.AbilityUsed = _data.GetBool(offset);
+                offset += 1;
+                result.Floor = _data.GetPubKey(offset);
+                offset += 32;
+                return offset - initialOffset;
+            }
+        }
+
+        public partial class FloorAddress
+        {
+            public uint FloorId { get; set; }
+
+            public ushort Instance { get; set; }
+
+            public int Serialize(byte[] _data, int initialOffset)
+            {
+                int offset = initialOffset;
+                _data.WriteU32(FloorId, offset);
+                offset += 4;
+                _data.WriteU16(Instance, offset);
+                offset += 2;
+                return offset - initialOffset;
+            }
+
+            public static int Deserialize(ReadOnlySpan<byte> _data, int initialOffset, out FloorAddress result)
+            {
+                int offset = initialOffset;
+                result = new FloorAddress();
+                result.FloorId = _data.GetU32(offset);
+                offset += 4;
+                result.Instance = _data.GetU16(offset);
+                offset += 2;
+                return offset - initialOffset;
+            }
+        }
+
+        public partial class GameAction
+        {
+            public ulong ActionId { get; set; }
+
+            public byte ActionType { get; set; }
+
+            public byte FromX { get; set; }
+
+            public byte FromY { get; set; }
+
+            public byte ToX { get; set; }
+
+            public byte ToY { get; set; }
+
+            public TileData Tile { get; set; }
+
+            public ulong Amount { get; set; }
+
+            public int Serialize(byte[] _data, int initialOffset)
+            {
+                int offset = initialOffset;
+                _data.WriteU64(ActionId, offset);
+                offset += 8;
+                _data.WriteU8(ActionType, offset);
+                offset += 1;
+                _data.WriteU8(FromX, offset);
+                offset += 1;
+                _data.WriteU8(FromY, offset);
+                offset += 1;
+                _data.WriteU8(ToX, offset);
+                offset += 1;
+                _data.WriteU8(ToY, offset);
+                offset += 1;
+                offset += Tile.Serialize(_data, offset);
+                _data.WriteU64(Amount, offset);
+                offset += 8;
+                return offset - initialOffset;
+            }
+
+            public static int Deserialize(ReadOnlySpan<byte> _data, int initialOffset, out GameAction result)
+            {
+                int offset = initialOffset;
+                result = new GameAction();
+                result.ActionId = _data.GetU64(offset);
+                offset += 8;
+                result.ActionType = _data.GetU8(offset);
+                offset += 1;
+                result.FromX = _data.GetU8(offset);
+                offset += 1;
+                result.FromY = _data.GetU8(offset);
+                offset += 1;
+                result.ToX = _data.GetU8(offset);
+                offset += 1;
+                result.ToY = _data.GetU8(offset);
+                offset += 1;
+                offset += TileData.Deserialize(_data, offset, out var resultTile);
+                result.Tile = resultTile;
+                result.Amount = _data.GetU64(offset);
+                offset += 8;
+                return offset - initialOffset;
+            }
+        }
+
+        public partial class GearItem
+        {
+            public byte Rarity { get; set; }
+
+            public uint Damage { get; set; }
+
+            public uint Defence { get; set; }
+
+            public uint Armor { get; set; }
+
+            public ushort Floor { get; set; }
+
+            public bool Equipped { get; set; }
+
+            public byte Tier { get; set; }
+
+            public byte Status { get; set; }
+
+            public int Serialize(byte[] _data, int initialOffset)
+            {
+                int offset = initialOffset;
+                _data.WriteU8(Rarity, offset);
+                offset += 1;
+                _data.WriteU32(Damage, offset);
+                offset += 4;
+                _data.WriteU32(Defence, offset);
+                offset += 4;
+                _data.WriteU32(Armor, offset);
+                offset += 4;
+                _data.WriteU16(Floor, offset);
+                offset += 2;
+                _data.WriteBool(Equipped, offset);
+                offset += 1;
+                _data.WriteU8(Tier, offset);
+                offset += 1;
+                _data.WriteU8(Status, offset);
+                offset += 1;
+                return offset - initialOffset;
+            }
+
+            public static int Deserialize(ReadOnlySpan<byte> _data, int initialOffset, out GearItem result)
+            {
+                int offset = initialOffset;
+                result = new GearItem();
+                result.Rarity = _data.GetU8(offset);
+                offset += 1;
+                result.Damage = _data.GetU32(offset);
+                offset += 4;
+                result.Defence = _data.GetU32(offset);
+                offset += 4;
+                result.Armor = _data.GetU32(offset);
+                offset += 4;
+                result.Floor = _data.GetU16(offset);
+                offset += 2;
+                result.Equipped = _data.GetBool(offset);
+                offset += 1;
+                result.Tier = _data.GetU8(offset);
+                offset += 1;
+                result.Status = _data.GetU8(offset);
+                offset += 1;
+                return offset - initialOffset;
+            }
+        }
+
+        public partial class Buff
+        {
+            public byte Kind { get; set; }
+
+            public uint Amount { get; set; }
+
+            public long ExpiresAt { get; set; }
+
+            public ushort MovesLeft { get; set; }
+
+            public int Serialize(byte[] _data, int initialOffset)
+            {
+                int offset = initialOffset;
+                _data.WriteU8(Kind, offset);
+                offset += 1;
+                _data.WriteU32(Amount, offset);
+                offset += 4;
+                _data.WriteS64(ExpiresAt, offset);
+                offset += 8;
+                _data.WriteU16(MovesLeft, offset);
+                offset += 2;
+                return offset - initialOffset;
+            }
+
+            public static int Deserialize(ReadOnlySpan<byte> _data, int initialOffset, out Buff result)
+            {
+                int offset = initialOffset;
+                result = new Buff();
+                result.Kind = _data.GetU8(offset);
+                offset += 1;
+                result.Amount = _data.GetU32(offset);
+                offset += 4;
+                result.ExpiresAt = _data.GetS64(offset);
+                offset += 8;
+                result.MovesLeft = _data.GetU16(offset);
+                offset += 2;
+                return offset - initialOffset;
+            }
+        }
+
+        public partial class InventoryItem
+        {
+            public ushort ItemId { get; set; }
+
+            public uint Amount { get; set; }
+
+            public int Serialize(byte[] _data, int initialOffset)
+            {
+                int offset = initialOffset;
+                _data.WriteU16(ItemId, offset);
+                offset += 2;
+                _data.WriteU32(Amount, offset);
+                offset += 4;
+                return offset - initialOffset;
+            }
+
+            public static int Deserialize(ReadOnlySpan<byte> _data, int initialOffset, out InventoryItem result)
+            {
+                int offset = initialOffset;
+                result = new InventoryItem();
+                result.ItemId = _data.GetU16(offset);
+                offset += 2;
+                result.Amount = _data.GetU32(offset);
+                offset += 4;
+                return offset - initialOffset;
+            }
+        }
+
+        public partial class LifetimeStats
+        {
+            public ushort MaxFloor { get; set; }
+
+            public uint Kills { get; set; }
+
+            public uint Deaths { get; set; }
+
+            public uint ChestsOpened { get; set; }
+
+            public int Serialize(byte[] _data, int initialOffset)
+            {
+                int offset = initialOffset;
+                _data.WriteU16(MaxFloor, offset);
+                offset += 2;
+                _data.WriteU32(Kills, offset);
+                offset += 4;
+                _data.WriteU32(Deaths, offset);
+                offset += 4;
+                _data.WriteU32(ChestsOpened, offset);
+                offset += 4;
+                return offset - initialOffset;
+            }
+
+            public static int Deserialize(ReadOnlySpan<byte> _data, int initialOffset, out LifetimeStats result)
+            {
+                int offset = initialOffset;
+                result = new LifetimeStats();
+                result.MaxFloor = _data.GetU16(offset);
+                offset += 2;
+                result.Kills = _data.GetU32(offset);
+                offset += 4;
+                result.Deaths = _data.GetU32(offset);
+                offset += 4;
+                result.ChestsOpened = _data.GetU32(offset);
+                offset += 4;
+                return offset - initialOffset;
+            }
+        }
+
+        public partial class PackedAction
+        {
+            public ulong ActionId { get; set; }
+
+            public byte ActionType { get; set; }
+
+            public byte From { get; set; }
+
+            public byte To { get; set; }
+
+            public PackedTile Tile { get; set; }
+
+            public ulong Amount { get; set; }
+
+            public int Serialize(byte[] _data, int initialOffset)
+            {
+                int offset = initialOffset;
+                _data.WriteU64(ActionId, offset);
+                offset += 8;
+                _data.WriteU8(ActionType, offset);
+                offset += 1;
+                _data.WriteU8(From, offset);
+                offset += 1;
+                _data.WriteU8(To, offset);
+                offset += 1;
+                offset += Tile.Serialize(_data, offset);
+                _data.WriteU64(Amount, offset);
+                offset += 8;
+                return offset - initialOffset;
+            }
+
+            public static int Deserialize(ReadOnlySpan<byte> _data, int initialOffset, out PackedAction result)
+            {
+                int offset = initialOffset;
+                result = new PackedAction();
+                result.ActionId = _data.GetU64(offset);
+                offset += 8;
+                result.ActionType = _data.GetU8(offset);
+                offset += 1;
+                result.From = _data.GetU8(offset);
+                offset += 1;
+                result.To = _data.GetU8(offset);
+                offset += 1;
+                offset += PackedTile.Deserialize(_data, offset, out var resultTile);
+                result.Tile = resultTile;
+                result.Amount = _data.GetU64(offset);
+                offset += 8;
+                return offset - initialOffset;
+            }
+        }
+
+        public partial class PackedTile
+        {
+            public byte TileType { get; set; }
+
+            public byte Entity { get; set; }
+
+            public ushort Level { get; set; }
+
+            public uint Xp { get; set; }
+
+            public uint Damage { get; set; }
+
+            public uint Defence { get; set; }
+
+            public uint Armor { get; set; }
+
+            public uint MaxArmor { get; set; }
+
+            public uint Health { get; set; }
+
+            public uint MaxHealth { get; set; }
+
+            public byte CritChance { get; set; }
+
+            public byte CritMultiplier { get; set; }
+
+            public byte Evasion { get; set; }
+
+            public byte DamageRange { get; set; }
+
+            public byte Class { get; set; }
+
+            public byte Status { get; set; }
+
+            public byte StatusTurns { get; set; }
+
+            public int Serialize(byte[] _data, int initialOffset)
+            {
+                int offset = initialOffset;
+                _data.WriteU8(TileType, offset);
+                offset += 1;
+                _data.WriteU8(Entity, offset);
+                offset += 1;
+                _data.WriteU16(Level, offset);
+                offset += 2;
+                _data.WriteU32(Xp, offset);
                 offset += 4;
-                result.Xp = _data.GetU32(offset);
+                _data.WriteU32(Damage, offset);
                 offset += 4;
-                result.Health = _data.GetU32(offset);
+                _data.WriteU32(Defence, offset);
                 offset += 4;
-                result.MaxHealth = _data.GetU32(offset);
+                _data.WriteU32(Armor, offset);
+                offset += 4;
+                _data.WriteU32(MaxArmor, offset);
+                offset += 4;
+                _data.WriteU32(Health, offset);
+                offset += 4;
+                _data.WriteU32(MaxHealth, offset);
+                offset += 4;
+                _data.WriteU8(CritChance, offset);
+                offset += 1;
+                _data.WriteU8(CritMultiplier, offset);
+                offset += 1;
+                _data.WriteU8(Evasion, offset);
+                offset += 1;
+                _data.WriteU8(DamageRange, offset);
+                offset += 1;
+                _data.WriteU8(Class, offset);
+                offset += 1;
+                _data.WriteU8(Status, offset);
+                offset += 1;
+                _data.WriteU8(StatusTurns, offset);
+                offset += 1;
+                return offset - initialOffset;
+            }
+
+            public static int Deserialize(ReadOnlySpan<byte> _data, int initialOffset, out PackedTile result)
+            {
+                int offset = initialOffset;
+                result = new PackedTile();
+                result.TileType = _data.GetU8(offset);
+                offset += 1;
+                result.Entity = _data.GetU8(offset);
+                offset += 1;
+                result.Level = _data.GetU16(offset);
+                offset += 2;
+                result.Xp = _data.GetU32(offset);
                 offset += 4;
                 result.Damage = _data.GetU32(offset);
                 offset += 4;
                 result.Defence = _data.GetU32(offset);
                 offset += 4;
-                result.Swords = _data.GetU32(offset);
+                result.Armor = _data.GetU32(offset);
                 offset += 4;
-                result.Shields = _data.GetU32(offset);
+                result.MaxArmor = _data.GetU32(offset);
                 offset += 4;
-                result.Energy = _data.GetU32(offset);
+                result.Health = _data.GetU32(offset);
                 offset += 4;
-                result.LastLogin = _data.GetS64(offset);
-                offset += 8;
-                result.LastId = _data.GetU16(offset);
-                offset += 2;
-                result.CurrentFloor = _data.GetU16(offset);
-                offset += 2;
-                offset += TileData2.Deserialize(_data, offset, out var resultTileData);
-                result.TileData = resultTileData;
-                return result;
+                result.MaxHealth = _data.GetU32(offset);
+                offset += 4;
+                result.CritChance = _data.GetU8(offset);
+                offset += 1;
+                result.CritMultiplier = _data.GetU8(offset);
+                offset += 1;
+                result.Evasion = _data.GetU8(offset);
+                offset += 1;
+                result.DamageRange = _data.GetU8(offset);
+                offset += 1;
+                result.Class = _data.GetU8(offset);
+                offset += 1;
+                result.Status = _data.GetU8(offset);
+                offset += 1;
+                result.StatusTurns = _data.GetU8(offset);
+                offset += 1;
+                return offset - initialOffset;
             }
         }
-    }
 
-    namespace Errors
-    {
-        public enum TufiaErrorKind : uint
+        public partial class PlayerSlot
         {
-            NotEnoughEnergy = 6000U,
-            WrongAuthority = 6001U,
-            PlayerNotOnBoard = 6002U,
-            OutOfBounds = 6003U,
-            PlayerAlreadyExists = 6004U,
-            BoardIsFull = 6005U,
-            PlayerIsAlreadyOnThisTile = 6006U
+            public PublicKey Player { get; set; }
+
+            public byte Avatar { get; set; }
+
+            public byte[] Name { get; set; }
+
+            public int Serialize(byte[] _data, int initialOffset)
+            {
+                int offset = initialOffset;
+                _data.WritePubKey(Player, offset);
+                offset += 32;
+                _data.WriteU8(Avatar, offset);
+                offset += 1;
+                _data.WriteSpan(Name, offset);
+                offset += 12;
+                return offset - initialOffset;
+            }
+
+            public static int Deserialize(ReadOnlySpan<byte> _data, int initialOffset, out PlayerSlot result)
+            {
+                int offset = initialOffset;
+                result = new PlayerSlot();
+                result.Player = _data.GetPubKey(offset);
+                offset += 32;
+                result.Avatar = _data.GetU8(offset);
+                offset += 1;
+                result.Name = _data.GetBytes(offset, 12);
+                offset += 12;
+                return offset - initialOffset;
+            }
         }
-    }
 
-    namespace Types
-    {
-        public partial class GameAction
+        public partial class Recipe
         {
-            public ulong ActionId { get; set; }
+            public InventoryItem[] Materials { get; set; }
 
-            public byte ActionType { get; set; }
+            public GearItem Result { get; set; }
 
-            public byte FromX { get; set; }
+            public int Serialize(byte[] _data, int initialOffset)
+            {
+                int offset = initialOffset;
+                _data.WriteS32(Materials.Length, offset);
+                offset += 4;
+                foreach (var MaterialsElement in Materials)
+                {
+                    offset += MaterialsElement.Serialize(_data, offset);
+                }
 
-            public byte FromY { get; set; }
+                offset += Result.Serialize(_data, offset);
+                return offset - initialOffset;
+            }
 
-            public byte ToX { get; set; }
+            public static int Deserialize(ReadOnlySpan<byte> _data, int initialOffset, out Recipe result)
+            {
+                int offset = initialOffset;
+                result = new Recipe();
+                int resultMaterialsLength = (int)_data.GetU32(offset);
+                offset += 4;
+                result.Materials = new InventoryItem[resultMaterialsLength];
+                for (uint resultMaterialsIdx = 0; resultMaterialsIdx < resultMaterialsLength; resultMaterialsIdx++)
+                {
+                    offset += InventoryItem.Deserialize(_data, offset, out var resultMaterialsItem);
+                    result.Materials[resultMaterialsIdx] = resultMaterialsItem;
+                }
 
-            public byte ToY { get; set; }
+                offset += GearItem.Deserialize(_data, offset, out var resultResult);
+                result.Result = resultResult;
+                return offset - initialOffset;
+            }
+        }
 
-            public TileData Tile { get; set; }
+        public partial class SeasonBadge
+        {
+            public ushort Season { get; set; }
 
-            public ulong Amount { get; set; }
+            public byte Rank { get; set; }
 
             public int Serialize(byte[] _data, int initialOffset)
             {
                 int offset = initialOffset;
-                _data.WriteU64(ActionId, offset);
-                offset += 8;
-                _data.WriteU8(ActionType, offset);
-                offset += 1;
-                _data.WriteU8(FromX, offset);
-                offset += 1;
-                _data.WriteU8(FromY, offset);
-                offset += 1;
-                _data.WriteU8(ToX, offset);
-                offset += 1;
-                _data.WriteU8(ToY, offset);
+                _data.WriteU16(Season, offset);
+                offset += 2;
+                _data.WriteU8(Rank, offset);
                 offset += 1;
-                offset += Tile.Serialize(_data, offset);
-                _data.WriteU64(Amount, offset);
-                offset += 8;
                 return offset - initialOffset;
             }
 
-            public static int Deserialize(ReadOnlySpan<byte> _data, int initialOffset, out GameAction result)
+            public static int Deserialize(ReadOnlySpan<byte> _data, int initialOffset, out SeasonBadge result)
             {
                 int offset = initialOffset;
-                result = new GameAction();
-                result.ActionId = _data.GetU64(offset);
-                offset += 8;
-                result.ActionType = _data.GetU8(offset);
-                offset += 1;
-                result.FromX = _data.GetU8(offset);
-                offset += 1;
-                result.FromY = _data.GetU8(offset);
-                offset += 1;
-                result.ToX = _data.GetU8(offset);
-                offset += 1;
-                result.ToY = _data.GetU8(offset);
+                result = new SeasonBadge();
+                result.Season = _data.GetU16(offset);
+                offset += 2;
+                result.Rank = _data.GetU8(offset);
                 offset += 1;
-                offset += TileData.Deserialize(_data, offset, out var resultTile);
-                result.Tile = resultTile;
-                result.Amount = _data.GetU64(offset);
-                offset += 8;
                 return offset - initialOffset;
             }
         }
@@ -266,6 +1141,24 @@ public partial class TileData
 
             public uint TileMaxHealth { get; set; }
 
+            public byte TileCritChance { get; set; }
+
+            public byte TileCritMultiplier { get; set; }
+
+            public byte TileEvasion { get; set; }
+
+            public byte TileDamageRange { get; set; }
+
+            public byte TileClass { get; set; }
+
+            public byte TileAvatar { get; set; }
+
+            public byte[] TileName { get; set; }
+
+            public byte TileStatus { get; set; }
+
+            public byte TileStatusTurns { get; set; }
+
             public int Serialize(byte[] _data, int initialOffset)
             {
                 int offset = initialOffset;
@@ -289,6 +1182,24 @@ public int Serialize(byte[] _data, int initialOffset)
                 offset += 4;
                 _data.WriteU32(TileMaxHealth, offset);
                 offset += 4;
+                _data.WriteU8(TileCritChance, offset);
+                offset += 1;
+                _data.WriteU8(TileCritMultiplier, offset);
+                offset += 1;
+                _data.WriteU8(TileEvasion, offset);
+                offset += 1;
+                _data.WriteU8(TileDamageRange, offset);
+                offset += 1;
+                _data.WriteU8(TileClass, offset);
+                offset += 1;
+                _data.WriteU8(TileAvatar, offset);
+                offset += 1;
+                _data.WriteSpan(TileName, offset);
+                offset += 12;
+                _data.WriteU8(TileStatus, offset);
+                offset += 1;
+                _data.WriteU8(TileStatusTurns, offset);
+                offset += 1;
                 return offset - initialOffset;
             }
 
@@ -316,6 +1227,24 @@ public static int Deserialize(ReadOnlySpan<byte> _data, int initialOffset, out T
                 offset += 4;
                 result.TileMaxHealth = _data.GetU32(offset);
                 offset += 4;
+                result.TileCritChance = _data.GetU8(offset);
+                offset += 1;
+                result.TileCritMultiplier = _data.GetU8(offset);
+                offset += 1;
+                result.TileEvasion = _data.GetU8(offset);
+                offset += 1;
+                result.TileDamageRange = _data.GetU8(offset);
+                offset += 1;
+                result.TileClass = _data.GetU8(offset);
+                offset += 1;
+                result.TileAvatar = _data.GetU8(offset);
+                offset += 1;
+                result.TileName = _data.GetBytes(offset, 12);
+                offset += 12;
+                result.TileStatus = _data.GetU8(offset);
+                offset += 1;
+                result.TileStatusTurns = _data.GetU8(offset);
+                offset += 1;
                 return offset - initialOffset;
             }
         }
@@ -342,6 +1271,24 @@ public partial class TileData2
 
             public uint TileMaxHealth { get; set; }
 
+            public byte TileCritChance { get; set; }
+
+            public byte TileCritMultiplier { get; set; }
+
+            public byte TileEvasion { get; set; }
+
+            public byte TileDamageRange { get; set; }
+
+            public byte TileClass { get; set; }
+
+            public byte TileAvatar { get; set; }
+
+            public byte[] TileName { get; set; }
+
+            public byte TileStatus { get; set; }
+
+            public byte TileStatusTurns { get; set; }
+
             public int Serialize(byte[] _data, int initialOffset)
             {
                 int offset = initialOffset;
@@ -365,6 +1312,24 @@ public int Serialize(byte[] _data, int initialOffset)
                 offset += 4;
                 _data.WriteU32(TileMaxHealth, offset);
                 offset += 4;
+                _data.WriteU8(TileCritChance, offset);
+                offset += 1;
+                _data.WriteU8(TileCritMultiplier, offset);
+                offset += 1;
+                _data.WriteU8(TileEvasion, offset);
+                offset += 1;
+                _data.WriteU8(TileDamageRange, offset);
+                offset += 1;
+                _data.WriteU8(TileClass, offset);
+                offset += 1;
+                _data.WriteU8(TileAvatar, offset);
+                offset += 1;
+                _data.WriteSpan(TileName, offset);
+                offset += 12;
+                _data.WriteU8(TileStatus, offset);
+                offset += 1;
+                _data.WriteU8(TileStatusTurns, offset);
+                offset += 1;
                 return offset - initialOffset;
             }
 
@@ -392,6 +1357,24 @@ public static int Deserialize(ReadOnlySpan<byte> _data, int initialOffset, out T
                 offset += 4;
                 result.TileMaxHealth = _data.GetU32(offset);
                 offset += 4;
+                result.TileCritChance = _data.GetU8(offset);
+                offset += 1;
+                result.TileCritMultiplier = _data.GetU8(offset);
+                offset += 1;
+                result.TileEvasion = _data.GetU8(offset);
+                offset += 1;
+                result.TileDamageRange = _data.GetU8(offset);
+                offset += 1;
+                result.TileClass = _data.GetU8(offset);
+                offset += 1;
+                result.TileAvatar = _data.GetU8(offset);
+                offset += 1;
+                result.TileName = _data.GetBytes(offset, 12);
+                offset += 12;
+                result.TileStatus = _data.GetU8(offset);
+                offset += 1;
+                result.TileStatusTurns = _data.GetU8(offset);
+                offset += 1;
                 return offset - initialOffset;
             }
         }
@@ -403,20 +1386,42 @@ public TufiaClient(IRpcClient rpcClient, IStreamingRpcClient streamingRpcClient,
         {
         }
 
-        public async Task<Solana.Unity.Programs.Models.ProgramAccountsResultWrapper<List<GameData>>> GetGameDatasAsync(string programAddress, Commitment commitment = Commitment.Finalized)
+        public async Task<Solana.Unity.Programs.Models.ProgramAccountsResultWrapper<List<FloorData>>> GetFloorDatasAsync(string programAddress, Commitment commitment = Commitment.Finalized)
+        {
+            var list = new List<Solana.Unity.Rpc.Models.MemCmp>{new Solana.Unity.Rpc.Models.MemCmp{Bytes = FloorData.ACCOUFloorDataT_DISCRIMIFloorDataATOR_B58, Offset = 0}};
+            var res = await RpcClient.GetProgramAccountsAsync(programAddress, commitment, memCmpList: list);
+            if (!res.WasSuccessful || !(res.Result?.Count > 0))
+                return new Solana.Unity.Programs.Models.ProgramAccountsResultWrapper<List<FloorData>>(res);
+            List<FloorData> resultingAccounts = new List<FloorData>(res.Result.Count);
+            resultingAccounts.AddRange(res.Result.Select(result => FloorData.Deserialize(Convert.FromBase64String(result.Account.Data[0]))));
+            return new Solana.Unity.Programs.Models.ProgramAccountsResultWrapper<List<FloorData>>(res, resultingAccounts);
+        }
+
+        public async Task<Solana.Unity.Programs.Models.ProgramAccountsResultWrapper<List<FloorDirectory>>> GetFloorDirectorysAsync(string programAddress, Commitment commitment = Commitment.Finalized)
+        {
+            var list = new List<Solana.Unity.Rpc.Models.MemCmp>{new Solana.Unity.Rpc.Models.MemCmp{Bytes = FloorDirectory.ACCOUFloorDirectoryT_DISCRIMIFloorDirectoryATOR_B58, Offset = 0}};
+            var res = await RpcClient.GetProgramAccountsAsync(programAddress, commitment, memCmpList: list);
+            if (!res.WasSuccessful || !(res.Result?.Count > 0))
+                return new Solana.Unity.Programs.Models.ProgramAccountsResultWrapper<List<FloorDirectory>>(res);
+            List<FloorDirectory> resultingAccounts = new List<FloorDirectory>(res.Result.Count);
+            resultingAccounts.AddRange(res.Result.Select(result => FloorDirectory.Deserialize(Convert.FromBase64String(result.Account.Data[0]))));
+            return new Solana.Unity.Programs.Models.ProgramAccountsResultWrapper<List<FloorDirectory>>(res, resultingAccounts);
+        }
+
+        public async Task<Solana.Unity.Programs.Models.ProgramAccountsResultWrapper<List<GameConfig>>> GetGameConfigsAsync(string programAddress, Commitment commitment = Commitment.Finalized)
         {
-            var list = new List<Solana.Unity.Rpc.Models.MemCmp>{new Solana.Unity.Rpc.Models.MemCmp{Bytes = GameData.ACCOUNT_DISCRIMINATOR_B58, Offset = 0}};
+            var list = new List<Solana.Unity.Rpc.Models.MemCmp>{new Solana.Unity.Rpc.Models.MemCmp{Bytes = GameConfig.ACCOUGameConfigT_DISCRIMIGameConfigATOR_B58, Offset = 0}};
             var res = await RpcClient.GetProgramAccountsAsync(programAddress, commitment, memCmpList: list);
             if (!res.WasSuccessful || !(res.Result?.Count > 0))
-                return new Solana.Unity.Programs.Models.ProgramAccountsResultWrapper<List<GameData>>(res);
-            List<GameData> resultingAccounts = new List<GameData>(res.Result.Count);
-            resultingAccounts.AddRange(res.Result.Select(result => GameData.Deserialize(Convert.FromBase64String(result.Account.Data[0]))));
-            return new Solana.Unity.Programs.Models.ProgramAccountsResultWrapper<List<GameData>>(res, resultingAccounts);
+                return new Solana.Unity.Programs.Models.ProgramAccountsResultWrapper<List<GameConfig>>(res);
+            List<GameConfig> resultingAccounts = new List<GameConfig>(res.Result.Count);
+            resultingAccounts.AddRange(res.Result.Select(result => GameConfig.Deserialize(Convert.FromBase64String(result.Account.Data[0]))));
+            return new Solana.Unity.Programs.Models.ProgramAccountsResultWrapper<List<GameConfig>>(res, resultingAccounts);
         }
 
         public async Task<Solana.Unity.Programs.Models.ProgramAccountsResultWrapper<List<PlayerData>>> GetPlayerDatasAsync(string programAddress, Commitment commitment = Commitment.Finalized)
         {
-            var list = new List<Solana.Unity.Rpc.Models.MemCmp>{new Solana.Unity.Rpc.Models.MemCmp{Bytes = PlayerData.ACCOUNT_DISCRIMINATOR_B58, Offset = 0}};
+            var list = new List<Solana.Unity.Rpc.Models.MemCmp>{new Solana.Unity.Rpc.Models.MemCmp{Bytes = PlayerData.ACCOUPlayerDataT_DISCRIMIPlayerDataATOR_B58, Offset = 0}};
             var res = await RpcClient.GetProgramAccountsAsync(programAddress, commitment, memCmpList: list);
             if (!res.WasSuccessful || !(res.Result?.Count > 0))
                 return new Solana.Unity.Programs.Models.ProgramAccountsResultWrapper<List<PlayerData>>(res);
@@ -425,13 +1430,31 @@ public async Task<Solana.Unity.Programs.Models.ProgramAccountsResultWrapper<List
             return new Solana.Unity.Programs.Models.ProgramAccountsResultWrapper<List<PlayerData>>(res, resultingAccounts);
         }
 
-        public async Task<Solana.Unity.Programs.Models.AccountResultWrapper<GameData>> GetGameDataAsync(string accountAddress, Commitment commitment = Commitment.Finalized)
+        public async Task<Solana.Unity.Programs.Models.AccountResultWrapper<FloorData>> GetFloorDataAsync(string accountAddress, Commitment commitment = Commitment.Finalized)
+        {
+            var res = await RpcClient.GetAccountInfoAsync(accountAddress, commitment);
+            if (!res.WasSuccessful)
+                return new Solana.Unity.Programs.Models.AccountResultWrapper<FloorData>(res);
+            var resultingAccount = FloorData.Deserialize(Convert.FromBase64String(res.Result.Value.Data[0]));
+            return new Solana.Unity.Programs.Models.AccountResultWrapper<FloorData>(res, resultingAccount);
+        }
+
+        public async Task<Solana.Unity.Programs.Models.AccountResultWrapper<FloorDirectory>> GetFloorDirectoryAsync(string accountAddress, Commitment commitment = Commitment.Finalized)
+        {
+            var res = await RpcClient.GetAccountInfoAsync(accountAddress, commitment);
+            if (!res.WasSuccessful)
+                return new Solana.Unity.Programs.Models.AccountResultWrapper<FloorDirectory>(res);
+            var resultingAccount = FloorDirectory.Deserialize(Convert.FromBase64String(res.Result.Value.Data[0]));
+            return new Solana.Unity.Programs.Models.AccountResultWrapper<FloorDirectory>(res, resultingAccount);
+        }
+
+        public async Task<Solana.Unity.Programs.Models.AccountResultWrapper<GameConfig>> GetGameConfigAsync(string accountAddress, Commitment commitment = Commitment.Finalized)
         {
             var res = await RpcClient.GetAccountInfoAsync(accountAddress, commitment);
             if (!res.WasSuccessful)
-                return new Solana.Unity.Programs.Models.AccountResultWrapper<GameData>(res);
-            var resultingAccount = GameData.Deserialize(Convert.FromBase64String(res.Result.Value.Data[0]));
-            return new Solana.Unity.Programs.Models.AccountResultWrapper<GameData>(res, resultingAccount);
+                return new Solana.Unity.Programs.Models.AccountResultWrapper<GameConfig>(res);
+            var resultingAccount = GameConfig.Deserialize(Convert.FromBase64String(res.Result.Value.Data[0]));
+            return new Solana.Unity.Programs.Models.AccountResultWrapper<GameConfig>(res, resultingAccount);
         }
 
         public async Task<Solana.Unity.Programs.Models.AccountResultWrapper<PlayerData>> GetPlayerDataAsync(string accountAddress, Commitment commitment = Commitment.Finalized)
@@ -443,13 +1466,37 @@ public async Task<Solana.Unity.Programs.Models.AccountResultWrapper<PlayerData>>
             return new Solana.Unity.Programs.Models.AccountResultWrapper<PlayerData>(res, resultingAccount);
         }
 
-        public async Task<SubscriptionState> SubscribeGameDataAsync(string accountAddress, Action<SubscriptionState, Solana.Unity.Rpc.Messages.ResponseValue<Solana.Unity.Rpc.Models.AccountInfo>, GameData> callback, Commitment commitment = Commitment.Finalized)
+        public async Task<SubscriptionState> SubscribeFloorDataAsync(string accountAddress, Action<SubscriptionState, Solana.Unity.Rpc.Messages.ResponseValue<Solana.Unity.Rpc.Models.AccountInfo>, FloorData> callback, Commitment commitment = Commitment.Finalized)
+        {
+            SubscriptionState res = await StreamingRpcClient.SubscribeAccountInfoAsync(accountAddress, (s, e) =>
+            {
+                FloorData parsingResult = null;
+                if (e.Value?.Data?.Count > 0)
+                    parsingResult = FloorData.Deserialize(Convert.FromBase64String(e.Value.Data[0]));
+                callback(s, e, parsingResult);
+            }, commitment);
+            return res;
+        }
+
+        public async Task<SubscriptionState> SubscribeFloorDirectoryAsync(string accountAddress, Action<SubscriptionState, Solana.Unity.Rpc.Messages.ResponseValue<Solana.Unity.Rpc.Models.AccountInfo>, FloorDirectory> callback, Commitment commitment = Commitment.Finalized)
+        {
+            SubscriptionState res = await StreamingRpcClient.SubscribeAccountInfoAsync(accountAddress, (s, e) =>
+            {
+                FloorDirectory parsingResult = null;
+                if (e.Value?.Data?.Count > 0)
+                    parsingResult = FloorDirectory.Deserialize(Convert.FromBase64String(e.Value.Data[0]));
+                callback(s, e, parsingResult);
+            }, commitment);
+            return res;
+        }
+
+        public async Task<SubscriptionState> SubscribeGameConfigAsync(string accountAddress, Action<SubscriptionState, Solana.Unity.Rpc.Messages.ResponseValue<Solana.Unity.Rpc.Models.AccountInfo>, GameConfig> callback, Commitment commitment = Commitment.Finalized)
         {
             SubscriptionState res = await StreamingRpcClient.SubscribeAccountInfoAsync(accountAddress, (s, e) =>
             {
-                GameData parsingResult = null;
+                GameConfig parsingResult = null;
                 if (e.Value?.Data?.Count > 0)
-                    parsingResult = GameData.Deserialize(Convert.FromBase64String(e.Value.Data[0]));
+                    parsingResult = GameConfig.Deserialize(Convert.FromBase64String(e.Value.Data[0]));
                 callback(s, e, parsingResult);
             }, commitment);
             return res;
@@ -467,33 +1514,33 @@ public async Task<SubscriptionState> SubscribePlayerDataAsync(string accountAddr
             return res;
         }
 
-        public async Task<RequestResult<string>> SendInitPlayerAsync(InitPlayerAccounts accounts, string levelSeed, PublicKey feePayer, Func<byte[], PublicKey, byte[]> signingCallback, PublicKey programId)
+        public async Task<RequestResult<string>> SendInitPlayerAsync(InitPlayerAccounts accounts, byte @class, PublicKey feePayer, Func<byte[], PublicKey, byte[]> signingCallback, PublicKey programId)
         {
-            Solana.Unity.Rpc.Models.TransactionInstruction instr = Program.TufiaProgram.InitPlayer(accounts, levelSeed, programId);
+            Solana.Unity.Rpc.Models.TransactionInstruction instr = Program.TufiaProgram.InitPlayer(accounts, @class, programId);
             return await SignAndSendTransaction(instr, feePayer, signingCallback);
         }
 
-        public async Task<RequestResult<string>> SendMoveToTileAsync(MoveToTileAccounts accounts, string levelSeed, ushort counter, ulong x, ulong y, PublicKey feePayer, Func<byte[], PublicKey, byte[]> signingCallback, PublicKey programId)
+        public async Task<RequestResult<string>> SendMoveToTileAsync(MoveToTileAccounts accounts, FloorAddress floor, ushort counter, ulong x, ulong y, PublicKey feePayer, Func<byte[], PublicKey, byte[]> signingCallback, PublicKey programId)
         {
-            Solana.Unity.Rpc.Models.TransactionInstruction instr = Program.TufiaProgram.MoveToTile(accounts, levelSeed, counter, x, y, programId);
+            Solana.Unity.Rpc.Models.TransactionInstruction instr = Program.TufiaProgram.MoveToTile(accounts, floor, counter, x, y, programId);
             return await SignAndSendTransaction(instr, feePayer, signingCallback);
         }
 
-        public async Task<RequestResult<string>> SendMoveToNextFloorAsync(MoveToNextFloorAccounts accounts, string levelSeed, ushort counter, PublicKey feePayer, Func<byte[], PublicKey, byte[]> signingCallback, PublicKey programId)
+        public async Task<RequestResult<string>> SendMoveToNextFloorAsync(MoveToNextFloorAccounts accounts, FloorAddress floor, ushort counter, PublicKey feePayer, Func<byte[], PublicKey, byte[]> signingCallback, PublicKey programId)
         {
-            Solana.Unity.Rpc.Models.TransactionInstruction instr = Program.TufiaProgram.MoveToNextFloor(accounts, levelSeed, counter, programId);
+            Solana.Unity.Rpc.Models.TransactionInstruction instr = Program.TufiaProgram.MoveToNextFloor(accounts, floor, counter, programId);
             return await SignAndSendTransaction(instr, feePayer, signingCallback);
         }
 
-        public async Task<RequestResult<string>> SendBuyNextFloorAsync(BuyNextFloorAccounts accounts, string levelSeed, ushort counter, PublicKey feePayer, Func<byte[], PublicKey, byte[]> signingCallback, PublicKey programId)
+        public async Task<RequestResult<string>> SendBuyNextFloorAsync(BuyNextFloorAccounts accounts, FloorAddress floor, ushort counter, PublicKey feePayer, Func<byte[], PublicKey, byte[]> signingCallback, PublicKey programId)
         {
-            Solana.Unity.Rpc.Models.TransactionInstruction instr = Program.TufiaProgram.BuyNextFloor(accounts, levelSeed, counter, programId);
+            Solana.Unity.Rpc.Models.TransactionInstruction instr = Program.TufiaProgram.BuyNextFloor(accounts, floor, counter, programId);
             return await SignAndSendTransaction(instr, feePayer, signingCallback);
         }
 
-        public async Task<RequestResult<string>> SendResetFloorAsync(ResetFloorAccounts accounts, string levelSeed, ushort counter, PublicKey feePayer, Func<byte[], PublicKey, byte[]> signingCallback, PublicKey programId)
+        public async Task<RequestResult<string>> SendResetFloorAsync(ResetFloorAccounts accounts, FloorAddress floor, ushort counter, PublicKey feePayer, Func<byte[], PublicKey, byte[]> signingCallback, PublicKey programId)
         {
-            Solana.Unity.Rpc.Models.TransactionInstruction instr = Program.TufiaProgram.ResetFloor(accounts, levelSeed, counter, programId);
+            Solana.Unity.Rpc.Models.TransactionInstruction instr = Program.TufiaProgram.ResetFloor(accounts, floor, counter, programId);
             return await SignAndSendTransaction(instr, feePayer, signingCallback);
         }
 
@@ -509,7 +1556,7 @@ public class InitPlayerAccounts
         {
             public PublicKey Player { get; set; }
 
-            public PublicKey GameData { get; set; }
+            public PublicKey Config { get; set; }
 
             public PublicKey Signer { get; set; }
 
@@ -522,8 +1569,14 @@ public class MoveToTileAccounts
 
             public PublicKey Player { get; set; }
 
+            public PublicKey Config { get; set; }
+
             public PublicKey GameData { get; set; }
 
+            public PublicKey Leaderboard { get; set; }
+
+            public PublicKey Party { get; set; }
+
             public PublicKey Signer { get; set; }
 
             public PublicKey SystemProgram { get; set; }
@@ -535,8 +1588,14 @@ public class MoveToNextFloorAccounts
 
             public PublicKey Player { get; set; }
 
+            public PublicKey Config { get; set; }
+
             public PublicKey GameData { get; set; }
 
+            public PublicKey Directory { get; set; }
+
+            public PublicKey Leaderboard { get; set; }
+
             public PublicKey Signer { get; set; }
 
             public PublicKey SystemProgram { get; set; }
@@ -548,8 +1607,16 @@ public class BuyNextFloorAccounts
 
             public PublicKey Player { get; set; }
 
+            public PublicKey Config { get; set; }
+
+            public PublicKey Directory { get; set; }
+
             public PublicKey GameData { get; set; }
 
+            public PublicKey OldGameData { get; set; }
+
+            public PublicKey Party { get; set; }
+
             public PublicKey Signer { get; set; }
 
             public PublicKey SystemProgram { get; set; }
@@ -561,6 +1628,8 @@ public class ResetFloorAccounts
 
             public PublicKey Player { get; set; }
 
+            public PublicKey Config { get; set; }
+
             public PublicKey GameData { get; set; }
 
             public PublicKey Signer { get; set; }
@@ -570,29 +1639,30 @@ public class ResetFloorAccounts
 
         public static class TufiaProgram
         {
-            public static Solana.Unity.Rpc.Models.TransactionInstruction InitPlayer(InitPlayerAccounts accounts, string levelSeed, PublicKey programId)
+            public static Solana.Unity.Rpc.Models.TransactionInstruction InitPlayer(InitPlayerAccounts accounts, byte @class, PublicKey programId)
             {
                 List<Solana.Unity.Rpc.Models.AccountMeta> keys = new()
-                {Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Player, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.GameData, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Signer, true), Solana.Unity.Rpc.Models.AccountMeta.ReadOnly(accounts.SystemProgram, false)};
+                {Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Player, false), Solana.Unity.Rpc.Models.AccountMeta.ReadOnly(accounts.Config, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Signer, true), Solana.Unity.Rpc.Models.AccountMeta.ReadOnly(accounts.SystemProgram, false)};
                 byte[] _data = new byte[1200];
                 int offset = 0;
                 _data.WriteU64(4819994211046333298UL, offset);
                 offset += 8;
-                offset += _data.WriteBorshString(levelSeed, offset);
+                _data.WriteU8(@class, offset);
+                offset += 1;
                 byte[] resultData = new byte[offset];
                 Array.Copy(_data, resultData, offset);
                 return new Solana.Unity.Rpc.Models.TransactionInstruction{Keys = keys, ProgramId = programId.KeyBytes, Data = resultData};
             }
 
-            public static Solana.Unity.Rpc.Models.TransactionInstruction MoveToTile(MoveToTileAccounts accounts, string levelSeed, ushort counter, ulong x, ulong y, PublicKey programId)
+            public static Solana.Unity.Rpc.Models.TransactionInstruction MoveToTile(MoveToTileAccounts accounts, FloorAddress floor, ushort counter, ulong x, ulong y, PublicKey programId)
             {
                 List<Solana.Unity.Rpc.Models.AccountMeta> keys = new()
-                {Solana.Unity.Rpc.Models.AccountMeta.ReadOnly(accounts.SessionToken == null ? programId : accounts.SessionToken, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Player, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.GameData, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Signer, true), Solana.Unity.Rpc.Models.AccountMeta.ReadOnly(accounts.SystemProgram, false)};
+                {Solana.Unity.Rpc.Models.AccountMeta.ReadOnly(accounts.SessionToken == null ? programId : accounts.SessionToken, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Player, false), Solana.Unity.Rpc.Models.AccountMeta.ReadOnly(accounts.Config, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.GameData, false), accounts.Leaderboard == null ? Solana.Unity.Rpc.Models.AccountMeta.ReadOnly(programId, false) : Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Leaderboard, false), accounts.Party == null ? Solana.Unity.Rpc.Models.AccountMeta.ReadOnly(programId, false) : Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Party, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Signer, true), Solana.Unity.Rpc.Models.AccountMeta.ReadOnly(accounts.SystemProgram, false)};
                 byte[] _data = new byte[1200];
                 int offset = 0;
                 _data.WriteU64(5279588053843574468UL, offset);
                 offset += 8;
-                offset += _data.WriteBorshString(levelSeed, offset);
+                offset += floor.Serialize(_data, offset);
                 _data.WriteU16(counter, offset);
                 offset += 2;
                 _data.WriteU64(x, offset);
@@ -604,15 +1674,15 @@ public static Solana.Unity.Rpc.Models.TransactionInstruction MoveToTile(MoveToTi
                 return new Solana.Unity.Rpc.Models.TransactionInstruction{Keys = keys, ProgramId = programId.KeyBytes, Data = resultData};
             }
 
-            public static Solana.Unity.Rpc.Models.TransactionInstruction MoveToNextFloor(MoveToNextFloorAccounts accounts, string levelSeed, ushort counter, PublicKey programId)
+            public static Solana.Unity.Rpc.Models.TransactionInstruction MoveToNextFloor(MoveToNextFloorAccounts accounts, FloorAddress floor, ushort counter, PublicKey programId)
             {
                 List<Solana.Unity.Rpc.Models.AccountMeta> keys = new()
-                {Solana.Unity.Rpc.Models.AccountMeta.ReadOnly(accounts.SessionToken == null ? programId : accounts.SessionToken, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Player, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.GameData, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Signer, true), Solana.Unity.Rpc.Models.AccountMeta.ReadOnly(accounts.SystemProgram, false)};
+                {Solana.Unity.Rpc.Models.AccountMeta.ReadOnly(accounts.SessionToken == null ? programId : accounts.SessionToken, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Player, false), Solana.Unity.Rpc.Models.AccountMeta.ReadOnly(accounts.Config, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.GameData, false), accounts.Directory == null ? Solana.Unity.Rpc.Models.AccountMeta.ReadOnly(programId, false) : Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Directory, false), accounts.Leaderboard == null ? Solana.Unity.Rpc.Models.AccountMeta.ReadOnly(programId, false) : Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Leaderboard, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Signer, true), Solana.Unity.Rpc.Models.AccountMeta.ReadOnly(accounts.SystemProgram, false)};
                 byte[] _data = new byte[1200];
                 int offset = 0;
                 _data.WriteU64(2481710203484716370UL, offset);
                 offset += 8;
-                offset += _data.WriteBorshString(levelSeed, offset);
+                offset += floor.Serialize(_data, offset);
                 _data.WriteU16(counter, offset);
                 offset += 2;
                 byte[] resultData = new byte[offset];
@@ -620,15 +1690,15 @@ public static Solana.Unity.Rpc.Models.TransactionInstruction MoveToNextFloor(Mov
                 return new Solana.Unity.Rpc.Models.TransactionInstruction{Keys = keys, ProgramId = programId.KeyBytes, Data = resultData};
             }
 
-            public static Solana.Unity.Rpc.Models.TransactionInstruction BuyNextFloor(BuyNextFloorAccounts accounts, string levelSeed, ushort counter, PublicKey programId)
+            public static Solana.Unity.Rpc.Models.TransactionInstruction BuyNextFloor(BuyNextFloorAccounts accounts, FloorAddress floor, ushort counter, PublicKey programId)
             {
                 List<Solana.Unity.Rpc.Models.AccountMeta> keys = new()
-                {Solana.Unity.Rpc.Models.AccountMeta.ReadOnly(accounts.SessionToken == null ? programId : accounts.SessionToken, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Player, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.GameData, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Signer, true), Solana.Unity.Rpc.Models.AccountMeta.ReadOnly(accounts.SystemProgram, false)};
+                {Solana.Unity.Rpc.Models.AccountMeta.ReadOnly(accounts.SessionToken == null ? programId : accounts.SessionToken, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Player, false), Solana.Unity.Rpc.Models.AccountMeta.ReadOnly(accounts.Config, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Directory, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.GameData, false), accounts.OldGameData == null ? Solana.Unity.Rpc.Models.AccountMeta.ReadOnly(programId, false) : Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.OldGameData, false), accounts.Party == null ? Solana.Unity.Rpc.Models.AccountMeta.ReadOnly(programId, false) : Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Party, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Signer, true), Solana.Unity.Rpc.Models.AccountMeta.ReadOnly(accounts.SystemProgram, false)};
                 byte[] _data = new byte[1200];
                 int offset = 0;
                 _data.WriteU64(7033119409976356332UL, offset);
                 offset += 8;
-                offset += _data.WriteBorshString(levelSeed, offset);
+                offset += floor.Serialize(_data, offset);
                 _data.WriteU16(counter, offset);
                 offset += 2;
                 byte[] resultData = new byte[offset];
@@ -636,15 +1706,15 @@ public static Solana.Unity.Rpc.Models.TransactionInstruction BuyNextFloor(BuyNex
                 return new Solana.Unity.Rpc.Models.TransactionInstruction{Keys = keys, ProgramId = programId.KeyBytes, Data = resultData};
             }
 
-            public static Solana.Unity.Rpc.Models.TransactionInstruction ResetFloor(ResetFloorAccounts accounts, string levelSeed, ushort counter, PublicKey programId)
+            public static Solana.Unity.Rpc.Models.TransactionInstruction ResetFloor(ResetFloorAccounts accounts, FloorAddress floor, ushort counter, PublicKey programId)
             {
                 List<Solana.Unity.Rpc.Models.AccountMeta> keys = new()
-                {Solana.Unity.Rpc.Models.AccountMeta.ReadOnly(accounts.SessionToken == null ? programId : accounts.SessionToken, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Player, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.GameData, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Signer, true), Solana.Unity.Rpc.Models.AccountMeta.ReadOnly(accounts.SystemProgram, false)};
+                {Solana.Unity.Rpc.Models.AccountMeta.ReadOnly(accounts.SessionToken == null ? programId : accounts.SessionToken, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Player, false), Solana.Unity.Rpc.Models.AccountMeta.ReadOnly(accounts.Config, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.GameData, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Signer, true), Solana.Unity.Rpc.Models.AccountMeta.ReadOnly(accounts.SystemProgram, false)};
                 byte[] _data = new byte[1200];
                 int offset = 0;
                 _data.WriteU64(7303134863959624869UL, offset);
                 offset += 8;
-                offset += _data.WriteBorshString(levelSeed, offset);
+                offset += floor.Serialize(_data, offset);
                 _data.WriteU16(counter, offset);
                 offset += 2;
                 byte[] resultData = new byte[offset];